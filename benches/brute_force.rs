@@ -0,0 +1,36 @@
+//! Compares the search, which skips the pairs of points covered by earlier candidates, with
+//! checking every candidate line on a large regular polygon, printing the time of each. Timings
+//! vary between machines and runs, so they are reported, not asserted; that both find the same
+//! axes is tested by `alg::tests::test_matches_brute_force`.
+//!
+//! Run with `cargo bench --bench brute_force`.
+
+use num_traits::Float;
+use rust_symm::alg::{candidate_axes, get_lines_of_sym_or_empty, verify_axis};
+use rust_symm::collections::HashSet;
+use rust_symm::model::{Line, Point};
+use std::time::Instant;
+
+fn main() {
+    let n = 500;
+    let polygon: HashSet<Point> = (0..n)
+        .map(|k| {
+            let (sin, cos) = Float::sin_cos(k as f64 * core::f64::consts::TAU / n as f64);
+            Point::new(10. * cos, 10. * sin)
+        })
+        .collect();
+
+    let start = Instant::now();
+    let lines = get_lines_of_sym_or_empty(&polygon, None);
+    let search_time = start.elapsed();
+
+    let start = Instant::now();
+    let expected: Vec<Line> = candidate_axes(&polygon).filter(|l| verify_axis(&polygon, l)).collect();
+    let brute_force_time = start.elapsed();
+
+    assert_eq!(lines.len(), n);
+    assert!(expected.iter().all(|e| lines.iter().any(|l| l.approx_eq(e, 1e-9))));
+    println!("{} points, {} axes", polygon.len(), lines.len());
+    println!("search:      {:?}", search_time);
+    println!("brute force: {:?}", brute_force_time);
+}
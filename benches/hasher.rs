@@ -0,0 +1,41 @@
+//! Compares the default hasher against foldhash on a grid of 5000 points, printing the time of
+//! each search. Timings vary between machines and runs, so they are reported, not asserted.
+//!
+//! Run with `cargo bench --bench hasher`.
+
+use core::hash::BuildHasher;
+use rust_symm::alg::{get_lines_of_sym, verify_axis};
+use rust_symm::collections::HashSet;
+use rust_symm::model::Point;
+use std::time::Instant;
+
+/// Builds a `w` by `h` grid of integer points centered on the origin, so that its reflections
+/// are exact.
+fn centered_grid<S: BuildHasher + Default>(w: usize, h: usize) -> HashSet<Point, S> {
+    let mut points = HashSet::with_hasher(S::default());
+    for i in 0..w {
+        for j in 0..h {
+            points.insert(Point::new((2 * i) as f64 - (w - 1) as f64, (2 * j) as f64 - (h - 1) as f64));
+        }
+    }
+    points
+}
+
+fn main() {
+    let default: HashSet<Point> = centered_grid(100, 50);
+    let fast: HashSet<Point, foldhash::fast::RandomState> = centered_grid(100, 50);
+
+    let start = Instant::now();
+    let default_lines = get_lines_of_sym(&default, Some(false)).unwrap();
+    let default_time = start.elapsed();
+
+    let start = Instant::now();
+    let fast_lines = get_lines_of_sym(&fast, Some(false)).unwrap();
+    let fast_time = start.elapsed();
+
+    assert_eq!(default_lines.len(), fast_lines.len());
+    assert!(fast_lines.iter().all(|l| verify_axis(&default, l)));
+    println!("{} points, {} axes", default.len(), default_lines.len());
+    println!("default hasher: {:?}", default_time);
+    println!("foldhash:       {:?}", fast_time);
+}
@@ -0,0 +1,9 @@
+//! The `rust_symm` Python extension module, with the functions of `rust_symm::python`.
+
+use pyo3::prelude::*;
+
+#[pymodule]
+#[pyo3(name = "rust_symm")]
+fn rust_symm_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    rust_symm::python::register(m)
+}
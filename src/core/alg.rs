@@ -1,5 +1,277 @@
-use crate::model::{Line, Point, UnorderedPointPair};
-use std::collections::{HashMap, HashSet};
+use crate::cache::ReflectionCache;
+use crate::collections::{DefaultHashBuilder, HashMap, HashSet};
+use crate::config::{self, PointEquality, Scalar, SymmetryOptions};
+use crate::error::SymmError;
+use crate::model::{LabeledPoint, Line, Point, UnorderedPointPair, WeightedPoint};
+use crate::util;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::hash::{BuildHasher, Hash};
+use core::mem;
+use core::ops::ControlFlow;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+// Called by path on `f64`, whose inherent float methods need `std`.
+use num_traits::Float;
+
+/// The outcome of a line-of-symmetry search run with `get_lines_of_sym_with`.
+#[derive(Debug)]
+pub struct SymmetryResult<T = f64, S = DefaultHashBuilder> {
+    /// The lines of symmetry found. If the search was cancelled, this holds only
+    /// the lines found before cancellation.
+    pub lines: HashSet<Line<T>, S>,
+    /// Whether the search was stopped early by the options' cancellation flag or deadline.
+    pub cancelled: bool,
+}
+
+/// A set of lines of symmetry, e.g. the `lines` of a `SymmetryResult`, with queries that compare
+/// the lines within tolerance.
+///
+/// A `HashSet`'s `contains` finds a line by its hash, which equal lines computed differently can
+/// miss if rounding puts them in different cells of the hash's grid. `contains_axis` compares
+/// the line with each one of the set instead.
+#[derive(Debug)]
+pub struct AxisSet<T = f64, S = DefaultHashBuilder> {
+    lines: HashSet<Line<T>, S>,
+}
+
+impl<T: Scalar, S: BuildHasher> AxisSet<T, S> {
+    /// Wraps a set of lines.
+    pub fn new(lines: HashSet<Line<T>, S>) -> Self {
+        Self { lines }
+    }
+
+    /// Returns the number of lines.
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Returns true if the set has no lines.
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Returns the orientations of the lines in radians, as by `Line::angle_rad`, in increasing
+    /// order.
+    pub fn angles(&self) -> Vec<T> {
+        let mut angles: Vec<T> = self.lines.iter().map(Line::angle_rad).collect();
+        angles.sort_by(|a1, a2| a1.partial_cmp(a2).unwrap_or(core::cmp::Ordering::Equal));
+        angles
+    }
+
+    /// Checks whether the set holds a line equal to `line` within `config::default_epsilon`, as by
+    /// `Line::approx_eq`, however either equation is scaled or signed.
+    pub fn contains_axis(&self, line: &Line<T>) -> bool {
+        self.lines.iter().any(|l| l.approx_eq(line, config::default_epsilon()))
+    }
+
+    /// Returns an iterator over the lines, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &Line<T>> {
+        self.lines.iter()
+    }
+
+    /// Returns the wrapped set of lines.
+    pub fn into_inner(self) -> HashSet<Line<T>, S> {
+        self.lines
+    }
+}
+
+impl<T: Scalar, S: BuildHasher> From<HashSet<Line<T>, S>> for AxisSet<T, S> {
+    fn from(lines: HashSet<Line<T>, S>) -> Self {
+        Self::new(lines)
+    }
+}
+
+/// Configures and runs a line-of-symmetry search in one expression, as a fluent alternative to
+/// building `SymmetryOptions` for `get_lines_of_sym_with`:
+///
+/// ```
+/// use rust_symm::alg::Detector;
+/// use rust_symm::collections::HashSet;
+/// use rust_symm::model::Point;
+///
+/// let triangle = [Point::new(0., 0.), Point::new(2., 0.), Point::new(1., 3.)];
+/// let points: HashSet<Point> = triangle.into_iter().collect();
+/// let axes = Detector::new().tolerance(1e-6).high_degree(false).detect(&points);
+/// assert_eq!(axes.len(), 1);
+/// ```
+///
+/// Options without a setter of their own can be set on the `SymmetryOptions` given to
+/// `with_options`.
+#[derive(Debug, Clone, Default)]
+pub struct Detector {
+    opts: SymmetryOptions,
+}
+
+impl Detector {
+    /// Creates a detector with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a detector with the given options.
+    pub fn with_options(opts: SymmetryOptions) -> Self {
+        Self { opts }
+    }
+
+    /// Sets the tolerance of the search, in the input's units. See `SymmetryOptions::epsilon`.
+    pub fn tolerance(mut self, tol: f64) -> Self {
+        self.opts.epsilon = Some(tol);
+        self
+    }
+
+    /// Sets whether the points are expected to have a high degree of partial symmetry. See
+    /// `SymmetryOptions::high_degree_expected`.
+    pub fn high_degree(mut self, expected: bool) -> Self {
+        self.opts.high_degree_expected = expected;
+        self
+    }
+
+    /// Sets whether the line through collinear points is reported as an axis. See
+    /// `SymmetryOptions::include_through_line`.
+    pub fn include_through_line(mut self, include: bool) -> Self {
+        self.opts.include_through_line = include;
+        self
+    }
+
+    /// Returns the options the detector searches with.
+    pub fn options(&self) -> &SymmetryOptions {
+        &self.opts
+    }
+
+    /// Returns the lines of symmetry of `points`, as found by `get_lines_of_sym_with`.
+    pub fn detect<T: Scalar, S: BuildHasher + Default>(
+        &self,
+        points: &HashSet<Point<T>, S>,
+    ) -> AxisSet<T, S> {
+        AxisSet::new(get_lines_of_sym_with(points, &self.opts).lines)
+    }
+}
+
+/// Buffers for line-of-symmetry searches, reused from one search to the next by callers running
+/// many of them, e.g. on thousands of small sets in a batch.
+///
+/// `get_lines_of_sym_with` allocates the sets and records of its search afresh on every call:
+/// the lines found, the normalized copy of the points, which points are matched with their
+/// reflections, and which pairs of points are covered by earlier candidates. `detect_into`
+/// clears them instead, keeping their allocations for the next search.
+#[derive(Debug)]
+pub struct Workspace<T = f64, S = DefaultHashBuilder> {
+    /// The lines returned by the last search.
+    lines: HashSet<Line<T>, S>,
+    /// The lines found by the last search in the normalized coordinates of the points.
+    search_lines: HashSet<Line<T>, S>,
+    unit_points: HashSet<Point<T>, S>,
+    matched: Vec<usize>,
+    covered: PairBitset,
+    /// The bits of the pairs covered in the sparse mode of `SymmetryOptions::candidate_neighbours`.
+    covered_among: Vec<u64>,
+}
+
+impl<T: Scalar, S: BuildHasher + Default> Workspace<T, S> {
+    /// Creates a workspace with empty buffers.
+    pub fn new() -> Self {
+        Self {
+            lines: HashSet::with_hasher(S::default()),
+            search_lines: HashSet::with_hasher(S::default()),
+            unit_points: HashSet::with_hasher(S::default()),
+            matched: Vec::new(),
+            covered: PairBitset::new(0),
+            covered_among: Vec::new(),
+        }
+    }
+
+    /// Returns the lines of symmetry of `points`, as found by `get_lines_of_sym_with`, in a set
+    /// held by the workspace until the next search. If the search is cancelled, this holds only
+    /// the lines found before cancellation.
+    pub fn detect_into(&mut self, points: &HashSet<Point<T>, S>, opts: &SymmetryOptions) -> &HashSet<Line<T>, S> {
+        let result = search_normalized(points, opts, &mut SymmetryStats::default(), None, self, &mut keep_searching);
+        self.lines = result.lines;
+        &self.lines
+    }
+}
+
+impl<T: Scalar, S: BuildHasher + Default> Default for Workspace<T, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Counts of the work done by a line-of-symmetry search, returned by `get_lines_of_sym_with_stats`.
+///
+/// Collinear inputs are handled without generating candidates from pairs of points, so only
+/// `axes_found` is counted for them. With the `std` feature, the axes of a regular polygon are
+/// taken without a search, and only one candidate is counted per axis, so `candidates_generated`
+/// differs between builds with and without `std` for such inputs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymmetryStats {
+    /// Candidate lines generated from pairs of points.
+    pub candidates_generated: usize,
+    /// Candidate lines rejected before checking the points against them, by
+    /// `SymmetryOptions::prune_by_centroid` or `SymmetryOptions::prune_by_bounding_box`.
+    pub candidates_pruned: usize,
+    /// Lines of symmetry returned.
+    pub axes_found: usize,
+}
+
+/// The time a line-of-symmetry search spent in each of its phases, returned by
+/// `get_lines_of_sym_timed`.
+///
+/// The phases don't cover the setup of the search, such as normalizing the points, so their sum
+/// is less than the total time. Collinear inputs are handled without pairs of points, so only
+/// `through_line` is recorded for them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timing {
+    /// Generating candidate lines from pairs of points, including skipping the pairs covered by
+    /// earlier candidates and pruning candidates.
+    pub pair_generation: Duration,
+    /// Checking the candidate lines against the points, including the final check of the lines
+    /// found.
+    pub candidate_evaluation: Duration,
+    /// Checking whether the points are collinear, and finding the axes of collinear points.
+    pub through_line: Duration,
+}
+
+/// A clock for timing a phase of a search when its `Timing` is recorded. Without `std` there is
+/// no clock, and every phase takes no time.
+struct PhaseClock {
+    #[cfg(feature = "std")]
+    start: Option<Instant>,
+}
+
+impl PhaseClock {
+    fn start(timing: &Option<&mut Timing>) -> Self {
+        #[cfg(not(feature = "std"))]
+        let _ = timing;
+        Self {
+            #[cfg(feature = "std")]
+            start: timing.is_some().then(Instant::now),
+        }
+    }
+
+    /// Adds the time since the clock started to the phase of `timing` chosen by `phase`.
+    fn stop(self, timing: &mut Option<&mut Timing>, phase: fn(&mut Timing) -> &mut Duration) {
+        #[cfg(feature = "std")]
+        if let (Some(start), Some(timing)) = (self.start, timing.as_deref_mut()) {
+            *phase(timing) += start.elapsed();
+        }
+        #[cfg(not(feature = "std"))]
+        let _ = (timing, phase);
+    }
+}
+
+/// The lines of symmetry of a set of points, as returned by `get_lines_of_sym_or_infinite`.
+#[derive(Debug)]
+pub enum LinesOfSym<T = f64, S = DefaultHashBuilder> {
+    /// The set has finitely many lines of symmetry, possibly none.
+    Finite(HashSet<Line<T>, S>),
+    /// Every line through the set's single location is a line of symmetry.
+    Infinite,
+}
 
 /// Returns all lines of symmetry for a given set of points.
 ///
@@ -16,148 +288,5151 @@ use std::collections::{HashMap, HashSet};
 ///
 /// # Returns
 ///
-/// A `HashSet` containing all lines of symmetry for the input set of points.
+/// A `HashSet` containing all lines of symmetry for the input set of points, which is empty if
+/// the set has none.
+///
+/// # Errors
+///
+/// Returns `SymmError::TooFewPoints` if the set has fewer than two points, and
+/// `SymmError::DegenerateInput` if all of its points coincide within tolerance. Use
+/// `get_lines_of_sym_or_empty` to get an empty set in these cases instead.
 ///
 /// # Notes
 ///
-/// - At least two points are required to define a line of symmetry.
 /// - The function uses tolerance-based floating-point comparisons to account for imprecision.
-/// - If the input set contains fewer than two points, an empty set is returned and a warning is printed.
-/// ```
-pub fn get_lines_of_sym(points: &HashSet<Point>, high_degree_expected: Option<bool>) -> HashSet<Line> {
-    // Returns a set of lines of symmetry for the given set of points.
-    let high_degree_expected = high_degree_expected.unwrap_or(true);
+///   The points are first moved to be centered on the origin with an extent near 1 (see
+///   `get_lines_of_sym_with`), so the tolerance is relative to the size of the set.
+/// - The internal sets and the returned set use the input set's hasher, so a faster `BuildHasher`
+///   than the default SipHash can be supplied by building the input set with it.
+pub fn get_lines_of_sym<T: Scalar, S: BuildHasher + Default>(
+    points: &HashSet<Point<T>, S>,
+    high_degree_expected: Option<bool>,
+) -> Result<HashSet<Line<T>, S>, SymmError> {
+    let mut iter = points.iter();
+    let (Some(first), Some(_)) = (iter.next(), iter.next()) else {
+        return Err(SymmError::TooFewPoints);
+    };
+    if points.iter().all(|p| p.distance(first) <= config::default_epsilon()) {
+        return Err(SymmError::DegenerateInput);
+    }
 
-    let mut lines_set: HashSet<Line> = HashSet::new();
+    Ok(get_lines_of_sym_or_empty(points, high_degree_expected))
+}
 
-    if points.len() < 2 {
-        eprintln!("Warning: at least 2 points needed to find lines of symmetry.");
-        return lines_set;
+/// Like `get_lines_of_sym`, but returns an empty set, printing a warning if fewer than two points
+/// are given, where `get_lines_of_sym` returns an error.
+pub fn get_lines_of_sym_or_empty<T: Scalar, S: BuildHasher + Default>(
+    points: &HashSet<Point<T>, S>,
+    high_degree_expected: Option<bool>,
+) -> HashSet<Line<T>, S> {
+    let opts = SymmetryOptions {
+        high_degree_expected: high_degree_expected.unwrap_or(true),
+        ..SymmetryOptions::default()
+    };
+
+    get_lines_of_sym_with(points, &opts).lines
+}
+
+/// Returns all lines of symmetry for a given set of points, as configured by `opts`.
+///
+/// The candidate loop checks `opts.cancel_flag` and `opts.deadline` before each candidate
+/// line. If either requests a stop, the lines found so far are returned with
+/// `cancelled` set to `true`.
+///
+/// Before the search, the points are translated to put their centroid at the origin and scaled
+/// by a power of two to an extent near 1, and the lines found are mapped back to the input's
+/// coordinates. Without this, reflections of points with large coordinates (e.g. around `1e7`)
+/// are off by more than the tolerance, and points much closer than the tolerance in a tiny set
+/// would be merged. The default tolerance thus applies relative to the extent of the set, while
+/// the tolerances given in `opts` are in the input's units.
+pub fn get_lines_of_sym_with<T: Scalar, S: BuildHasher + Default>(
+    points: &HashSet<Point<T>, S>,
+    opts: &SymmetryOptions,
+) -> SymmetryResult<T, S> {
+    search_normalized(points, opts, &mut SymmetryStats::default(), None, &mut Workspace::new(), &mut keep_searching)
+}
+
+/// Like `get_lines_of_sym_with`, but also returns counts of the candidate lines generated and
+/// pruned and of the axes found.
+pub fn get_lines_of_sym_with_stats<T: Scalar, S: BuildHasher + Default>(
+    points: &HashSet<Point<T>, S>,
+    opts: &SymmetryOptions,
+) -> (SymmetryResult<T, S>, SymmetryStats) {
+    let mut stats = SymmetryStats::default();
+    let result = search_normalized(points, opts, &mut stats, None, &mut Workspace::new(), &mut keep_searching);
+    (result, stats)
+}
+
+/// Returns all lines of symmetry of the points whose entry in `mask` is `true`, as configured by
+/// `opts`, e.g. to leave out suspected noise points without building a new set for each choice.
+///
+/// # Panics
+///
+/// Panics if `mask` and `points` differ in length.
+pub fn get_lines_of_sym_masked<T: Scalar>(
+    points: &[Point<T>],
+    mask: &[bool],
+    opts: &SymmetryOptions,
+) -> HashSet<Line<T>> {
+    assert_eq!(points.len(), mask.len(), "the mask must have one entry per point");
+    let masked: HashSet<Point<T>> =
+        points.iter().zip(mask).filter(|(_, &included)| included).map(|(p, _)| *p).collect();
+    get_lines_of_sym_with(&masked, opts).lines
+}
+
+/// Like `get_lines_of_sym_with`, but also returns the time spent in each phase of the search, to
+/// diagnose slow inputs or compare the effect of options such as `prune_by_centroid`. Not
+/// available without the `std` feature.
+///
+/// Timing the phases reads the clock for every pair of points, which slows the search slightly.
+#[cfg(feature = "std")]
+pub fn get_lines_of_sym_timed<T: Scalar, S: BuildHasher + Default>(
+    points: &HashSet<Point<T>, S>,
+    opts: &SymmetryOptions,
+) -> (SymmetryResult<T, S>, Timing) {
+    let mut timing = Timing::default();
+    let mut stats = SymmetryStats::default();
+    let ws = &mut Workspace::new();
+    let result = search_normalized(points, opts, &mut stats, Some(&mut timing), ws, &mut keep_searching);
+    (result, timing)
+}
+
+/// A translation and scaling that moves a set of points to be centered on the origin with a
+/// bounding box whose longer side is near 1, for searching in coordinates where the tolerance is
+/// well matched to the set.
+struct Normalization<T> {
+    center: Point<T>,
+    /// A power of two, so that scaling is exact.
+    scale: T,
+}
+
+impl<T: Scalar> Normalization<T> {
+    /// Returns the normalization of `points`, or `None` if their centroid isn't finite or they all
+    /// coincide with it.
+    fn of(points: &[&Point<T>]) -> Option<Self> {
+        let center = centroid(points)?;
+        let (min, max) = points.iter().fold(((center.x, center.y), (center.x, center.y)), |(min, max), p| {
+            ((min.0.min(p.x), min.1.min(p.y)), (max.0.max(p.x), max.1.max(p.y)))
+        });
+        // The longer side of the bounding box.
+        let extent = (max.0 - min.0).max(max.1 - min.1);
+        if !extent.is_normal() {
+            return None;
+        }
+
+        let two = T::one() + T::one();
+        Some(Self { center, scale: two.powi(extent.log2().round().to_i32()?) })
     }
 
-    // A set of pairs of points that can be used to generate candidate lines of symmetry.
-    let mut e_line_generators: HashSet<UnorderedPointPair> = HashSet::new();
+    /// Maps a point from the input's coordinates to the normalized ones.
+    fn to_unit(&self, p: &Point<T>) -> Point<T> {
+        Point::new((p.x - self.center.x) / self.scale, (p.y - self.center.y) / self.scale)
+    }
 
-    // Add all possible pairs of points to the set of generators.
-    let points_vec: Vec<&Point> = points.iter().collect();
-    for i in 0..points_vec.len() {
-        for j in (i + 1)..points_vec.len() {
-            let unord_ppair = UnorderedPointPair::new(points_vec[i], points_vec[j]);
-            e_line_generators.insert(unord_ppair);
+    /// Maps a line from the normalized coordinates to the input's: `a·x' + b·y' + c = 0` with
+    /// `x' = (x - cx) / s` becomes `a·x + b·y + (c·s - a·cx - b·cy) = 0`.
+    fn line_from_unit(&self, line: &Line<T>) -> Line<T> {
+        let c = line.c * self.scale - line.a * self.center.x - line.b * self.center.y;
+        Line::new(line.a, line.b, c)
+    }
+}
+
+/// Runs `search_lines_of_sym` on the normalized points (see `Normalization`), with the tolerances
+/// of `opts` scaled along, and maps the lines found back to the input's coordinates. Points in
+/// `opts.keep_out_region` are left out first, and points closer than `opts.min_feature_size`
+/// merged. The phases of the search are timed into `timing` if given, and its buffers taken from
+/// `ws`. Each axis found is passed to `on_axis` in the input's coordinates, before clustering,
+/// unless `opts.require_axis_point` drops it.
+fn search_normalized<T: Scalar, S: BuildHasher + Default>(
+    points: &HashSet<Point<T>, S>,
+    opts: &SymmetryOptions,
+    stats: &mut SymmetryStats,
+    timing: Option<&mut Timing>,
+    ws: &mut Workspace<T, S>,
+    on_axis: &mut dyn FnMut(&Line<T>) -> ControlFlow<()>,
+) -> SymmetryResult<T, S> {
+    let kept;
+    let points = match &opts.keep_out_region {
+        Some(region) => {
+            kept = outside_region(points, region);
+            &kept
         }
+        None => points,
+    };
+    let merged;
+    let points = match opts.min_feature_size {
+        Some(size) => {
+            merged = merge_close_points(points, T::from(size).unwrap());
+            &merged
+        }
+        None => points,
+    };
+
+    let reflect = |line: &Line<T>, p: &Point<T>| line.try_reflected_point(p);
+    // Reflections are matched by the equality of `opts.point_equality`.
+    let matcher = None::<&ToleranceMatcher>;
+    let points_vec: Vec<&Point<T>> = points.iter().collect();
+    let Some(normalization) = Normalization::of(&points_vec) else {
+        let on_axis = &mut |line: &Line<T>| {
+            if opts.require_axis_point && !has_point_on(line, points, opts) {
+                return ControlFlow::Continue(());
+            }
+            on_axis(line)
+        };
+        let mut result = search_lines_of_sym(points, opts, stats, timing, reflect, matcher, ws, on_axis);
+        if opts.require_axis_point {
+            retain_axes_through_points(&mut result.lines, points, opts, stats);
+        }
+        return result;
+    };
+
+    let scale = normalization.scale.to_f64().unwrap();
+    let unit_opts = SymmetryOptions {
+        epsilon: opts.epsilon.map(|eps| eps / scale),
+        point_equality: match opts.point_equality {
+            PointEquality::Radial(tol) => PointEquality::Radial(tol / scale),
+            PointEquality::Box => PointEquality::Box,
+        },
+        // Clustering is done on the lines in the input's coordinates.
+        cluster_tolerance: None,
+        ..opts.clone()
+    };
+    let mut unit_points = mem::take(&mut ws.unit_points);
+    unit_points.clear();
+    unit_points.extend(points_vec.iter().map(|p| normalization.to_unit(p)));
+
+    let on_unit_axis = &mut |line: &Line<T>| {
+        if opts.require_axis_point && !has_point_on(line, &unit_points, &unit_opts) {
+            return ControlFlow::Continue(());
+        }
+        on_axis(&normalization.line_from_unit(line))
+    };
+    let mut result = search_lines_of_sym(&unit_points, &unit_opts, stats, timing, reflect, matcher, ws, on_unit_axis);
+    if opts.require_axis_point {
+        retain_axes_through_points(&mut result.lines, &unit_points, &unit_opts, stats);
+    }
+    let mut lines = mem::take(&mut ws.lines);
+    lines.clear();
+    lines.extend(result.lines.iter().map(|line| normalization.line_from_unit(line)));
+    ws.unit_points = unit_points;
+    ws.search_lines = result.lines;
+    if let Some(tol) = opts.cluster_tolerance {
+        lines = cluster_lines(lines, T::from(tol).unwrap());
+        stats.axes_found = lines.len();
     }
 
-    // A reusable map to track reflections of points across the candidate lines.
-    let mut point_reflections: HashMap<&Point, &Point> = HashMap::new();
+    SymmetryResult { lines, cancelled: result.cancelled }
+}
+
+/// Keeps only the lines that some point lies on, within the tolerance of `opts`. See
+/// `SymmetryOptions::require_axis_point`.
+fn retain_axes_through_points<T: Scalar, S: BuildHasher>(
+    lines: &mut HashSet<Line<T>, S>,
+    points: &HashSet<Point<T>, S>,
+    opts: &SymmetryOptions,
+    stats: &mut SymmetryStats,
+) {
+    lines.retain(|line| has_point_on(line, points, opts));
+    stats.axes_found = lines.len();
+}
 
-    // A flag to indicate whether a line that goes through all points is possible.
-    let mut through_line_possible = true;
+/// Checks whether some point lies on `line`, within the tolerance of `opts`.
+fn has_point_on<T: Scalar, S: BuildHasher>(
+    line: &Line<T>,
+    points: &HashSet<Point<T>, S>,
+    opts: &SymmetryOptions,
+) -> bool {
+    let eps = opts.epsilon.map_or_else(config::default_epsilon, |eps| T::from(eps).unwrap());
+    points.iter().any(|p| line.is_point_on_line_tol(p, eps))
+}
 
-    while let Some(e_pair) = e_line_generators.iter().next().cloned() {
-        // Generate candidate line
-        let e_line = get_equidistant_line(&e_pair.p1, &e_pair.p2);
+/// An `on_axis` callback for `search_normalized` and `search_lines_of_sym` that lets the search
+/// run to completion.
+fn keep_searching<T>(_: &Line<T>) -> ControlFlow<()> {
+    ControlFlow::Continue(())
+}
 
-        // reflection covered by this line; can be removed from input pairs.
-        e_line_generators.remove(&e_pair);
+/// Returns the points outside the box `region`, given by its lower-left and upper-right corners,
+/// leaving out the points on its boundary too. See `SymmetryOptions::keep_out_region`.
+fn outside_region<T: Scalar, S: BuildHasher + Default>(
+    points: &HashSet<Point<T>, S>,
+    (min, max): &(Point, Point),
+) -> HashSet<Point<T>, S> {
+    let inside = |p: &Point<T>| {
+        let (x, y) = (p.x.to_f64().unwrap(), p.y.to_f64().unwrap());
+        min.x <= x && x <= max.x && min.y <= y && y <= max.y
+    };
+    points.iter().filter(|p| !inside(p)).copied().collect()
+}
 
-        let mut valid_line = true;
+/// Returns an iterator over the lines of symmetry of a set of points, as configured by `opts`,
+/// yielding each axis as soon as it is found, e.g. to draw the axes while a long search runs.
+///
+/// The search of `get_lines_of_sym_with` runs on another thread, and hands each axis over once it
+/// is checked, so an axis once yielded is final. Collected, the axes equal the lines of
+/// `get_lines_of_sym_with`, except that `opts.cluster_tolerance` isn't applied, since it needs all
+/// the axes at once. The iterator ends early once `opts.cancel_flag` or `opts.deadline` requests a
+/// stop. Dropping the iterator stops the search at the next axis it finds.
+///
+/// Without `std` there are no threads: the search runs to completion on the first call to `next`.
+pub fn axes_iter(points: &HashSet<Point>, opts: &SymmetryOptions) -> impl Iterator<Item = Line> {
+    AxesIter::new(points, opts)
+}
 
-        point_reflections.insert(e_pair.p1, e_pair.p2);
-        point_reflections.insert(e_pair.p2, e_pair.p1);
+/// The receiving end of the search behind `axes_iter`.
+#[cfg(feature = "std")]
+struct AxesIter {
+    axes: std::sync::mpsc::Receiver<Line>,
+    /// The thread running the search, until it has been joined.
+    search: Option<std::thread::JoinHandle<()>>,
+}
 
-        for point in points {
-            /* Check that all input points have a reflection across the line in the input set */
-            if let None = point_reflections.get(point) {
-                // Input point not yet in the reflections.
-                let reflection = e_line.get_reflected_point(point);
-
-                if reflection == *point {
-                    // Point is on the line, is its own reflection.
-                    if !point_reflections.contains_key(point) {
-                        point_reflections.insert(point, point);
-                    }
-                } else {
-                    // Reflection is a separate point.
-                    if points.contains(&reflection) {
-                        // Reflection is in the input set.
-                        let reflection_in_input = points.get(&reflection).unwrap();
-                        point_reflections.insert(point, reflection_in_input);
-                        point_reflections.insert(reflection_in_input, point);
-
-                        /*
-                         * This reflection has been covered; it can be removed from the set of generating pairs, regardless
-                         * of whether the candidate line is a line of symmetry.
-                         */
-                        let covered_pair = UnorderedPointPair::new(point, reflection_in_input);
-
-                        if e_line_generators.contains(&covered_pair) {
-                            e_line_generators.remove(&covered_pair);
-                        }
-                    } else {
-                        /*
-                        Reflection is not in the input set, so this line is not valid.
-                        If a high degree of partial symmetry is expected, don't break, because 
-                        we can still use this line to remove pairs of points that are symmetric across it.
-                        */
-                        valid_line = false;
-
-                        if !high_degree_expected {
-                            break;
-                        }
-                    }
+#[cfg(feature = "std")]
+impl AxesIter {
+    fn new(points: &HashSet<Point>, opts: &SymmetryOptions) -> Self {
+        // Without a buffer, the search waits for each axis to be taken before looking further.
+        let (sender, axes) = std::sync::mpsc::sync_channel(0);
+        let (points, opts) = (points.clone(), opts.clone());
+        let search = std::thread::spawn(move || {
+            // A failed send means the iterator was dropped.
+            let mut send = |line: &Line| match sender.send(*line) {
+                Ok(()) => ControlFlow::Continue(()),
+                Err(_) => ControlFlow::Break(()),
+            };
+            search_normalized(&points, &opts, &mut SymmetryStats::default(), None, &mut Workspace::new(), &mut send);
+        });
+        Self { axes, search: Some(search) }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Iterator for AxesIter {
+    type Item = Line;
+
+    fn next(&mut self) -> Option<Line> {
+        match self.axes.recv() {
+            Ok(axis) => Some(axis),
+            Err(_) => {
+                // The search is over; pass on a panic of its thread.
+                if let Some(Err(panic)) = self.search.take().map(std::thread::JoinHandle::join) {
+                    std::panic::resume_unwind(panic);
                 }
+                None
             }
         }
+    }
+}
 
-        if valid_line {
-            if through_line_possible {
-                through_line_possible = false;
-            }
+/// The axes behind `axes_iter`, found all at once on the first call to `next`.
+#[cfg(not(feature = "std"))]
+struct AxesIter {
+    search: Option<(HashSet<Point>, SymmetryOptions)>,
+    axes: alloc::vec::IntoIter<Line>,
+}
 
-            if !lines_set.contains(&e_line) {
-                lines_set.insert(e_line);
-            }
+#[cfg(not(feature = "std"))]
+impl AxesIter {
+    fn new(points: &HashSet<Point>, opts: &SymmetryOptions) -> Self {
+        Self { search: Some((points.clone(), opts.clone())), axes: Vec::new().into_iter() }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl Iterator for AxesIter {
+    type Item = Line;
+
+    fn next(&mut self) -> Option<Line> {
+        if let Some((points, opts)) = self.search.take() {
+            let mut axes = Vec::new();
+            let mut collect = |line: &Line| {
+                axes.push(*line);
+                ControlFlow::Continue(())
+            };
+            search_normalized(&points, &opts, &mut SymmetryStats::default(), None, &mut Workspace::new(), &mut collect);
+            self.axes = axes.into_iter();
+        }
+        self.axes.next()
+    }
+}
+
+/// Returns all lines of symmetry for a given set of points, short-circuiting the cases that don't
+/// need the full search.
+///
+/// A single point, or a cluster of points all within floating-point tolerance of one location,
+/// has infinitely many lines of symmetry, which `get_lines_of_sym` can't return; `Infinite` is
+/// returned for these. A regular polygon, detected by `rotational_symmetry_order` equalling the
+/// number of points, has all its axes through its center, so they are found with
+/// `axes_through_center` in `O(n)` candidates instead of one per pair of points. Other sets are
+/// searched with `get_lines_of_sym`, and an empty set has no lines of symmetry.
+pub fn get_lines_of_sym_or_infinite<T: Scalar, S: BuildHasher + Default>(
+    points: &HashSet<Point<T>, S>,
+) -> LinesOfSym<T, S> {
+    let points_vec: Vec<&Point<T>> = points.iter().collect();
+    let Some(first) = points_vec.first() else {
+        return LinesOfSym::Finite(HashSet::with_hasher(S::default()));
+    };
+
+    if points_vec.iter().all(|p| p.distance(first) <= config::default_epsilon()) {
+        return LinesOfSym::Infinite;
+    }
+
+    if points_vec.len() >= 3 && rotational_symmetry_order(points) == points_vec.len() {
+        if let Some(center) = centroid(&points_vec) {
+            return LinesOfSym::Finite(axes_through_center(points, &center).into_iter().collect());
+        }
+    }
+
+    LinesOfSym::Finite(get_lines_of_sym_or_empty(points, None))
+}
+
+/// Returns the order of the rotational symmetry of a set of points about their centroid: the
+/// number of rotations, including the identity, that map the set onto itself.
+///
+/// Every rotation mapping the set onto itself fixes the centroid, and the rotations form a cyclic
+/// group whose order divides the number of points away from the centroid, so only rotations by
+/// `2π / k` for those divisors `k` are checked. An empty set has order 1, and a set whose points
+/// all coincide with the centroid, which every rotation maps onto itself, has order 0.
+pub fn rotational_symmetry_order<T: Scalar, S: BuildHasher>(points: &HashSet<Point<T>, S>) -> usize {
+    let points_vec: Vec<&Point<T>> = points.iter().collect();
+    let Some(center) = centroid(&points_vec) else {
+        return 1;
+    };
+
+    let off_center = points_vec.iter().filter(|p| ***p != center).count();
+    if off_center == 0 {
+        return 0;
+    }
+
+    (2..=off_center)
+        .rev()
+        .filter(|k| off_center % k == 0)
+        .find(|&k| is_invariant_under_rotation(points, &points_vec, &center, k, config::default_epsilon()))
+        .unwrap_or(1)
+}
+
+/// Checks whether a set of points is mapped onto itself by the rotation by `2π / order` about its
+/// centroid, which is cheaper than `rotational_symmetry_order` when only one order is in question.
+///
+/// Every set is invariant under the identity, i.e. for `order` 1, and `order` 0 is taken to be the
+/// identity as well. An empty set is invariant under every rotation. A set whose number of points
+/// away from the centroid isn't a multiple of `order` can't be invariant, so it isn't rotated.
+pub fn has_rotational_symmetry<T: Scalar, S: BuildHasher>(
+    points: &HashSet<Point<T>, S>,
+    order: usize,
+) -> bool {
+    let points_vec: Vec<&Point<T>> = points.iter().collect();
+    let Some(center) = centroid(&points_vec) else {
+        return true;
+    };
+    if order <= 1 {
+        return true;
+    }
+
+    let off_center = points_vec.iter().filter(|p| ***p != center).count();
+    off_center.is_multiple_of(order)
+        && is_invariant_under_rotation(points, &points_vec, &center, order, config::default_epsilon())
+}
+
+/// Checks whether the rotation by `2π / order` about `center` maps every point of `points_vec`, the
+/// points of `points`, onto a point of `points` within `tol`.
+fn is_invariant_under_rotation<T: Scalar, S: BuildHasher>(
+    points: &HashSet<Point<T>, S>,
+    points_vec: &[&Point<T>],
+    center: &Point<T>,
+    order: usize,
+    tol: T,
+) -> bool {
+    let tau = T::from(core::f64::consts::TAU).unwrap();
+    let (sin, cos) = Float::sin_cos(tau / T::from(order).unwrap());
+    points_vec.iter().all(|p| {
+        let (dx, dy) = (p.x - center.x, p.y - center.y);
+        Point::from_array([center.x + dx * cos - dy * sin, center.y + dx * sin + dy * cos])
+            .is_ok_and(|rotated| find_point(points, &rotated, tol).is_some())
+    })
+}
+
+/// A summary of the symmetries of a set of points, as returned by `describe`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeSymmetry {
+    /// The number of lines of symmetry.
+    pub reflective_axes: usize,
+    /// The order of the rotational symmetry, as returned by `rotational_symmetry_order`.
+    pub rotational_order: usize,
+    /// Whether a half turn about the centroid maps the set onto itself.
+    pub central: bool,
+    /// The symmetry group, `Cn` or `Dn`, followed by a description of a typical shape with that
+    /// symmetry, e.g. `"D4 (square-like)"`.
+    pub label: String,
+}
+
+/// Returns a summary of the reflective and rotational symmetries of a set of points.
+///
+/// A set with `n`-fold rotational symmetry and no lines of symmetry has the cyclic symmetry group
+/// `Cn`; one with lines of symmetry has `n` of them and the dihedral group `Dn`. A set whose
+/// points all coincide, which every line and rotation through the point maps onto itself, has
+/// `reflective_axes` and `rotational_order` of 0 and the label `"D∞ (point-like)"`.
+pub fn describe<T: Scalar, S: BuildHasher + Default>(points: &HashSet<Point<T>, S>) -> ShapeSymmetry {
+    let reflective_axes = match get_lines_of_sym_or_infinite(points) {
+        LinesOfSym::Finite(lines) => lines.len(),
+        LinesOfSym::Infinite => {
+            return ShapeSymmetry {
+                reflective_axes: 0,
+                rotational_order: 0,
+                central: true,
+                label: String::from("D∞ (point-like)"),
+            };
         }
+    };
+    let rotational_order = rotational_symmetry_order(points);
+
+    let group = if reflective_axes > 0 { "D" } else { "C" };
+    let shape = match (reflective_axes > 0, rotational_order) {
+        (false, 1) => String::from("asymmetric"),
+        (false, 2) => String::from("parallelogram-like"),
+        (false, _) => String::from("rotational"),
+        (true, 1) => String::from("mirror-symmetric"),
+        (true, 2) => String::from("rectangle-like"),
+        (true, 3) => String::from("equilateral-triangle-like"),
+        (true, 4) => String::from("square-like"),
+        (true, n) => format!("regular-{n}-gon-like"),
+    };
+
+    ShapeSymmetry {
+        reflective_axes,
+        rotational_order,
+        central: rotational_order.is_multiple_of(2),
+        label: format!("{group}{rotational_order} ({shape})"),
+    }
+}
+
+/// Returns the order of the symmetry group of a set of points, as classified by `describe`: `2n`
+/// for the dihedral group `Dn`, `n` for the cyclic group `Cn`, and 1 for an asymmetric set.
+///
+/// A set whose points all coincide is mapped onto itself by infinitely many rotations and
+/// reflections; `usize::MAX` is returned for it.
+pub fn symmetry_group_order<T: Scalar, S: BuildHasher + Default>(points: &HashSet<Point<T>, S>) -> usize {
+    let symmetry = describe(points);
+    match (symmetry.reflective_axes, symmetry.rotational_order) {
+        (_, 0) => usize::MAX,
+        (0, n) => n,
+        (_, n) => 2 * n,
+    }
+}
+
+/// Checks whether the lines of symmetry found for a set of points agree with its rotational
+/// symmetry, as a consistency check of the detection.
+///
+/// The symmetry group of a finite set is cyclic or dihedral: a set with `n`-fold rotational
+/// symmetry has either no lines of symmetry or exactly `n` of them. A different count, as returned
+/// by `describe`, means that the detection missed or invented an axis, e.g. because of a tolerance
+/// mismatch between the reflective and the rotational checks.
+pub fn is_consistent_dihedral<T: Scalar, S: BuildHasher + Default>(
+    points: &HashSet<Point<T>, S>,
+) -> bool {
+    let symmetry = describe(points);
+    symmetry.reflective_axes == 0 || symmetry.reflective_axes == symmetry.rotational_order
+}
+
+/// Returns the centroid of the given points, or `None` if there are none or it isn't finite.
+fn centroid<T: Scalar>(points: &[&Point<T>]) -> Option<Point<T>> {
+    Point::mean(points.iter().map(|p| **p))
+}
 
-        point_reflections.clear();
+/// The axes `geometry::regular_polygon_axes_seeded` gives for the points with `seed`, if they are
+/// the vertices of a regular polygon, converted to the scalar type. Sets whose points aren't all as
+/// far from their centroid within `tol` are ruled out before they are converted to look for the
+/// polygon.
+#[cfg(feature = "std")]
+fn regular_polygon_axes<T: Scalar>(points: &[&Point<T>], tol: T, seed: Option<u64>) -> Option<Vec<Line<T>>> {
+    let center = centroid(points)?;
+    let radius = center.distance(points.first()?);
+    if points.len() < 3 || points.iter().any(|p| (center.distance(p) - radius).abs() > tol) {
+        return None;
     }
 
-    /* Only possible missing line of symmetry: a line that goes through all the points. */
-    if through_line_possible && points_vec.len() >= 2 {
-        let p1 = points_vec[0];
-        let p2 = points_vec[1];
-        let through_line = get_through_line(p1, p2);
+    let converted: std::collections::HashSet<Point> =
+        points.iter().map(|p| Some(Point::new(p.x.to_f64()?, p.y.to_f64()?))).collect::<Option<_>>()?;
+    crate::geometry::regular_polygon_axes_seeded(&converted, seed)?
+        .iter()
+        .map(|line| Some(Line::new(T::from(line.a)?, T::from(line.b)?, T::from(line.c)?)))
+        .collect()
+}
+
+/// Returns all lines of symmetry for a given set of points, as configured by `opts`, looking up
+/// reflections of points across candidate lines in `cache` before computing them.
+///
+/// Reusing one cache across calls on overlapping point sets (e.g. sliding windows) avoids
+/// recomputing reflections the earlier calls already made. See `cache::ReflectionCache` for
+/// the memory tradeoff.
+///
+/// Unlike `get_lines_of_sym_with`, this searches the points as given, without normalizing them,
+/// since normalized coordinates depend on each set's centroid and extent and would rarely be shared
+/// between overlapping sets. The tolerances are thus absolute, in the input's units, and applied to
+/// reflections computed in the input's coordinates: for points far from the origin or spread far
+/// wider than the tolerance, reflections are rounded by more than the tolerance and true axes can
+/// be missed. Use `get_lines_of_sym_with` for such inputs.
+pub fn get_lines_of_sym_cached<T: Scalar, S: BuildHasher + Default>(
+    points: &HashSet<Point<T>, S>,
+    opts: &SymmetryOptions,
+    cache: &mut ReflectionCache<T>,
+) -> SymmetryResult<T, S> {
+    let reflect = |line: &Line<T>, p: &Point<T>| cache.try_reflected_point(line, p);
+    let mut stats = SymmetryStats::default();
+    let ws = &mut Workspace::new();
+    search_lines_of_sym(points, opts, &mut stats, None, reflect, None::<&ToleranceMatcher>, ws, &mut keep_searching)
+}
+
+/// The search behind `get_lines_of_sym_with`, computing reflections of points across candidate
+/// lines with `reflect` and recording its work in `stats`. A candidate line across which some
+/// reflection can't be computed (see `Line::try_reflected_point`) is invalid. The returned set
+/// and the search's records are taken from the buffers of `ws`.
+///
+/// A reflection is matched to a point by `matcher` if given, or else by the equality of
+/// `opts.point_equality`; a point on a candidate line is offered to `matcher` as its own partner.
+///
+/// Each line is checked again as it is found, and then passed to `on_axis`, which can stop the
+/// search as if cancelled. Clustering by `opts.cluster_tolerance` is applied after that.
+#[allow(clippy::too_many_arguments)]
+fn search_lines_of_sym<T: Scalar, S: BuildHasher + Default, M: ReflectionMatcher<T> + ?Sized>(
+    points: &HashSet<Point<T>, S>,
+    opts: &SymmetryOptions,
+    stats: &mut SymmetryStats,
+    mut timing: Option<&mut Timing>,
+    mut reflect: impl FnMut(&Line<T>, &Point<T>) -> Option<Point<T>>,
+    matcher: Option<&M>,
+    ws: &mut Workspace<T, S>,
+    on_axis: &mut dyn FnMut(&Line<T>) -> ControlFlow<()>,
+) -> SymmetryResult<T, S> {
+    // Returns a set of lines of symmetry for the given set of points.
+    let high_degree_expected = opts.high_degree_expected;
+
+    let mut lines_set = mem::take(&mut ws.search_lines);
+    lines_set.clear();
+
+    if points.len() < 2 {
+        #[cfg(feature = "std")]
+        if !opts.quiet {
+            eprintln!("Warning: at least 2 points needed to find lines of symmetry.");
+        }
+        return SymmetryResult {
+            lines: lines_set,
+            cancelled: false,
+        };
+    }
+
+    // The points, with lookups within tolerance, and positions into `points_vec`.
+    let index = PointIndex::new(points);
+    let points_vec = index.points();
+
+    let eps = opts.epsilon.map_or_else(config::default_epsilon, |eps| T::from(eps).unwrap());
 
-        let mut through_line_valid = true;
-        for point in &points_vec[2..] {
-            if !through_line.is_point_on_line(*point) {
-                through_line_valid = false;
+    // The tolerance of `same` in each coordinate, which bounds the points a lookup compares.
+    let (radial, window) = match opts.point_equality {
+        PointEquality::Box => (None, eps),
+        PointEquality::Radial(tol) => (Some(T::from(tol).unwrap()), T::from(tol).unwrap()),
+    };
+    let same = |p: &Point<T>, q: &Point<T>| match radial {
+        Some(tol) => p.approx_eq_radial(q, tol),
+        None => p.approx_eq(q, eps),
+    };
+    // The position of a point equal to `target` by `same`.
+    let lookup = |target: &Point<T>| match radial {
+        Some(tol) => index.position_within(target, tol),
+        None => index.match_position(target, eps, same),
+    };
+    let custom = matcher.map(|matcher| (matcher, &index));
+    // Like `verify_axis`, with this search's equality.
+    let is_axis = |line: &Line<T>| {
+        points_vec.iter().all(|p| match custom {
+            Some((matcher, index)) => line.try_reflected_point(p).and_then(|r| matcher.matches(p, &r, index)).is_some(),
+            None => line
+                .try_reflected_point(p)
+                .and_then(|r| lookup(&r))
+                .and_then(|i| line.try_reflected_point(points_vec[i]))
+                .is_some_and(|back| same(&back, p)),
+        })
+    };
+
+    // Collinear points have their own, simpler set of candidate axes.
+    let clock = PhaseClock::start(&timing);
+    if let Some(through_line) = collinear_through_line(points_vec, eps) {
+        let mut cancelled = false;
+        for line in collinear_axes(points_vec, through_line, opts.include_through_line, is_axis) {
+            // The line through the points maps each onto itself, which a custom matcher can refuse.
+            if custom.is_some() && !is_axis(&line) {
+                continue;
+            }
+            if lines_set.insert(line) && on_axis(&line).is_break() {
+                cancelled = true;
                 break;
             }
         }
 
-        if through_line_valid && !lines_set.contains(&through_line) {
-            lines_set.insert(through_line);
+        clock.stop(&mut timing, |t| &mut t.through_line);
+        stats.axes_found = lines_set.len();
+        return SymmetryResult {
+            lines: lines_set,
+            cancelled,
+        };
+    }
+
+    clock.stop(&mut timing, |t| &mut t.through_line);
+
+    // A regular polygon's axes are known without a search. Each is replaced by the candidate the
+    // search would generate for it, from a pair of points it swaps, and they are taken once those
+    // all check out as axes with this search's equality, which a custom matcher may not share.
+    #[cfg(feature = "std")]
+    if custom.is_none() && !opts.is_cancelled() {
+        let clock = PhaseClock::start(&timing);
+        let axes = regular_polygon_axes(points_vec, window, opts.seed).and_then(|axes| {
+            axes.iter()
+                .map(|axis| {
+                    let (i, j) = (0..points_vec.len()).find_map(|i| {
+                        let j = axis.try_reflected_point(points_vec[i]).and_then(|r| lookup(&r))?;
+                        (j != i).then_some((i, j))
+                    })?;
+                    let pair = UnorderedPointPair::new(points_vec[i], points_vec[j]);
+                    Some(get_equidistant_line(pair.p1, pair.p2)).filter(is_axis)
+                })
+                .collect::<Option<Vec<_>>>()
+        });
+        clock.stop(&mut timing, |t| &mut t.candidate_evaluation);
+        if let Some(axes) = axes {
+            stats.candidates_generated += axes.len();
+            let mut cancelled = false;
+            for line in axes {
+                if lines_set.insert(line) && on_axis(&line).is_break() {
+                    cancelled = true;
+                    break;
+                }
+            }
+            if let Some(tol) = opts.cluster_tolerance.filter(|_| !cancelled) {
+                lines_set = cluster_lines(lines_set, T::from(tol).unwrap());
+            }
+
+            stats.axes_found = lines_set.len();
+            return SymmetryResult {
+                lines: lines_set,
+                cancelled,
+            };
         }
     }
 
-    lines_set
-}
+    // Quick necessary condition: bail out if some points cannot have a mirror partner, with points
+    // matched within the tolerance of `same`. Sparse mode skips it, as it compares all pairs, and so
+    // does a custom matcher, whose matches needn't be within that tolerance.
+    let clock = PhaseClock::start(&timing);
+    let possible =
+        opts.candidate_neighbours.is_some() || custom.is_some() || mirror_partners_possible(points_vec, window);
+    clock.stop(&mut timing, |t| &mut t.candidate_evaluation);
+    if !possible {
+        return SymmetryResult {
+            lines: lines_set,
+            cancelled: false,
+        };
+    }
 
-pub fn get_equidistant_line(p1: &Point, p2: &Point) -> Line {
-    // Returns a line that is equidistant from p1 and p2
-    let a = p2.x - p1.x;
-    let b = p2.y - p1.y;
-    let c = 0.5 * (p1.x.powf(2.0) + p1.y.powf(2.0) - p2.x.powf(2.0) - p2.y.powf(2.0));
+    let n = points_vec.len();
 
-    Line::new(a, b, c)
-}
+    // The index of each point in `points_vec`, so that pairs of points can be tracked by index.
+    let point_index: QuantizedIndex<T, S> = QuantizedIndex::new(points_vec, eps);
 
-pub fn get_through_line(p1: &Point, p2: &Point) -> Line {
-    // Returns a line that goes through p1 and p2
-    let a = p2.y - p1.y;
-    let b = p1.x - p2.x;
-    let c = -(a * p1.x + b * p1.y);
+    // The pairs generating candidates in the sparse mode of `opts.candidate_neighbours`, a few for
+    // each point.
+    let sparse_pairs = opts.candidate_neighbours.map(|k| sparse_candidate_pairs(points_vec, &index.by_x, k));
 
-    Line::new(a, b, c)
+    // Pairs of points whose reflection has been covered by an earlier candidate line; they would
+    // generate the same line, so they are skipped. In sparse mode, only the pairs generating
+    // candidates are tracked, and in low-memory mode none are.
+    let mut covered = (!opts.low_memory).then(|| match &sparse_pairs {
+        Some(pairs) => {
+            ws.covered_among.clear();
+            ws.covered_among.resize(pairs.len().div_ceil(64), 0);
+            CoveredPairs::Among(pairs, &mut ws.covered_among)
+        }
+        None => {
+            ws.covered.clear(n);
+            CoveredPairs::All(&mut ws.covered)
+        }
+    });
+
+    // A reusable record of which points have been matched with their reflection across a candidate:
+    // a point is matched for the current candidate if its entry equals the candidate's number.
+    let matched = &mut ws.matched;
+    matched.clear();
+    matched.resize(n, 0);
+    let mut candidate = 0;
+
+    // The axes found, by their `AxisKey`, so that an axis generated again by another pair, with
+    // differently scaled coefficients, is recognised even if those hash apart.
+    let mut found = AxisBuckets::new(eps, eps);
+
+    // Every line of symmetry maps the centroid onto itself.
+    let centroid = if opts.prune_by_centroid { centroid(points_vec) } else { None };
+
+    let prune_tol = if opts.prune_by_centroid || opts.prune_by_bounding_box {
+        prune_tolerance(points_vec, window)
+    } else {
+        window
+    };
+
+    // A line of symmetry maps the points onto themselves, and so maps each point onto a point
+    // within their bounding box. Checking this for the points at the ends of each side of the box
+    // rules out most orientations incompatible with the box's shape, e.g. axes near the diagonals
+    // of a long thin rectangle, which would reflect its long sides across its short ones.
+    let bounding_box = opts.prune_by_bounding_box.then(|| BoundingBox::new(points_vec));
+
+    // The candidate line of a pair, generated from the canonically ordered pair so that its
+    // coefficients don't depend on the set's iteration order.
+    let candidate_line = |i: usize, j: usize| {
+        let pair = UnorderedPointPair::new(points_vec[i], points_vec[j]);
+        get_equidistant_line(pair.p1, pair.p2)
+    };
+
+    // The pairs of points generating candidates: all of them, or the sparse ones.
+    let pairs: Box<dyn Iterator<Item = (usize, usize)>> = match &sparse_pairs {
+        Some(pairs) => Box::new(pairs.iter().copied()),
+        None => Box::new((0..n).flat_map(move |i| ((i + 1)..n).map(move |j| (i, j)))),
+    };
+    for (i, j) in pairs {
+        let clock = PhaseClock::start(&timing);
+        let skip = match &covered {
+            Some(covered) => covered.contains(i, j),
+            // Without covered pairs, skip at least the pairs generating an axis already found,
+            // possibly scaled differently, as looked up by its `AxisKey`.
+            None => found.contains(&candidate_line(i, j)),
+        };
+        if skip {
+            clock.stop(&mut timing, |t| &mut t.pair_generation);
+            continue;
+        }
+
+        if opts.is_cancelled() {
+            return SymmetryResult {
+                lines: lines_set,
+                cancelled: true,
+            };
+        }
+
+        let e_line = candidate_line(i, j);
+        stats.candidates_generated += 1;
+
+        if let Some(centroid) = &centroid {
+            if !e_line.try_reflected_point(centroid).is_some_and(|r| r.approx_eq(centroid, prune_tol)) {
+                stats.candidates_pruned += 1;
+                clock.stop(&mut timing, |t| &mut t.pair_generation);
+                continue;
+            }
+        }
+
+        if let Some(bounding_box) = &bounding_box {
+            if !bounding_box.extremes_reflect_inside(&e_line, prune_tol) {
+                stats.candidates_pruned += 1;
+                clock.stop(&mut timing, |t| &mut t.pair_generation);
+                continue;
+            }
+        }
+        clock.stop(&mut timing, |t| &mut t.pair_generation);
+        let clock = PhaseClock::start(&timing);
+
+        let mut valid_line = true;
+
+        candidate += 1;
+        matched[i] = candidate;
+        matched[j] = candidate;
+
+        for (k, point) in points_vec.iter().enumerate() {
+            /* Check that all input points have a reflection across the line in the input set */
+            if matched[k] == candidate {
+                continue;
+            }
+
+            let reflection = reflect(&e_line, point);
+
+            if custom.is_none() && reflection.as_ref().is_some_and(|r| same(r, point)) {
+                // Point is on the line, is its own reflection.
+                matched[k] = candidate;
+            } else if let Some(r) = reflection.and_then(|reflection| {
+                if let Some((matcher, index)) = custom {
+                    return matcher.matches(point, &reflection, index).and_then(|q| index.position_of(q));
+                }
+                if radial.is_some() {
+                    // Radial equality isn't consistent with the hash, so every lookup is a search.
+                    return lookup(&reflection);
+                }
+
+                // The grid's cells are as wide as `eps`, so a hit is equal by `same`, and a partner
+                // within `eps` is never missed.
+                point_index.get(points_vec, &reflection)
+            }) {
+                // Reflection is in the input set.
+                matched[k] = candidate;
+                matched[r] = candidate;
+
+                /*
+                 * This reflection has been covered; the pair can be skipped as a generator, regardless
+                 * of whether the candidate line is a line of symmetry.
+                 */
+                if let Some(covered) = covered.as_mut().filter(|_| r != k) {
+                    covered.insert(k, r);
+                }
+            } else {
+                /*
+                Reflection is not in the input set (or couldn't be computed), so this line is not valid.
+                If a high degree of partial symmetry is expected, don't break, because
+                we can still use this line to skip pairs of points that are symmetric across it.
+                */
+                valid_line = false;
+
+                if !high_degree_expected {
+                    break;
+                }
+            }
+        }
+
+        // Double-check every line before keeping it, so that a line once reported is final.
+        if valid_line && is_axis(&e_line) && found.insert(e_line) {
+            lines_set.insert(e_line);
+            if on_axis(&e_line).is_break() {
+                return SymmetryResult {
+                    lines: lines_set,
+                    cancelled: true,
+                };
+            }
+        }
+        clock.stop(&mut timing, |t| &mut t.candidate_evaluation);
+    }
+
+    if let Some(tol) = opts.cluster_tolerance {
+        lines_set = cluster_lines(lines_set, T::from(tol).unwrap());
+    }
+
+    stats.axes_found = lines_set.len();
+    SymmetryResult {
+        lines: lines_set,
+        cancelled: false,
+    }
+}
+
+/// Returns the tolerance at which pruning compares the centroid of `points` and the reflections of
+/// their extremes, widened from the search's tolerance `window`. These are rounded by up to about
+/// `n` times the precision of the largest coordinate, or a true axis of points spread far wider
+/// than the tolerance could be pruned.
+fn prune_tolerance<T: Scalar>(points: &[&Point<T>], window: T) -> T {
+    let magnitude = points.iter().fold(T::zero(), |m, p| m.max(p.x.abs()).max(p.y.abs()));
+    window + magnitude * T::epsilon() * T::from(points.len()).unwrap()
+}
+
+/// Checks whether `line` is a line of symmetry of `points`.
+///
+/// Every point is reflected across the line, and its reflection must land within tolerance on
+/// a point of the set. That point is reflected back in turn and must land on the original point,
+/// which guards against a reflection that only matches approximately in one direction. Points are
+/// compared at `config::default_epsilon`, whatever `SymmetryOptions::epsilon` a search used; see
+/// `verify_axis_tol` to compare them at another tolerance.
+pub fn verify_axis<T: Scalar, S: BuildHasher>(points: &HashSet<Point<T>, S>, line: &Line<T>) -> bool {
+    verify_axis_tol(points, line, config::default_epsilon())
+}
+
+/// Like `verify_axis`, but with points compared within `tol` in each coordinate, e.g. to confirm
+/// the axes of a search run with `SymmetryOptions::epsilon` at the same tolerance.
+pub fn verify_axis_tol<T: Scalar, S: BuildHasher>(points: &HashSet<Point<T>, S>, line: &Line<T>, tol: T) -> bool {
+    points.iter().all(|p| has_mirror_partner(points, line, p, tol))
+}
+
+/// Returns the perpendicular bisector of `p1` and `p2` if it is a line of symmetry of `points`,
+/// i.e. the candidate line a search generates from the pair, once it passes `verify_axis`.
+///
+/// This is the step by which every axis swapping points is found, for custom search strategies
+/// that choose their own pairs. Returns `None` if the points coincide or the bisector isn't an
+/// axis of the whole set.
+pub fn axis_from_pair<T: Scalar, S: BuildHasher>(
+    points: &HashSet<Point<T>, S>,
+    p1: &Point<T>,
+    p2: &Point<T>,
+) -> Option<Line<T>> {
+    perpendicular_bisector(p1, p2).filter(|line| verify_axis(points, line))
+}
+
+/// Checks whether the line through the markers `m1` and `m2` is a line of symmetry of `points`, as
+/// checked by `verify_axis`. The markers needn't be points of the set.
+///
+/// Returns `false` if the markers coincide within tolerance, as they define no line.
+pub fn is_symmetric_about_markers<T: Scalar, S: BuildHasher>(
+    points: &HashSet<Point<T>, S>,
+    m1: &Point<T>,
+    m2: &Point<T>,
+) -> bool {
+    Line::through_points(m1, m2).is_some_and(|line| verify_axis(points, &line))
+}
+
+/// Returns the line across which the set `a` reflects onto the set `b`, e.g. to register a part
+/// against its mirrored counterpart, or `None` if there is no such line.
+///
+/// Unlike a line of symmetry of one set, the line needn't map either set onto itself: each point of
+/// `a` must reflect onto a point of `b` within tolerance, and each point of `b` onto one of `a`. The
+/// line maps the centroid of `a` onto that of `b`, so unless the centroids coincide it is their
+/// perpendicular bisector. Otherwise it passes through the common centroid, and the point of `a`
+/// farthest from it is tried against each point of `b` as far from it. If `a` and `b` are the
+/// same set, this is one of its lines of symmetry. Returns `None` for sets of different sizes, for
+/// empty sets, and for sets whose points all coincide with their centroid, which have no single
+/// line.
+pub fn mirror_plane_between<T: Scalar, S: BuildHasher>(
+    a: &HashSet<Point<T>, S>,
+    b: &HashSet<Point<T>, S>,
+) -> Option<Line<T>> {
+    if a.len() != b.len() {
+        return None;
+    }
+    let (a_vec, b_vec): (Vec<&Point<T>>, Vec<&Point<T>>) = (a.iter().collect(), b.iter().collect());
+    let (a_center, b_center) = (centroid(&a_vec)?, centroid(&b_vec)?);
+    let (a_index, b_index) = (PointIndex::new(a), PointIndex::new(b));
+    let is_mirror = |line: &Line<T>| {
+        let onto = |from: &[&Point<T>], to: &PointIndex<T>| {
+            from.iter().all(|p| line.try_reflected_point(p).is_some_and(|r| to.get(&r).is_some()))
+        };
+        onto(&a_vec, &b_index) && onto(&b_vec, &a_index)
+    };
+
+    if a_center != b_center {
+        return perpendicular_bisector(&a_center, &b_center).filter(is_mirror);
+    }
+
+    let farthest = a_vec.iter().copied().max_by(|p, q| {
+        p.distance(&a_center).partial_cmp(&q.distance(&a_center)).unwrap_or(core::cmp::Ordering::Equal)
+    })?;
+    let radius = farthest.distance(&a_center);
+    if radius <= config::default_epsilon() {
+        return None;
+    }
+
+    b_vec
+        .iter()
+        .filter(|q| util::floats_equal_toler(q.distance(&a_center), radius))
+        .map(|q| match perpendicular_bisector(farthest, q) {
+            Some(bisector) => bisector,
+            None => get_through_line(&a_center, farthest),
+        })
+        .find(is_mirror)
+}
+
+/// Returns the lines among `candidates` that are lines of symmetry of `points`, as checked by
+/// `verify_axis`, in their order in `candidates`.
+///
+/// This confirms axes guessed by other means, e.g. the principal directions of the points,
+/// without a search.
+pub fn validate_axes<T: Scalar, S: BuildHasher>(
+    points: &HashSet<Point<T>, S>,
+    candidates: &[Line<T>],
+) -> Vec<Line<T>> {
+    candidates
+        .iter()
+        .filter(|line| verify_axis(points, line))
+        .copied()
+        .collect()
+}
+
+/// Returns the points of the set whose reflections across `line` are missing from the set, i.e.
+/// the points for which `verify_axis` rejects the line.
+///
+/// This explains why a set that is expected to be symmetric about `line` isn't, e.g. by pointing
+/// at a displaced or missing point. The result is empty exactly if `line` is a line of symmetry.
+pub fn explain_no_axis<T: Scalar, S: BuildHasher>(
+    points: &HashSet<Point<T>, S>,
+    line: &Line<T>,
+) -> Vec<Point<T>> {
+    let tol = config::default_epsilon();
+    points.iter().filter(|p| !has_mirror_partner(points, line, p, tol)).copied().collect()
+}
+
+/// A candidate line rejected by `detect_verbose`, with the point that rules it out.
+#[derive(Debug)]
+pub struct RejectedAxis {
+    /// The candidate line.
+    pub line: Line,
+    /// The least point, by x then y, whose reflection across the line is missing from the set.
+    pub offending_point: Point,
+}
+
+/// Returns the lines of symmetry of the points, together with every other candidate line and the
+/// point that rules it out, for debugging a search or teaching how one works.
+///
+/// The candidates are those of `candidate_axes`, each checked once even if several pairs of points
+/// generate it, and checked as by `verify_axis`. Unlike `get_lines_of_sym`, which moves on at the
+/// first point without a partner in whatever order it checks the points, the offending point of a
+/// rejected line is the least of them, so it doesn't change between runs. Every candidate is
+/// compared with those before it, which takes `O(n⁴)` time, for small sets. The rejected lines are
+/// in the order of the candidates.
+pub fn detect_verbose(points: &HashSet<Point>) -> (HashSet<Line>, Vec<RejectedAxis>) {
+    let mut sorted: Vec<&Point> = points.iter().collect();
+    sorted.sort_by(|p, q| p.x.total_cmp(&q.x).then(p.y.total_cmp(&q.y)));
+
+    let eps = config::default_epsilon();
+    let mut axes: Vec<Line> = Vec::new();
+    let mut rejected: Vec<RejectedAxis> = Vec::new();
+    for line in candidate_axes(points) {
+        let seen = |l: &Line| l.approx_eq(&line, eps);
+        if axes.iter().any(seen) || rejected.iter().any(|r| seen(&r.line)) {
+            continue;
+        }
+        match sorted.iter().find(|p| !has_mirror_partner(points, &line, p, eps)) {
+            Some(p) => rejected.push(RejectedAxis { line, offending_point: **p }),
+            None => axes.push(line),
+        }
+    }
+    (axes.into_iter().collect(), rejected)
+}
+
+/// Returns each pair of distinct points of the set that reflect onto each other across `line`,
+/// e.g. for drawing the correspondences of an axis.
+///
+/// Each pair is yielded once, with its points in canonical order. Points on the line, which
+/// reflect onto themselves, and points without a partner in the set are skipped.
+pub fn symmetric_pairs<'a, T: Scalar, S: BuildHasher>(
+    points: &'a HashSet<Point<T>, S>,
+    line: &'a Line<T>,
+) -> impl Iterator<Item = (&'a Point<T>, &'a Point<T>)> + 'a {
+    points.iter().filter_map(move |p| {
+        let partner = find_point(points, &line.try_reflected_point(p)?, config::default_epsilon())?;
+        (p < partner).then_some((p, partner))
+    })
+}
+
+/// Splits the points by their reflections across `line`: into the pairs of distinct points that
+/// reflect onto each other, the points on the line, which reflect onto themselves, and the points
+/// without a partner in the set, i.e. those of `explain_no_axis`.
+///
+/// Partners are matched as by `verify_axis`, so the line is a line of symmetry exactly if the last
+/// part is empty. Each pair is listed once, with its points in canonical order, and each part is
+/// sorted by x then y, so equal sets give equal results.
+pub fn partition_by_reflection(
+    points: &HashSet<Point>,
+    line: &Line,
+) -> (Vec<(Point, Point)>, Vec<Point>, Vec<Point>) {
+    let mut pairs = Vec::new();
+    let mut on_axis = Vec::new();
+    let mut unmatched = Vec::new();
+    let tol = config::default_epsilon();
+    for p in points {
+        let partner = line.try_reflected_point(p).and_then(|reflection| find_point(points, &reflection, tol));
+        match partner {
+            Some(q) if line.try_reflected_point(q).as_ref() == Some(p) => {
+                if q == p {
+                    on_axis.push(*p);
+                } else if p < q {
+                    pairs.push((*p, *q));
+                }
+            }
+            _ => unmatched.push(*p),
+        }
+    }
+
+    let by_coordinates = |p: &Point, q: &Point| p.x.total_cmp(&q.x).then(p.y.total_cmp(&q.y));
+    pairs.sort_by(|(p1, _), (p2, _)| by_coordinates(p1, p2));
+    on_axis.sort_by(by_coordinates);
+    unmatched.sort_by(by_coordinates);
+    (pairs, on_axis, unmatched)
+}
+
+/// Checks whether the reflection of `p` across `line` is a point of the set that reflects back
+/// onto `p`, within `tol`, as required of every point by `verify_axis_tol`.
+fn has_mirror_partner<T: Scalar, S: BuildHasher>(
+    points: &HashSet<Point<T>, S>,
+    line: &Line<T>,
+    p: &Point<T>,
+    tol: T,
+) -> bool {
+    let partner = line.try_reflected_point(p).and_then(|reflection| find_point(points, &reflection, tol));
+    match partner {
+        Some(partner) => line.try_reflected_point(partner).is_some_and(|back| back.approx_eq(p, tol)),
+        None => false,
+    }
+}
+
+/// Returns the points of the set that lie on `line`, within floating-point tolerance.
+pub fn points_on_axis<'a, T: Scalar, S: BuildHasher>(
+    points: &'a HashSet<Point<T>, S>,
+    line: &Line<T>,
+) -> Vec<&'a Point<T>> {
+    points.iter().filter(|p| line.is_point_on_line(p)).collect()
+}
+
+/// Returns the points lying on every line of symmetry of the set, as found by
+/// `get_lines_of_sym_or_empty`, e.g. the center of a regular polygon when it is a point of the set.
+///
+/// These are the points that every reflection, and so every rotation, of the set leaves in place,
+/// which makes them candidate pivots or rotation centers. A set with two or more axes fixes at
+/// most the point where they cross. A set without lines of symmetry has no fixed points here,
+/// although the identity maps all of them onto themselves.
+pub fn fixed_points<T: Scalar, S: BuildHasher + Default>(
+    points: &HashSet<Point<T>, S>,
+) -> HashSet<Point<T>, S> {
+    let axes = get_lines_of_sym_or_empty(points, None);
+    if axes.is_empty() {
+        return HashSet::with_hasher(S::default());
+    }
+    points.iter().filter(|p| axes.iter().all(|axis| axis.is_point_on_line(p))).copied().collect()
+}
+
+/// Returns the lines of symmetry of the points, as found by `get_lines_of_sym_or_empty`, each with
+/// the number of points on it as counted by `points_on_axis`, most points first.
+///
+/// The axes through the most points are often the primary ones of a shape, e.g. the axes of a
+/// regular hexagon through opposite vertices rather than through the midpoints of opposite edges.
+/// Axes through equally many points are ordered by orientation, as by `Line::angle_rad`.
+pub fn axes_by_on_line_count<T: Scalar, S: BuildHasher + Default>(
+    points: &HashSet<Point<T>, S>,
+) -> Vec<(Line<T>, usize)> {
+    let mut axes: Vec<(Line<T>, usize)> = get_lines_of_sym_or_empty(points, None)
+        .into_iter()
+        .map(|line| {
+            let on_line = points_on_axis(points, &line).len();
+            (line, on_line)
+        })
+        .collect();
+    axes.sort_by(|(l1, n1), (l2, n2)| {
+        n2.cmp(n1).then(l1.angle_rad().partial_cmp(&l2.angle_rad()).unwrap_or(core::cmp::Ordering::Equal))
+    });
+    axes
+}
+
+/// Merges near-duplicate lines, returning one representative of each group.
+///
+/// Two lines are grouped if the angle between them is at most `tol` radians and the foot of
+/// the origin's perpendicular on one lies within `tol` of the other. Floating-point error
+/// across different generating pairs can produce such near-duplicates of a single axis.
+///
+/// Lines are visited in order of orientation, then of their coefficients, and each group is
+/// represented by its first line, so the representatives don't depend on the set's iteration order.
+pub fn cluster_lines<T: Scalar, S: BuildHasher + Default>(
+    lines: HashSet<Line<T>, S>,
+    tol: T,
+) -> HashSet<Line<T>, S> {
+    cluster_line_vec(lines.into_iter().collect(), tol, tol).into_iter().collect()
+}
+
+/// Merges lines that are the same axis within `tol_angle` radians in angle and `tol_dist` in
+/// offset, returning one representative of each group, e.g. to clean up results gathered from
+/// several searches or older versions.
+///
+/// Lines whose coefficients are exactly proportional are already equal by `==` and hash alike,
+/// so a set holds only one of them; this also merges those that differ by rounding, or by
+/// more than the scalar's tolerance yet within the given ones. Apart from the separate
+/// tolerances, lines are grouped as by `cluster_lines`: the offset is the distance from the foot
+/// of the origin's perpendicular on one line to the other.
+pub fn dedup_axes<T: Scalar, S: BuildHasher + Default>(
+    lines: HashSet<Line<T>, S>,
+    tol_angle: T,
+    tol_dist: T,
+) -> HashSet<Line<T>, S> {
+    cluster_line_vec(lines.into_iter().collect(), tol_angle, tol_dist).into_iter().collect()
+}
+
+/// The clustering behind `cluster_lines`. Lines are sorted by orientation, and lines of equal
+/// orientation by their coefficients, all compared by `total_cmp`, so that the representatives
+/// depend only on the lines and not on their order in `lines`.
+fn cluster_line_vec<T: Scalar>(mut lines: Vec<Line<T>>, tol_angle: T, tol_dist: T) -> Vec<Line<T>> {
+    // Widened to `f64`, losslessly, for its `total_cmp`.
+    let key = |l: &Line<T>| [l.angle_rad(), l.a, l.b, l.c].map(|v| v.to_f64().unwrap());
+    lines.sort_by(|l1, l2| {
+        let (k1, k2) = (key(l1), key(l2));
+        k1.iter().zip(&k2).fold(core::cmp::Ordering::Equal, |order, (x, y)| order.then(x.total_cmp(y)))
+    });
+
+    let mut representatives = AxisBuckets::new(tol_angle, tol_dist);
+    for line in lines {
+        representatives.insert(line);
+    }
+    representatives.lines
+}
+
+/// Lines that are distinct within `tol_angle` in angle and `tol_dist` in offset, as compared by
+/// `lines_within`, indexed by the buckets of their `AxisKey` so that each new line is only
+/// compared with the lines in the buckets near its own.
+struct AxisBuckets<T> {
+    grid: Option<AxisGrid<T>>,
+    buckets: HashMap<AxisKey, Vec<usize>>,
+    lines: Vec<Line<T>>,
+    tol_angle: T,
+    tol_dist: T,
+}
+
+impl<T: Scalar> AxisBuckets<T> {
+    fn new(tol_angle: T, tol_dist: T) -> Self {
+        Self {
+            grid: AxisGrid::new(tol_angle, tol_dist),
+            buckets: HashMap::new(),
+            lines: Vec::new(),
+            tol_angle,
+            tol_dist,
+        }
+    }
+
+    /// Checks whether `line` is within the tolerances of a line already added.
+    fn contains(&self, line: &Line<T>) -> bool {
+        let near = |other: &Line<T>| lines_within(other, line, self.tol_angle, self.tol_dist);
+        match self.grid.as_ref().and_then(|grid| grid.neighbours(line)) {
+            Some(keys) => keys.iter().filter_map(|key| self.buckets.get(key)).flatten().any(|&i| near(&self.lines[i])),
+            None => self.lines.iter().any(near),
+        }
+    }
+
+    /// Adds `line` unless it is within the tolerances of a line already added, and returns whether
+    /// it was added.
+    fn insert(&mut self, line: Line<T>) -> bool {
+        if self.contains(&line) {
+            return false;
+        }
+        if let Some(key) = self.grid.as_ref().and_then(|grid| grid.key(&line)) {
+            self.buckets.entry(key).or_default().push(self.lines.len());
+        }
+        self.lines.push(line);
+        true
+    }
+}
+
+/// A line quantized to a bucket of the angle of its unit normal and one of its offset from the
+/// origin, as assigned by an `AxisGrid`.
+///
+/// Lines that only differ in the scaling of their coefficients, e.g. candidates generated from
+/// different pairs of points for the same axis, have the same key. Unlike the coefficients, the
+/// key can be hashed without float rounding splitting equal lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct AxisKey {
+    angle_bucket: i64,
+    offset_bucket: i64,
+}
+
+/// The buckets of `AxisKey`s for lines within `tol_angle` in angle and `tol_dist` in offset, as
+/// compared by `lines_within`: buckets are at least as wide as the tolerances, so the lines near a
+/// line are in the buckets next to its own.
+struct AxisGrid<T> {
+    /// The number of angle buckets in the half turn `[-π/2, π/2)` of normal angles.
+    angle_buckets: i64,
+    angle_width: T,
+    tol_angle: T,
+    tol_dist: T,
+}
+
+impl<T: Scalar> AxisGrid<T> {
+    /// The most offset buckets searched around a line before falling back to comparing all lines.
+    const MAX_OFFSET_BUCKETS: i64 = 16;
+
+    /// Returns the grid for the tolerances, or `None` if they are too small or too large to bucket
+    /// by, i.e. if there would be fewer than three angle buckets.
+    fn new(tol_angle: T, tol_dist: T) -> Option<Self> {
+        if !tol_angle.is_normal() || !tol_dist.is_normal() || tol_angle < T::zero() || tol_dist < T::zero() {
+            return None;
+        }
+        let pi = T::from(core::f64::consts::PI).unwrap();
+        let angle_buckets = (pi / tol_angle).floor().to_i64().filter(|&n| n >= 3)?;
+        let angle_width = pi / T::from(angle_buckets).unwrap();
+        Some(Self { angle_buckets, angle_width, tol_angle, tol_dist })
+    }
+
+    /// Returns the angle bucket of a valid line's unit normal, with its offset along that normal,
+    /// from the line's polar form (see `Line::to_polar`).
+    fn angle_bucket_and_offset(&self, line: &Line<T>) -> Option<(i64, T)> {
+        if !line.is_valid() {
+            return None;
+        }
+        let half_pi = T::from(core::f64::consts::FRAC_PI_2).unwrap();
+        let (angle, offset) = line.to_polar();
+        let bucket = ((angle + half_pi) / self.angle_width).floor().to_i64()?;
+        Some((bucket.clamp(0, self.angle_buckets - 1), offset))
+    }
+
+    /// Returns the key of a valid line.
+    fn key(&self, line: &Line<T>) -> Option<AxisKey> {
+        let (angle_bucket, offset) = self.angle_bucket_and_offset(line)?;
+        let offset_bucket = (offset / self.tol_dist).floor().to_i64()?;
+        Some(AxisKey { angle_bucket, offset_bucket })
+    }
+
+    /// Returns the keys of the buckets holding every line within the tolerances of `line`, or
+    /// `None` if there are too many to search.
+    ///
+    /// The offsets of nearby lines differ by up to `tol_dist` plus a term that grows with the
+    /// offset and the angle between the lines. Normals in the first and last angle buckets are
+    /// near opposite, so across that wrap the offset changes sign.
+    fn neighbours(&self, line: &Line<T>) -> Option<Vec<AxisKey>> {
+        let (angle_bucket, offset) = self.angle_bucket_and_offset(line)?;
+        let cos = self.tol_angle.cos();
+        let margin = self.tol_dist + (offset.abs() + self.tol_dist) * (T::one() - cos) / cos;
+
+        let mut keys = Vec::new();
+        for step in [-1, 0, 1] {
+            let (bucket, offset) = match angle_bucket + step {
+                b if b < 0 => (b + self.angle_buckets, -offset),
+                b if b >= self.angle_buckets => (b - self.angle_buckets, -offset),
+                b => (b, offset),
+            };
+            let low = ((offset - margin) / self.tol_dist).floor().to_i64()?;
+            let high = ((offset + margin) / self.tol_dist).floor().to_i64()?;
+            if high - low >= Self::MAX_OFFSET_BUCKETS {
+                return None;
+            }
+            keys.extend((low..=high).map(|offset_bucket| AxisKey { angle_bucket: bucket, offset_bucket }));
+        }
+        Some(keys)
+    }
+}
+
+/// Returns all lines of symmetry for a given set of points, checking candidate lines in parallel
+/// with rayon. Requires the `rayon` feature.
+///
+/// Each task checks the candidates generated by the pairs of one point with the points after
+/// it. Without the sequential search's shared record of covered pairs, several pairs generate
+/// each axis, with different scaling, so the tasks' results are merged with `cluster_lines`. The
+/// points are visited in sorted order and the tasks' results merged in task order, so the lines
+/// returned, including their scaling, don't depend on the set's iteration order or on scheduling.
+/// Points are compared at `config::default_epsilon`; see `get_lines_of_sym_par_tol`.
+#[cfg(feature = "rayon")]
+pub fn get_lines_of_sym_par<T, S>(points: &HashSet<Point<T>, S>) -> HashSet<Line<T>, S>
+where
+    T: Scalar + Send + Sync,
+    S: BuildHasher + Default + Sync,
+{
+    get_lines_of_sym_par_tol(points, config::default_epsilon())
+}
+
+/// Like `get_lines_of_sym_par`, but with points compared within `tol`, in the input's units.
+/// Collinear sets are searched by `get_lines_of_sym_with` with `tol` as `SymmetryOptions::epsilon`.
+#[cfg(feature = "rayon")]
+pub fn get_lines_of_sym_par_tol<T, S>(points: &HashSet<Point<T>, S>, tol: T) -> HashSet<Line<T>, S>
+where
+    T: Scalar + Send + Sync,
+    S: BuildHasher + Default + Sync,
+{
+    use rayon::prelude::*;
+
+    let mut points_vec: Vec<&Point<T>> = points.iter().collect();
+    if points_vec.len() < 2 || collinear_through_line(&points_vec, tol).is_some() {
+        return collinear_lines_of_sym(points, tol);
+    }
+    if !mirror_partners_possible(&points_vec, tol) {
+        return HashSet::with_hasher(S::default());
+    }
+    points_vec.sort_by(|p, q| p.partial_cmp(q).unwrap_or(core::cmp::Ordering::Equal));
+
+    let n = points_vec.len();
+    let per_task: Vec<Vec<Line<T>>> = (0..n)
+        .into_par_iter()
+        .map(|i| {
+            ((i + 1)..n)
+                .map(|j| {
+                    let pair = UnorderedPointPair::new(points_vec[i], points_vec[j]);
+                    get_equidistant_line(pair.p1, pair.p2)
+                })
+                .filter(|line| verify_axis_tol(points, line, tol))
+                .collect()
+        })
+        .collect();
+
+    let cluster_tol = fingerprint_tolerance(tol);
+    cluster_line_vec(per_task.into_iter().flatten().collect(), cluster_tol, cluster_tol)
+        .into_iter()
+        .collect()
+}
+
+/// Returns all lines of symmetry for a given set of points, checking candidate lines on one
+/// thread per available CPU (see `std::thread::available_parallelism`), without rayon. Not
+/// available without the `std` feature.
+///
+/// Like `get_lines_of_sym_par`, the candidates generated by each point with the points after it
+/// are checked together; the points are dealt out to the threads in turn, so that each thread
+/// gets a similar share of long and short runs of candidates. The threads look up reflections in
+/// one shared index of the points, sorted by x. The results are merged in point order with
+/// `cluster_lines`, so they don't depend on scheduling. As with `get_lines_of_sym_par`, points
+/// are compared at `config::default_epsilon`; see `get_lines_of_sym_threaded_tol`.
+#[cfg(feature = "std")]
+pub fn get_lines_of_sym_threaded<T, S>(points: &HashSet<Point<T>, S>) -> HashSet<Line<T>, S>
+where
+    T: Scalar + Send + Sync,
+    S: BuildHasher + Default + Sync,
+{
+    get_lines_of_sym_threaded_tol(points, config::default_epsilon())
+}
+
+/// Like `get_lines_of_sym_threaded`, but with points compared within `tol`, in the input's units,
+/// as by `get_lines_of_sym_par_tol`.
+#[cfg(feature = "std")]
+pub fn get_lines_of_sym_threaded_tol<T, S>(points: &HashSet<Point<T>, S>, tol: T) -> HashSet<Line<T>, S>
+where
+    T: Scalar + Send + Sync,
+    S: BuildHasher + Default + Sync,
+{
+    let threads = std::thread::available_parallelism().map_or(1, core::num::NonZero::get);
+    search_on_threads(points, threads, tol).0
+}
+
+/// Runs the search of `get_lines_of_sym_threaded_tol` on up to `threads` threads. Also returns the
+/// number of distinct threads that checked candidates.
+#[cfg(feature = "std")]
+fn search_on_threads<T, S>(points: &HashSet<Point<T>, S>, threads: usize, tol: T) -> (HashSet<Line<T>, S>, usize)
+where
+    T: Scalar + Send + Sync,
+    S: BuildHasher + Default + Sync,
+{
+    let mut points_vec: Vec<&Point<T>> = points.iter().collect();
+    if points_vec.len() < 2 || collinear_through_line(&points_vec, tol).is_some() {
+        return (collinear_lines_of_sym(points, tol), 0);
+    }
+    if !mirror_partners_possible(&points_vec, tol) {
+        return (HashSet::with_hasher(S::default()), 0);
+    }
+    points_vec.sort_by(|p, q| p.partial_cmp(q).unwrap_or(core::cmp::Ordering::Equal));
+
+    let n = points_vec.len();
+    let threads = threads.clamp(1, n);
+    let by_x = SortedByX::new(&points_vec);
+    let is_axis = |line: &Line<T>| {
+        points_vec.iter().all(|p| {
+            line.try_reflected_point(p)
+                .and_then(|r| by_x.find_within(&points_vec, &r, tol, |p, q| p.approx_eq(q, tol)))
+                .and_then(|i| line.try_reflected_point(points_vec[i]))
+                .is_some_and(|back| back.approx_eq(p, tol))
+        })
+    };
+
+    // The axes found from the candidates of each point, in point order.
+    let mut per_point: Vec<Vec<Line<T>>> = (0..n).map(|_| Vec::new()).collect();
+    let mut thread_ids = std::collections::HashSet::new();
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|t| {
+                let (points_vec, is_axis) = (&points_vec, &is_axis);
+                scope.spawn(move || {
+                    let found: Vec<(usize, Vec<Line<T>>)> = (t..n)
+                        .step_by(threads)
+                        .map(|i| {
+                            let axes = ((i + 1)..n)
+                                .map(|j| {
+                                    let pair = UnorderedPointPair::new(points_vec[i], points_vec[j]);
+                                    get_equidistant_line(pair.p1, pair.p2)
+                                })
+                                .filter(|line| is_axis(line))
+                                .collect();
+                            (i, axes)
+                        })
+                        .collect();
+                    (std::thread::current().id(), found)
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            let (id, found) = worker.join().unwrap();
+            thread_ids.insert(id);
+            for (i, axes) in found {
+                per_point[i] = axes;
+            }
+        }
+    });
+
+    let axes = per_point.into_iter().flatten().collect();
+    let cluster_tol = fingerprint_tolerance(tol);
+    let lines = cluster_line_vec(axes, cluster_tol, cluster_tol);
+    (lines.into_iter().collect(), thread_ids.len())
+}
+
+/// The lines of symmetry of a set of fewer than two or collinear points, for the parallel searches,
+/// found by `get_lines_of_sym_with` with points compared within `tol`.
+#[cfg(feature = "std")]
+fn collinear_lines_of_sym<T: Scalar, S: BuildHasher + Default>(
+    points: &HashSet<Point<T>, S>,
+    tol: T,
+) -> HashSet<Line<T>, S> {
+    let opts = SymmetryOptions {
+        epsilon: tol.to_f64(),
+        ..SymmetryOptions::default()
+    };
+    get_lines_of_sym_with(points, &opts).lines
+}
+
+/// Checks whether two lines are within `tol_angle` of each other in angle and `tol_dist` in offset.
+fn lines_within<T: Scalar>(l1: &Line<T>, l2: &Line<T>, tol_angle: T, tol_dist: T) -> bool {
+    if l1.angle_between(l2) > tol_angle {
+        return false;
+    }
+
+    // Foot of the perpendicular from the origin onto `l1`.
+    let denom = l1.a * l1.a + l1.b * l1.b;
+    let foot_x = -l1.c * l1.a / denom;
+    let foot_y = -l1.c * l1.b / denom;
+
+    let distance = (l2.a * foot_x + l2.b * foot_y + l2.c).abs() / l2.a.hypot(l2.b);
+    distance <= tol_dist
+}
+
+/// Finds the point of the set equal to `target` within `tol` in each coordinate.
+///
+/// The hash lookup handles the usual case; a reflection whose coordinates differ from the
+/// stored point by rounding hashes differently, so the set is scanned as a fallback.
+fn find_point<'a, T: Scalar, S: BuildHasher>(
+    points: &'a HashSet<Point<T>, S>,
+    target: &Point<T>,
+    tol: T,
+) -> Option<&'a Point<T>> {
+    let same = |q: &&Point<T>| q.approx_eq(target, tol);
+    points.get(target).filter(same).or_else(|| points.iter().find(same))
+}
+
+/// The points of a search by the cell of a square grid they lie in, with cells as wide as a
+/// tolerance, for finding the point equal within it to a computed one, e.g. a reflection.
+///
+/// An exact hash of the coordinates misses a reflection that differs from its partner by
+/// rounding, however small. Points equal within the tolerance lie in the same or adjacent cells,
+/// so looking in the 3 × 3 cells around the target finds its partner wherever rounding puts it.
+struct QuantizedIndex<T, S = DefaultHashBuilder> {
+    cells: HashMap<(i64, i64), Vec<usize>, S>,
+    width: T,
+}
+
+impl<T: Scalar, S: BuildHasher + Default> QuantizedIndex<T, S> {
+    /// Indexes `points` in cells as wide as `tol`.
+    fn new(points: &[&Point<T>], tol: T) -> Self {
+        let mut index = Self { cells: HashMap::default(), width: tol };
+        for (i, p) in points.iter().enumerate() {
+            index.cells.entry(index.cell(p)).or_default().push(i);
+        }
+        index
+    }
+
+    /// Returns the cell of `p`. Coordinates too far out for the grid share the cells at its edges.
+    fn cell(&self, p: &Point<T>) -> (i64, i64) {
+        let coordinate = |v: T| {
+            let v = (v / self.width).floor();
+            v.to_i64().unwrap_or(if v > T::zero() { i64::MAX } else { i64::MIN })
+        };
+        (coordinate(p.x), coordinate(p.y))
+    }
+
+    /// Returns the index in `points` of the point nearest `target` among those equal to it within
+    /// the width of a cell in each coordinate.
+    fn get(&self, points: &[&Point<T>], target: &Point<T>) -> Option<usize> {
+        let (cx, cy) = self.cell(target);
+        let neighbours = [-1, 0, 1].into_iter().flat_map(|dx| [-1, 0, 1].map(|dy| (dx, dy)));
+        neighbours
+            .filter_map(|(dx, dy)| self.cells.get(&(cx.saturating_add(dx), cy.saturating_add(dy))))
+            .flatten()
+            .copied()
+            .filter(|&i| points[i].approx_eq(target, self.width))
+            .min_by(|&i, &j| {
+                let (di, dj) = (points[i].distance(target), points[j].distance(target));
+                di.partial_cmp(&dj).unwrap_or(core::cmp::Ordering::Equal)
+            })
+    }
+}
+
+/// The points of a search in order of x, for finding a point equal to a target within tolerance
+/// when the hash lookup misses. Only the points within tolerance of the target in x are compared.
+struct SortedByX<T> {
+    /// The x coordinate and index of each point, in order of x.
+    entries: Vec<(T, usize)>,
+    min_y: T,
+    max_y: T,
+}
+
+impl<T: Scalar> SortedByX<T> {
+    fn new(points_vec: &[&Point<T>]) -> Self {
+        let mut entries: Vec<(T, usize)> = points_vec.iter().enumerate().map(|(i, p)| (p.x, i)).collect();
+        entries.sort_by(|(x1, _), (x2, _)| x1.partial_cmp(x2).unwrap_or(core::cmp::Ordering::Equal));
+        let min_y = points_vec.iter().map(|p| p.y).fold(T::infinity(), T::min);
+        let max_y = points_vec.iter().map(|p| p.y).fold(T::neg_infinity(), T::max);
+        SortedByX { entries, min_y, max_y }
+    }
+
+    /// Returns the index of a point that `same` considers equal to `target`, comparing only the
+    /// points within `tol` of `target` in each coordinate.
+    fn find_within(
+        &self,
+        points_vec: &[&Point<T>],
+        target: &Point<T>,
+        tol: T,
+        same: impl Fn(&Point<T>, &Point<T>) -> bool,
+    ) -> Option<usize> {
+        // Most misses of invalid candidates reflect outside the bounding box.
+        let (&(min_x, _), &(max_x, _)) = (self.entries.first()?, self.entries.last()?);
+        if util::floats_gt_toler(min_x, target.x, tol)
+            || util::floats_gt_toler(target.x, max_x, tol)
+            || util::floats_gt_toler(self.min_y, target.y, tol)
+            || util::floats_gt_toler(target.y, self.max_y, tol)
+        {
+            return None;
+        }
+
+        let start = self.entries.partition_point(|&(x, _)| util::floats_gt_toler(target.x, x, tol));
+        self.entries[start..]
+            .iter()
+            .take_while(|&&(x, _)| !util::floats_gt_toler(x, target.x, tol))
+            .map(|&(_, i)| i)
+            .find(|&i| same(points_vec[i], target))
+    }
+
+    /// Returns the index of the point nearest to `target` and its distance, or `None` if there
+    /// are no points.
+    fn nearest(&self, points_vec: &[&Point<T>], target: &Point<T>) -> Option<(usize, T)> {
+        self.nearest_k(points_vec, target, 1).first().copied()
+    }
+
+    /// Returns the indices of the `k` points nearest to `target` with their distances, nearest
+    /// first, or of all the points if there are fewer.
+    ///
+    /// The points are visited outwards from `target` in order of their distance in x, which stops
+    /// once that alone exceeds the distance of the `k`th nearest point so far.
+    fn nearest_k(&self, points_vec: &[&Point<T>], target: &Point<T>, k: usize) -> Vec<(usize, T)> {
+        let start = self.entries.partition_point(|&(x, _)| x < target.x);
+        let (mut below, mut above) = (start, start);
+        let mut best: Vec<(usize, T)> = Vec::with_capacity(k + 1);
+
+        loop {
+            let left = below.checked_sub(1).map(|e| (e, target.x - self.entries[e].0));
+            let right = self.entries.get(above).map(|&(x, _)| (above, x - target.x));
+            let (e, dx) = match (left, right) {
+                (Some(l), Some(r)) if r.1 < l.1 => r,
+                (Some(l), _) => l,
+                (None, Some(r)) => r,
+                (None, None) => break,
+            };
+            if best.len() == k && best.last().is_none_or(|&(_, distance)| dx > distance) {
+                break;
+            }
+            if e < start {
+                below = e;
+            } else {
+                above = e + 1;
+            }
+
+            let i = self.entries[e].1;
+            let distance = points_vec[i].distance(target);
+            let rank = best.partition_point(|&(_, nearer)| nearer <= distance);
+            if rank < k {
+                best.insert(rank, (i, distance));
+                best.truncate(k);
+            }
+        }
+
+        best
+    }
+}
+
+/// The points of a set indexed for finding the point a reflection lands on, as passed to a
+/// `ReflectionMatcher`.
+pub struct PointIndex<'a, T = f64> {
+    points_vec: Vec<&'a Point<T>>,
+    position: HashMap<&'a Point<T>, usize>,
+    by_x: SortedByX<T>,
+}
+
+impl<'a, T: Scalar> PointIndex<'a, T> {
+    /// Indexes the points of `points`.
+    pub fn new<S: BuildHasher>(points: &'a HashSet<Point<T>, S>) -> Self {
+        let points_vec: Vec<&Point<T>> = points.iter().collect();
+        let position = points_vec.iter().enumerate().map(|(i, p)| (*p, i)).collect();
+        let by_x = SortedByX::new(&points_vec);
+        Self { points_vec, position, by_x }
+    }
+
+    /// Returns the indexed points.
+    pub fn points(&self) -> &[&'a Point<T>] {
+        &self.points_vec
+    }
+
+    /// Returns the point equal to `target` by `==`, i.e. within `config::default_epsilon`.
+    ///
+    /// The hash lookup handles the usual case, and a search of the points near `target` in x
+    /// handles a target that differs from its point by rounding.
+    pub fn get(&self, target: &Point<T>) -> Option<&'a Point<T>> {
+        match self.position.get_key_value(target) {
+            Some((p, _)) => Some(*p),
+            None => self.find_within(target, config::default_epsilon(), |p, q| p == q),
+        }
+    }
+
+    /// Returns a point that `same` considers equal to `target`, comparing only the points within
+    /// `tol` of `target` in each coordinate, e.g. for radial or looser matching.
+    pub fn find_within(
+        &self,
+        target: &Point<T>,
+        tol: T,
+        same: impl Fn(&Point<T>, &Point<T>) -> bool,
+    ) -> Option<&'a Point<T>> {
+        self.match_position(target, tol, same).map(|i| self.points_vec[i])
+    }
+
+    /// Checks whether a point of the set is at most `tol` from `p`, with `tol` given explicitly in
+    /// place of the scalar's tolerance, e.g. by a matcher honouring a per-call tolerance.
+    pub fn contains_within(&self, p: &Point<T>, tol: T) -> bool {
+        self.position_within(p, tol).is_some()
+    }
+
+    /// Returns the point of the set nearest to `p` and its distance from `p`, or `None` if the set
+    /// is empty.
+    pub fn nearest(&self, p: &Point<T>) -> Option<(&'a Point<T>, T)> {
+        self.by_x.nearest(&self.points_vec, p).map(|(i, distance)| (self.points_vec[i], distance))
+    }
+
+    /// Returns the position of an indexed point in `points`.
+    fn position_of(&self, p: &Point<T>) -> Option<usize> {
+        self.position.get(p).copied()
+    }
+
+    /// Returns the position in `points` of the point `find_within` returns.
+    fn match_position(&self, target: &Point<T>, tol: T, same: impl Fn(&Point<T>, &Point<T>) -> bool) -> Option<usize> {
+        self.by_x.find_within(&self.points_vec, target, tol, same)
+    }
+
+    /// Returns the position in `points` of a point at most `tol` from `p`, as `contains_within`
+    /// looks for.
+    fn position_within(&self, p: &Point<T>, tol: T) -> Option<usize> {
+        self.match_position(p, tol, |q, target| q.approx_eq_radial(target, tol))
+    }
+}
+
+/// Returns the points of `a` equal by `==`, i.e. within the scalar's tolerance, to no point of `b`.
+///
+/// Unlike `HashSet::difference`, which compares the exact bits of the coordinates, a point of `a`
+/// that differs from a point of `b` by rounding, e.g. a point and its reflection reflected back,
+/// is matched. The points of `b` are indexed with `PointIndex`, so this takes `O(n log n)` time
+/// for sets that aren't crowded in x.
+pub fn set_difference<T: Scalar, S: BuildHasher + Default>(
+    a: &HashSet<Point<T>, S>,
+    b: &HashSet<Point<T>, S>,
+) -> HashSet<Point<T>, S> {
+    let index = PointIndex::new(b);
+    a.iter().filter(|p| index.get(p).is_none()).copied().collect()
+}
+
+/// Returns the points of `a` equal by `==` to some point of `b`. Like `set_difference`, points are
+/// matched within the scalar's tolerance rather than by their exact bits.
+pub fn set_intersection<T: Scalar, S: BuildHasher + Default>(
+    a: &HashSet<Point<T>, S>,
+    b: &HashSet<Point<T>, S>,
+) -> HashSet<Point<T>, S> {
+    let index = PointIndex::new(b);
+    a.iter().filter(|p| index.get(p).is_some()).copied().collect()
+}
+
+/// Returns the union of `a` and `b`, with each point of `b` equal by `==` to a point of `a` merged
+/// with it into their midpoint, e.g. to combine scanned fragments that overlap without leaving
+/// near-duplicate points, which would have no mirror partners, for the search.
+///
+/// Like `set_difference`, points are matched within the scalar's tolerance, with the points of
+/// `a` indexed with `PointIndex`. A point of `a` matched by several points of `b` is merged with
+/// the first of them in the iteration order of `b`, and the others are kept.
+pub fn merge_sets<T: Scalar, S: BuildHasher + Default>(
+    a: &HashSet<Point<T>, S>,
+    b: &HashSet<Point<T>, S>,
+) -> HashSet<Point<T>, S> {
+    let index = PointIndex::new(a);
+    let mut partners: HashMap<&Point<T>, &Point<T>> = HashMap::default();
+    let mut merged = HashSet::default();
+    for q in b {
+        match index.get(q) {
+            Some(p) if !partners.contains_key(p) => {
+                partners.insert(p, q);
+            }
+            _ => {
+                merged.insert(*q);
+            }
+        }
+    }
+
+    let two = T::one() + T::one();
+    merged.extend(a.iter().map(|p| match partners.get(p) {
+        Some(q) => Point::new((p.x + q.x) / two, (p.y + q.y) / two),
+        None => *p,
+    }));
+    merged
+}
+
+/// Decides which point of a set, if any, a point's reflection across a candidate axis lands on,
+/// for `get_lines_of_sym_with_matcher`.
+///
+/// Implementing this adapts the search to points carrying labels or weights, or to other notions
+/// of equality, without changing the candidate generation.
+pub trait ReflectionMatcher<T: Scalar = f64> {
+    /// Returns the point of `set` matching `reflected`, the reflection of `point`, or `None` if the
+    /// reflection has no partner. `point` is itself in `set`, so that data attached to it can be
+    /// compared with the partner's.
+    ///
+    /// Defaults to the point equal to `reflected` within tolerance, as found by `PointIndex::get`.
+    fn matches<'a>(
+        &self,
+        point: &Point<T>,
+        reflected: &Point<T>,
+        set: &PointIndex<'a, T>,
+    ) -> Option<&'a Point<T>> {
+        let _ = point;
+        set.get(reflected)
+    }
+}
+
+/// The default `ReflectionMatcher`, matching a reflection to the point equal to it within tolerance.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ToleranceMatcher;
+
+impl<T: Scalar> ReflectionMatcher<T> for ToleranceMatcher {}
+
+/// Returns all lines of symmetry for a set of points, with reflections matched to points by
+/// `matcher`.
+///
+/// This is the search of `get_lines_of_sym_with` with default options, on the points as given,
+/// except that a candidate line is checked by reflecting every point across it and asking
+/// `matcher` for the reflection's partner; the line is an axis if every point has one. A point on
+/// the line is offered its own reflection, i.e. itself. With `ToleranceMatcher`, this finds the
+/// same lines as `get_lines_of_sym` on sets of moderate extent near the origin.
+///
+/// The matcher is given the points as they are, e.g. to look up data attached to them, so unlike
+/// `get_lines_of_sym_with`, the search isn't normalized: the tolerance is the absolute
+/// `config::default_epsilon`, applied to reflections computed in the input's coordinates. For
+/// points far from the origin or spread far wider than the tolerance, reflections are rounded by
+/// more than the tolerance and true axes can be missed.
+pub fn get_lines_of_sym_with_matcher<T, S, M>(
+    points: &HashSet<Point<T>, S>,
+    matcher: &M,
+) -> HashSet<Line<T>, S>
+where
+    T: Scalar,
+    S: BuildHasher + Default,
+    M: ReflectionMatcher<T> + ?Sized,
+{
+    let reflect = |line: &Line<T>, p: &Point<T>| line.try_reflected_point(p);
+    // The matcher is given the points as they are, so the search isn't normalized.
+    let opts = SymmetryOptions {
+        quiet: true,
+        ..SymmetryOptions::default()
+    };
+    let mut stats = SymmetryStats::default();
+    let ws = &mut Workspace::new();
+    search_lines_of_sym(points, &opts, &mut stats, None, reflect, Some(matcher), ws, &mut keep_searching).lines
+}
+
+/// Returns the lines of symmetry of a set of points that pass through `center`.
+///
+/// Any such axis maps a reference point (the one farthest from the center) to a point at the same
+/// distance from the center, so the only candidates are the line through the center and the
+/// reference point, and the perpendicular bisectors between the reference point and each point
+/// equidistant from the center. This needs `O(n)` candidates rather than one per pair of points,
+/// which makes it much cheaper than `get_lines_of_sym` when the center is known, e.g. as the
+/// center of rotation.
+///
+/// If every point coincides with the center, every line through it is an axis; an empty set is
+/// returned in that case. Points are compared at `config::default_epsilon`; see
+/// `axes_through_center_tol`.
+pub fn axes_through_center<T: Scalar, S: BuildHasher>(
+    points: &HashSet<Point<T>, S>,
+    center: &Point<T>,
+) -> HashSet<Line<T>> {
+    axes_through_center_tol(points, center, config::default_epsilon())
+}
+
+/// Like `axes_through_center`, but with points compared within `tol`, in the input's units, as by
+/// `verify_axis_tol`.
+pub fn axes_through_center_tol<T: Scalar, S: BuildHasher>(
+    points: &HashSet<Point<T>, S>,
+    center: &Point<T>,
+    tol: T,
+) -> HashSet<Line<T>> {
+    let mut axes: HashSet<Line<T>> = HashSet::new();
+
+    let reference = points.iter().filter(|p| !p.approx_eq(center, tol)).max_by(|p, q| {
+        p.distance(center)
+            .partial_cmp(&q.distance(center))
+            .unwrap_or(core::cmp::Ordering::Equal)
+    });
+    let Some(reference) = reference else {
+        return axes;
+    };
+
+    let radius = reference.distance(center);
+    let candidates = points
+        .iter()
+        .filter(|q| (q.distance(center) - radius).abs() <= fingerprint_tolerance(tol))
+        .map(|q| {
+            if q == reference {
+                get_through_line(center, reference)
+            } else {
+                get_equidistant_line(reference, q)
+            }
+        });
+
+    for candidate in candidates {
+        if candidate.is_point_on_line_tol(center, tol) && verify_axis_tol(points, &candidate, tol) {
+            axes.insert(candidate);
+        }
+    }
+
+    axes
+}
+
+/// Returns the `k` such that the vertical line `x = k` is a line of symmetry of `points`, if any.
+///
+/// This is a fast path for samples of a curve `y = f(x)`, e.g. to check whether a sampled function
+/// is even about some `x = k`. With one sample per `x`, the only candidate is the vertical line
+/// midway between the smallest and largest `x`, so only that line is checked.
+pub fn vertical_axis_of_symmetry<T: Scalar, S: BuildHasher>(points: &HashSet<Point<T>, S>) -> Option<T> {
+    let mut xs = points.iter().map(|p| p.x);
+    let first = xs.next()?;
+    let (min_x, max_x) = xs.fold((first, first), |(lo, hi), x| (lo.min(x), hi.max(x)));
+
+    let k = (min_x + max_x) / (T::one() + T::one());
+    verify_axis(points, &Line::new(T::one(), T::zero(), -k)).then_some(k)
+}
+
+/// Returns an iterator over the candidate lines of symmetry for a set of points.
+///
+/// The iterator lazily yields the perpendicular bisector of every distinct pair of points,
+/// followed by the line through all the points if they are collinear. Every line of symmetry
+/// of the set is among these candidates, so this can be combined with a custom symmetry
+/// predicate in place of the built-in check in `get_lines_of_sym`.
+pub fn candidate_axes<T: Scalar, S: BuildHasher>(points: &HashSet<Point<T>, S>) -> impl Iterator<Item = Line<T>> + '_ {
+    let points_vec: Vec<&Point<T>> = points.iter().collect();
+    let through_line = collinear_through_line(&points_vec, config::default_epsilon());
+
+    let n = points_vec.len();
+    (0..n)
+        .flat_map(move |i| ((i + 1)..n).map(move |j| (i, j)))
+        .map(move |(i, j)| get_equidistant_line(points_vec[i], points_vec[j]))
+        .chain(through_line)
+}
+
+/// Returns all lines of symmetry for a set of labeled points.
+///
+/// A line is a line of symmetry if every point has a reflection across it in the set that
+/// carries the same label. Geometric axes that swap differently-labeled points are rejected.
+///
+/// Each location is expected to carry a single label; if the same location appears with
+/// several labels, only one of them is considered.
+///
+/// # Notes
+///
+/// - At least two points are required to define a line of symmetry.
+/// - If the input set contains fewer than two points, an empty set is returned and a warning is printed.
+pub fn get_lines_of_sym_labeled<T: Eq + Hash>(points: &HashSet<LabeledPoint<T>>) -> HashSet<Line> {
+    get_lines_of_sym_labeled_with(points, &SymmetryOptions::default())
+}
+
+/// Like `get_lines_of_sym_labeled`, but with points compared at `opts.epsilon` if set, and without
+/// the warning for fewer than two points if `opts.quiet` is set. The other options don't apply to
+/// labeled points.
+pub fn get_lines_of_sym_labeled_with<T: Eq + Hash>(
+    points: &HashSet<LabeledPoint<T>>,
+    opts: &SymmetryOptions,
+) -> HashSet<Line> {
+    let attributed: Vec<(&Point, &T)> = points.iter().map(|lp| (&lp.point, &lp.label)).collect();
+    let tol = opts.epsilon.unwrap_or_else(config::default_epsilon);
+    lines_of_sym_preserving(&attributed, |l1, l2| l1 == l2, tol, opts.quiet)
+}
+
+/// Returns all lines of symmetry for a set of weighted points.
+///
+/// A line is a line of symmetry if every point has a reflection across it in the set whose
+/// weight matches the point's weight within floating-point tolerance. This describes the
+/// symmetry of a discrete mass distribution.
+///
+/// # Notes
+///
+/// - At least two points are required to define a line of symmetry.
+/// - If the input set contains fewer than two points, an empty set is returned and a warning is printed.
+pub fn get_lines_of_sym_weighted(points: &HashSet<WeightedPoint>) -> HashSet<Line> {
+    get_lines_of_sym_weighted_with(points, &SymmetryOptions::default())
+}
+
+/// Like `get_lines_of_sym_weighted`, but with points compared at `opts.epsilon` if set, and without
+/// the warning for fewer than two points if `opts.quiet` is set. Weights are still compared at the
+/// scalar's tolerance. The other options don't apply to weighted points.
+pub fn get_lines_of_sym_weighted_with(points: &HashSet<WeightedPoint>, opts: &SymmetryOptions) -> HashSet<Line> {
+    let attributed: Vec<(&Point, f64)> = points.iter().map(|wp| (&wp.point, wp.weight)).collect();
+    let tol = opts.epsilon.unwrap_or_else(config::default_epsilon);
+    lines_of_sym_preserving(&attributed, |w1, w2| util::floats_equal_toler(*w1, *w2), tol, opts.quiet)
+}
+
+/// Finds the lines of symmetry of a set of points carrying extra data, such that every point's
+/// reflection carries data that is `compatible` with its own, with points compared within `tol`.
+/// Fewer than two points give no lines, with a warning unless `quiet` is set.
+///
+/// Each location is expected to appear once; if it appears several times, only one of its
+/// entries is considered.
+fn lines_of_sym_preserving<A>(
+    points: &[(&Point, A)],
+    compatible: impl Fn(&A, &A) -> bool,
+    tol: f64,
+    // Only read for the warning, which needs `std`.
+    #[cfg_attr(not(feature = "std"), allow(unused_variables))] quiet: bool,
+) -> HashSet<Line> {
+    let mut lines_set: HashSet<Line> = HashSet::new();
+
+    if points.len() < 2 {
+        #[cfg(feature = "std")]
+        if !quiet {
+            eprintln!("Warning: at least 2 points needed to find lines of symmetry.");
+        }
+        return lines_set;
+    }
+
+    let geometric: Vec<&Point> = points.iter().map(|(p, _)| *p).collect();
+    let index: QuantizedIndex<f64> = QuantizedIndex::new(&geometric, tol);
+
+    let mut covered = PairBitset::new(points.len());
+
+    for i in 0..points.len() {
+        for j in (i + 1)..points.len() {
+            // Only pairs of points with compatible data can be mirror partners.
+            if covered.contains(i, j) || !compatible(&points[i].1, &points[j].1) {
+                continue;
+            }
+
+            let e_pair = UnorderedPointPair::new(points[i].0, points[j].0);
+            let e_line = get_equidistant_line(e_pair.p1, e_pair.p2);
+
+            let mut valid_line = true;
+            for (k, (point, data)) in points.iter().enumerate() {
+                let reflection = e_line.try_reflected_point(point);
+
+                match reflection.and_then(|reflection| index.get(&geometric, &reflection)) {
+                    Some(r) if compatible(data, &points[r].1) => {
+                        // Covered pair; it would generate the same line.
+                        if r != k {
+                            covered.insert(k, r);
+                        }
+                    }
+                    _ => valid_line = false,
+                }
+            }
+
+            if valid_line {
+                lines_set.insert(e_line);
+            }
+        }
+    }
+
+    // A line through all the points maps every point onto itself, so the data is preserved.
+    if let Some(through_line) = collinear_through_line(&geometric, tol) {
+        lines_set.insert(through_line);
+    }
+
+    lines_set
+}
+
+/// A set of unordered pairs of distinct indices below `n`, stored as one bit per pair.
+#[derive(Debug)]
+struct PairBitset {
+    n: usize,
+    words: Vec<u64>,
+}
+
+impl PairBitset {
+    fn new(n: usize) -> Self {
+        let pairs = n * n.saturating_sub(1) / 2;
+        Self {
+            n,
+            words: vec![0; pairs.div_ceil(64)],
+        }
+    }
+
+    /// Empties the set and makes it hold pairs of indices below `n`, keeping its allocation.
+    fn clear(&mut self, n: usize) {
+        let pairs = n * n.saturating_sub(1) / 2;
+        self.n = n;
+        self.words.clear();
+        self.words.resize(pairs.div_ceil(64), 0);
+    }
+
+    /// Returns the position of the pair `{i, j}` among all pairs, ordered by smaller then larger index.
+    fn position(&self, i: usize, j: usize) -> usize {
+        let (i, j) = if i < j { (i, j) } else { (j, i) };
+        i * (2 * self.n - i - 1) / 2 + (j - i - 1)
+    }
+
+    fn insert(&mut self, i: usize, j: usize) {
+        let pos = self.position(i, j);
+        self.words[pos / 64] |= 1 << (pos % 64);
+    }
+
+    fn contains(&self, i: usize, j: usize) -> bool {
+        let pos = self.position(i, j);
+        self.words[pos / 64] & (1 << (pos % 64)) != 0
+    }
+}
+
+/// The pairs of points whose reflection has been covered by a candidate line during a search.
+enum CoveredPairs<'a> {
+    /// Every pair of points.
+    All(&'a mut PairBitset),
+    /// Only the given pairs, in increasing order, with a bit each for whether it is covered.
+    /// Other pairs are never covered.
+    Among(&'a [(usize, usize)], &'a mut Vec<u64>),
+}
+
+impl CoveredPairs<'_> {
+    fn insert(&mut self, i: usize, j: usize) {
+        match self {
+            Self::All(bitset) => bitset.insert(i, j),
+            Self::Among(pairs, words) => {
+                if let Ok(pos) = pairs.binary_search(&(i.min(j), i.max(j))) {
+                    words[pos / 64] |= 1 << (pos % 64);
+                }
+            }
+        }
+    }
+
+    fn contains(&self, i: usize, j: usize) -> bool {
+        match self {
+            Self::All(bitset) => bitset.contains(i, j),
+            Self::Among(pairs, words) => pairs
+                .binary_search(&(i.min(j), i.max(j)))
+                .is_ok_and(|pos| words[pos / 64] & (1 << (pos % 64)) != 0),
+        }
+    }
+}
+
+/// The bounding box of a non-empty set of points, with the points of the set at both ends of
+/// each of its sides.
+struct BoundingBox<'a, T> {
+    min: Point<T>,
+    max: Point<T>,
+    /// For the sides of least x, greatest x, least y and greatest y in turn, the points at the
+    /// end of lesser and of greater other coordinate.
+    extremes: [&'a Point<T>; 8],
+}
+
+/// A key ordering points lexicographically, minimized by a `BoundingBox` extreme.
+type ExtremeKey<T> = fn(&Point<T>) -> (T, T);
+
+impl<'a, T: Scalar> BoundingBox<'a, T> {
+    fn new(points: &[&'a Point<T>]) -> Self {
+        // Each extreme point minimizes one of these keys, lexicographically.
+        let keys: [ExtremeKey<T>; 8] = [
+            |p| (p.x, p.y),
+            |p| (p.x, -p.y),
+            |p| (-p.x, p.y),
+            |p| (-p.x, -p.y),
+            |p| (p.y, p.x),
+            |p| (p.y, -p.x),
+            |p| (-p.y, p.x),
+            |p| (-p.y, -p.x),
+        ];
+        let extremes = keys.map(|key| {
+            let least = |a: &&&Point<T>, b: &&&Point<T>| key(a).partial_cmp(&key(b)).unwrap();
+            *points.iter().min_by(least).unwrap()
+        });
+
+        BoundingBox {
+            min: Point { x: extremes[0].x, y: extremes[4].y },
+            max: Point { x: extremes[2].x, y: extremes[6].y },
+            extremes,
+        }
+    }
+
+    /// Checks whether the reflections of the extreme points across `line` lie within the box,
+    /// within `tol`.
+    fn extremes_reflect_inside(&self, line: &Line<T>, tol: T) -> bool {
+        self.extremes.iter().all(|p| match line.try_reflected_point(p) {
+            Some(r) => {
+                util::floats_ge_toler(r.x, self.min.x, tol)
+                    && util::floats_le_toler(r.x, self.max.x, tol)
+                    && util::floats_ge_toler(r.y, self.min.y, tol)
+                    && util::floats_le_toler(r.y, self.max.y, tol)
+            }
+            None => false,
+        })
+    }
+}
+
+/// Returns the pairs of points, by index in `points_vec`, that generate candidates in the sparse
+/// mode of `SymmetryOptions::candidate_neighbours`: each point with its `neighbours` nearest other
+/// points and with the point nearest to its reflection through the centroid, in increasing order.
+fn sparse_candidate_pairs<T: Scalar>(
+    points_vec: &[&Point<T>],
+    by_x: &SortedByX<T>,
+    neighbours: usize,
+) -> Vec<(usize, usize)> {
+    let Some(center) = centroid(points_vec) else {
+        return Vec::new();
+    };
+
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+    for (i, p) in points_vec.iter().enumerate() {
+        let across = Point::new(center.x + center.x - p.x, center.y + center.y - p.y);
+        let partners = by_x
+            .nearest_k(points_vec, p, neighbours + 1)
+            .into_iter()
+            .chain(by_x.nearest(points_vec, &across))
+            .map(|(j, _)| j);
+        pairs.extend(partners.filter(|&j| j != i).map(|j| (i.min(j), i.max(j))));
+    }
+    pairs.sort_unstable();
+    pairs.dedup();
+    pairs
+}
+
+/// Returns the line through all the given points, if there are at least two and they are collinear
+/// within `tol` (see `Line::is_point_on_line_within`).
+///
+/// The line is taken through the least point in order of exact coordinates, x then y, and the
+/// point farthest from it, so that nearby points don't make its direction ill-conditioned. Ties
+/// in distance are broken by the same order, so the coefficients of the line don't depend on the
+/// order of `points`, e.g. on the iteration order of a set.
+fn collinear_through_line<T: Scalar>(points: &[&Point<T>], tol: T) -> Option<Line<T>> {
+    if points.len() < 2 {
+        return None;
+    }
+
+    let exact = |p: &Point<T>, q: &Point<T>| {
+        let by_x = p.x.partial_cmp(&q.x).unwrap_or(core::cmp::Ordering::Equal);
+        by_x.then(p.y.partial_cmp(&q.y).unwrap_or(core::cmp::Ordering::Equal))
+    };
+    let first = points.iter().copied().min_by(|p, q| exact(p, q))?;
+    let farthest = points.iter().copied().max_by(|p, q| {
+        let by_distance = p.distance(first).partial_cmp(&q.distance(first));
+        by_distance.unwrap_or(core::cmp::Ordering::Equal).then(exact(p, q))
+    })?;
+
+    let line = get_through_line(first, farthest);
+    if points.iter().all(|p| line.is_point_on_line_within(p, tol)) {
+        Some(line)
+    } else {
+        None
+    }
+}
+
+/// Checks whether the given points all lie on one line, within `config::default_epsilon`. Fewer
+/// than two points are not collinear.
+pub fn are_collinear<T: Scalar>(points: &[&Point<T>]) -> bool {
+    are_collinear_tol(points, config::default_epsilon())
+}
+
+/// Like `are_collinear`, but within `tol`, in the units of the points.
+pub fn are_collinear_tol<T: Scalar>(points: &[&Point<T>], tol: T) -> bool {
+    collinear_through_line(points, tol).is_some()
+}
+
+/// The shape formed by a set of points, as returned by `input_shape_kind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    /// The set has no points.
+    Empty,
+    /// The points all coincide within floating-point tolerance, e.g. there is only one.
+    Single,
+    /// The points lie on one line but don't all coincide.
+    Collinear,
+    /// The points don't lie on one line.
+    Planar,
+}
+
+/// Returns the kind of shape formed by a set of points: none, a single location, a line, or a
+/// planar shape.
+///
+/// Each kind has its own lines of symmetry: none, infinitely many (see
+/// `get_lines_of_sym_or_infinite`), at most two for collinear points (the line through them and
+/// its perpendicular bisector), and the axes found by the pairwise search for planar shapes. The
+/// kind is decided at `config::default_epsilon`; use `input_shape_kind_tol` with the
+/// `SymmetryOptions::epsilon` of a search to get the kind that search sees.
+pub fn input_shape_kind<T: Scalar, S: BuildHasher>(points: &HashSet<Point<T>, S>) -> InputKind {
+    input_shape_kind_tol(points, config::default_epsilon())
+}
+
+/// Like `input_shape_kind`, but with the kind decided within `tol`, in the units of the points.
+pub fn input_shape_kind_tol<T: Scalar, S: BuildHasher>(points: &HashSet<Point<T>, S>, tol: T) -> InputKind {
+    let points_vec: Vec<&Point<T>> = points.iter().collect();
+    let Some(first) = points_vec.first() else {
+        return InputKind::Empty;
+    };
+
+    if points_vec.iter().all(|p| p.distance(first) <= tol) {
+        InputKind::Single
+    } else if are_collinear_tol(&points_vec, tol) {
+        InputKind::Collinear
+    } else {
+        InputKind::Planar
+    }
+}
+
+/// Returns the lines of symmetry of a set of collinear points, given the line through them.
+///
+/// The line through the points maps every point onto itself, so it is always an axis. The only
+/// other possible axis is perpendicular to it, swapping the two extreme points, and it is an axis
+/// if the points' positions along the line are symmetric, as checked by `is_axis`. The line
+/// through the points is left out unless `include_through_line` is set.
+fn collinear_axes<T: Scalar>(
+    points_vec: &[&Point<T>],
+    through_line: Line<T>,
+    include_through_line: bool,
+    is_axis: impl Fn(&Line<T>) -> bool,
+) -> Vec<Line<T>> {
+    // The direction of the through-line is (b, -a).
+    let project = |p: &Point<T>| through_line.b * p.x - through_line.a * p.y;
+    let (mut lo, mut hi) = (points_vec[0], points_vec[0]);
+    for p in points_vec {
+        if project(p) < project(lo) {
+            lo = p;
+        }
+        if project(p) > project(hi) {
+            hi = p;
+        }
+    }
+
+    let mut axes = Vec::new();
+
+    let extremes = UnorderedPointPair::new(lo, hi);
+    let bisector = get_equidistant_line(extremes.p1, extremes.p2);
+    if is_axis(&bisector) {
+        axes.push(bisector);
+    }
+
+    if include_through_line {
+        axes.push(through_line);
+    }
+
+    axes
+}
+
+/// Returns the perpendicular bisector of the segment from `p1` to `p2`.
+///
+/// Every point on the returned line is equidistant from `p1` and `p2`, and the line is
+/// perpendicular to the segment. Returns `None` if the points coincide, in which case the
+/// bisector is undefined.
+pub fn perpendicular_bisector<T: Scalar>(p1: &Point<T>, p2: &Point<T>) -> Option<Line<T>> {
+    if p1 == p2 {
+        return None;
+    }
+
+    Some(get_equidistant_line(p1, p2))
+}
+
+pub(crate) fn get_equidistant_line<T: Scalar>(p1: &Point<T>, p2: &Point<T>) -> Line<T> {
+    // Returns a line that is equidistant from p1 and p2; internal alias of `perpendicular_bisector`
+    // without the coincidence check.
+    let half = T::one() / (T::one() + T::one());
+    let a = p2.x - p1.x;
+    let b = p2.y - p1.y;
+    let c = half * (p1.x.powi(2) + p1.y.powi(2) - p2.x.powi(2) - p2.y.powi(2));
+
+    Line::new(a, b, c)
+}
+
+pub fn get_through_line<T: Scalar>(p1: &Point<T>, p2: &Point<T>) -> Line<T> {
+    // Returns a line that goes through p1 and p2
+    let a = p2.y - p1.y;
+    let b = p1.x - p2.x;
+    let c = -(a * p1.x + b * p1.y);
+
+    Line::new(a, b, c)
+}
+
+/// Returns the symmetry defect of a set of points across `steps` axis orientations through
+/// its centroid.
+///
+/// The orientations are evenly spaced over `[0, π)`, starting at the horizontal. The defect of
+/// an axis is the mean distance from each point's reflection across it to the nearest point of
+/// the set, so it is 0 for a line of symmetry and small for a near-symmetry that strict detection
+/// rejects. Plotting the profile shows both.
+///
+/// # Returns
+///
+/// `(angle, defect)` pairs, with angles in radians. The result is empty if `steps` is 0 or the
+/// set is empty.
+pub fn symmetry_profile(points: &HashSet<Point>, steps: usize) -> Vec<(f64, f64)> {
+    if points.is_empty() {
+        return Vec::new();
+    }
+
+    let n = points.len() as f64;
+    let cx = points.iter().map(|p| p.x).sum::<f64>() / n;
+    let cy = points.iter().map(|p| p.y).sum::<f64>() / n;
+
+    (0..steps)
+        .map(|k| {
+            let angle = k as f64 * core::f64::consts::PI / steps as f64;
+            let (sin, cos) = Float::sin_cos(angle);
+            (angle, axis_defect(points, &Line::new(-sin, cos, sin * cx - cos * cy)))
+        })
+        .collect()
+}
+
+/// Returns the mean distance from each point's reflection across `axis` to the nearest point of
+/// the set, as plotted by `symmetry_profile`. The set must not be empty.
+fn axis_defect(points: &HashSet<Point>, axis: &Line) -> f64 {
+    let total: f64 = points
+        .iter()
+        .map(|p| match axis.try_reflected_point(p) {
+            Some(reflection) => points
+                .iter()
+                .map(|q| q.distance(&reflection))
+                .fold(f64::INFINITY, f64::min),
+            None => f64::INFINITY,
+        })
+        .sum();
+
+    total / points.len() as f64
+}
+
+/// Splits the points into spatial clusters and returns each cluster with its own lines of symmetry,
+/// for data holding several separate shapes, whose symmetries don't hold for the whole set.
+///
+/// Clusters are formed by single linkage: two points are in the same cluster if a chain of points,
+/// each within `cluster_radius` of the next, connects them. This takes `O(n²)` distance checks.
+/// The clusters are ordered by their lowest point, by x then y. A cluster of a single point has no
+/// lines of symmetry reported.
+pub fn detect_clusters(points: &HashSet<Point>, cluster_radius: f64) -> Vec<(HashSet<Point>, HashSet<Line>)> {
+    let points_vec: Vec<&Point> = points.iter().collect();
+    let clusters = single_linkage_clusters(&points_vec, |p, q| p.distance(q) <= cluster_radius);
+
+    let lowest = |cluster: &HashSet<Point>| {
+        cluster.iter().map(|p| (p.x, p.y)).fold((f64::INFINITY, f64::INFINITY), |lo, p| {
+            if p.0.total_cmp(&lo.0).then(p.1.total_cmp(&lo.1)).is_lt() { p } else { lo }
+        })
+    };
+    let mut clusters: Vec<HashSet<Point>> =
+        clusters.into_iter().map(|cluster| cluster.into_iter().map(|i| *points_vec[i]).collect()).collect();
+    clusters.sort_by(|c1, c2| {
+        let (lo1, lo2) = (lowest(c1), lowest(c2));
+        lo1.0.total_cmp(&lo2.0).then(lo1.1.total_cmp(&lo2.1))
+    });
+
+    clusters
+        .into_iter()
+        .map(|cluster| {
+            let lines = if cluster.len() < 2 {
+                HashSet::new()
+            } else {
+                get_lines_of_sym_or_empty(&cluster, None)
+            };
+            (cluster, lines)
+        })
+        .collect()
+}
+
+/// Groups the indices of the points by single linkage: two points are in the same group if a chain
+/// of points, each `linked` to the next, connects them. This takes `O(n²)` checks.
+fn single_linkage_clusters<T: Scalar>(
+    points: &[&Point<T>],
+    linked: impl Fn(&Point<T>, &Point<T>) -> bool,
+) -> Vec<Vec<usize>> {
+    // A union-find forest over the points, each entry pointing towards its cluster's root.
+    let mut parent: Vec<usize> = (0..points.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    for i in 0..points.len() {
+        for j in (i + 1)..points.len() {
+            if linked(points[i], points[j]) {
+                let (ri, rj) = (root(&mut parent, i), root(&mut parent, j));
+                parent[ri] = rj;
+            }
+        }
+    }
+
+    let mut clusters: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..points.len() {
+        clusters.entry(root(&mut parent, i)).or_default().push(i);
+    }
+    clusters.into_values().collect()
+}
+
+/// Replaces each group of points linked by distances below `min_feature_size`, as by
+/// `single_linkage_clusters`, with the group's mean, for `SymmetryOptions::min_feature_size`.
+fn merge_close_points<T: Scalar, S: BuildHasher + Default>(
+    points: &HashSet<Point<T>, S>,
+    min_feature_size: T,
+) -> HashSet<Point<T>, S> {
+    let points_vec: Vec<&Point<T>> = points.iter().collect();
+    single_linkage_clusters(&points_vec, |p, q| p.distance(q) < min_feature_size)
+        .into_iter()
+        .filter_map(|cluster| Point::mean(cluster.into_iter().map(|i| *points_vec[i])))
+        .collect()
+}
+
+/// Returns the Hausdorff distance between the points and their reflection across `line`: the
+/// largest distance from a point of either set to the nearest point of the other.
+///
+/// This is 0 for a line of symmetry and measures the worst asymmetry globally, without pairing
+/// points up, so unlike the defect of `symmetry_profile` it isn't biased by points that have no
+/// partner, e.g. extra points near an otherwise symmetric pair. The reflection is an isometry, so
+/// the distance from the reflected set to the points equals the distance the other way, and only
+/// one is computed, in `O(n²)`. Returns 0 for an empty set, and infinity if a reflection can't be
+/// computed (see `Line::try_reflected_point`).
+pub fn reflection_hausdorff(points: &HashSet<Point>, line: &Line) -> f64 {
+    points
+        .iter()
+        .map(|p| match line.try_reflected_point(p) {
+            Some(reflection) => points
+                .iter()
+                .map(|q| q.distance(&reflection))
+                .fold(f64::INFINITY, f64::min),
+            None => f64::INFINITY,
+        })
+        .fold(0., f64::max)
+}
+
+/// Returns the fraction of the points whose reflection across `line` is a point of the set, as
+/// checked for every point by `verify_axis`: 1 for a line of symmetry, and lower the more points
+/// miss their partners.
+///
+/// Unlike distances such as `reflection_hausdorff`, the score doesn't depend on the size of the
+/// shape, so scores of different shapes compare directly. Points on the line are their own
+/// partners. The points are indexed with `PointIndex`, so this takes `O(n log n)` time for sets
+/// that aren't crowded in x. An empty set scores 1, like a set every point of which is matched.
+pub fn self_overlap_score(points: &HashSet<Point>, line: &Line) -> f64 {
+    if points.is_empty() {
+        return 1.;
+    }
+
+    mirrored_count(&PointIndex::new(points), line) as f64 / points.len() as f64
+}
+
+/// Returns the lines about which at least a fraction `1 - max_outlier_frac` of the points have
+/// mirror partners, each with the number of the other points, the outliers, fewest outliers
+/// first.
+///
+/// This is a RANSAC-like search for noisy data, e.g. detections in an image with spurious points,
+/// whose axes no search requiring every point to have a partner would find. The candidates are
+/// the lines of `candidate_axes`, and the points with partners across each are counted as by
+/// `self_overlap_score`. Candidates equal within tolerance are reported once. Each candidate is
+/// checked against all the points, so this takes `O(n³ log n)` time, for small and moderate sets.
+pub fn detect_robust(points: &HashSet<Point>, max_outlier_frac: f64) -> Vec<(Line, usize)> {
+    let index = PointIndex::new(points);
+    let min_mirrored = (1. - max_outlier_frac.clamp(0., 1.)) * points.len() as f64;
+
+    let eps = config::default_epsilon();
+    let mut found: Vec<(Line, usize)> = Vec::new();
+    for line in candidate_axes(points) {
+        if found.iter().any(|(axis, _)| axis.approx_eq(&line, eps)) {
+            continue;
+        }
+        let mirrored = mirrored_count(&index, &line);
+        if mirrored as f64 >= min_mirrored - eps {
+            found.push((line, points.len() - mirrored));
+        }
+    }
+    found.sort_by_key(|(_, outliers)| *outliers);
+    found
+}
+
+/// Counts the indexed points whose reflection across `line` is a point of the set that reflects
+/// back onto them, as checked by `verify_axis`.
+fn mirrored_count(index: &PointIndex<'_, f64>, line: &Line) -> usize {
+    let is_mirrored = |p: &&&Point| {
+        let partner = line.try_reflected_point(p).and_then(|reflection| index.get(&reflection));
+        partner.and_then(|partner| line.try_reflected_point(partner)).as_ref() == Some(**p)
+    };
+    index.points().iter().filter(is_mirrored).count()
+}
+
+/// Returns the center of symmetry of a list of values, i.e. the `c` for which every value `v` has a
+/// partner `2c - v` within `tol`, or `None` if there is none.
+///
+/// This is the one-dimensional problem left once the direction of an axis is known: projecting
+/// the points onto the axis' normal gives values symmetric about the axis' offset. Values are
+/// counted with multiplicity, so each can be the partner of one value only; a value within `tol`
+/// of the center is its own partner. The center is the midpoint of the smallest and largest value.
+/// Returns `None` for an empty list or a non-finite value.
+pub fn is_symmetric_1d(values: &[f64], tol: f64) -> Option<f64> {
+    if values.iter().any(|v| !v.is_finite()) {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let center = 0.5 * (sorted.first()? + sorted.last()?);
+
+    // The reflection about the center reverses the order of the values.
+    let mut pairs = sorted.iter().zip(sorted.iter().rev());
+    pairs.all(|(v, partner)| (2. * center - v - partner).abs() <= tol).then_some(center)
+}
+
+/// Returns the lines that are near-symmetries of the points within `tol`, each with a confidence
+/// in `[0, 1]` of how symmetric the points are about it, most confident first.
+///
+/// A line is a near-symmetry if every point's reflection across it is within distance `tol` of a
+/// point, as found by a search with `PointEquality::Radial(tol)`; near-duplicate lines are merged
+/// within `tol`. The confidence of a line is `1 - defect / scale`, clamped to `[0, 1]`, where the
+/// defect is the mean distance from each reflection to the nearest point (see `symmetry_profile`)
+/// and the scale is the mean distance of the points from their centroid. An exact line of
+/// symmetry thus has a confidence of 1, up to rounding. The result is empty if the points coincide.
+pub fn detect_with_confidence(points: &HashSet<Point>, tol: f64) -> Vec<(Line, f64)> {
+    let Some(center) = centroid(&points.iter().collect::<Vec<_>>()) else {
+        return Vec::new();
+    };
+    let scale = points.iter().map(|p| p.distance(&center)).sum::<f64>() / points.len() as f64;
+    if scale <= config::default_epsilon() {
+        return Vec::new();
+    }
+
+    let mut scored: Vec<(Line, f64)> = get_lines_of_sym_with(points, &near_symmetry_options(tol))
+        .lines
+        .into_iter()
+        .map(|line| {
+            let confidence = (1. - axis_defect(points, &line) / scale).clamp(0., 1.);
+            (line, confidence)
+        })
+        .collect();
+    scored.sort_by(|(_, c1), (_, c2)| c2.total_cmp(c1));
+    scored
+}
+
+/// Returns the lines that are near-symmetries of the points at each of the given tolerances, as
+/// found by `detect_with_confidence`, from the tightest tolerance to the loosest.
+///
+/// Axes found at tight tolerances are robust to noise, while axes that only appear at loose ones
+/// are emergent, i.e. symmetries the points only approximate. Each tolerance is paired with the
+/// lines found at it, so the evolution of the axes can be followed.
+pub fn detect_multiscale(points: &HashSet<Point>, tolerances: &[f64]) -> Vec<(f64, HashSet<Line>)> {
+    let mut tolerances = tolerances.to_vec();
+    tolerances.sort_by(f64::total_cmp);
+
+    tolerances
+        .into_iter()
+        .map(|tol| (tol, get_lines_of_sym_with(points, &near_symmetry_options(tol)).lines))
+        .collect()
+}
+
+/// The options of a search for near-symmetries within `tol`: reflections are matched to points
+/// within distance `tol`, and lines merged within `tol`.
+fn near_symmetry_options(tol: f64) -> SymmetryOptions {
+    SymmetryOptions {
+        point_equality: PointEquality::Radial(tol),
+        cluster_tolerance: Some(tol),
+        ..SymmetryOptions::default()
+    }
+}
+
+/// Returns the similarity symmetries of the points: lines paired with a scale factor `s` such
+/// that reflecting across the line and then scaling by `s` about a point of the line maps the
+/// points onto points of the set, e.g. for self-similar data. The lines of symmetry are paired
+/// with a scale of 1, and the other lines follow by increasing scale.
+///
+/// No finite set is mapped onto itself by a scaling, which moves its farthest point from the
+/// center farther, so a set sampled from a self-similar shape is taken to be truncated there:
+/// for a scale above 1, a point may be mapped beyond the distance of every point from the center,
+/// as long as more than half of the points are mapped onto points. The inverse maps, with scales
+/// below 1, aren't reported separately. A logarithmic spiral, say, is similar to itself under
+/// rotation with scaling, not reflection with scaling, while a zigzag that grows by `s` per point
+/// is mapped onto itself by reflecting across its middle and scaling by `s`.
+///
+/// The candidate maps are those taking a pair of points onto another pair, so this takes
+/// `O(n⁵ log n)` time in the worst case, for small sets. Points are matched as by `==`.
+pub fn similarity_symmetries(points: &HashSet<Point>) -> Vec<(Line, f64)> {
+    let mut found: Vec<(Line, f64)> =
+        get_lines_of_sym_or_empty(points, None).into_iter().map(|line| (line, 1.)).collect();
+    let points_vec: Vec<&Point> = points.iter().collect();
+    let index = PointIndex::new(points);
+    let n = points_vec.len();
+    let eps = config::default_epsilon();
+
+    for (i, k) in (0..n).flat_map(|i| ((i + 1)..n).map(move |k| (i, k))) {
+        for (j, l) in (0..n).flat_map(|j| (0..n).filter(move |&l| l != j).map(move |l| (j, l))) {
+            let (p1, p2, q1, q2) = (points_vec[i], points_vec[k], points_vec[j], points_vec[l]);
+            let Some((line, scale)) = reflection_with_scaling(points, &index, (p1, p2), (q1, q2), eps) else {
+                continue;
+            };
+            let seen = |(axis, s): &(Line, f64)| axis.approx_eq(&line, eps) && util::floats_equal_toler(*s, scale);
+            if !found.iter().any(seen) {
+                found.push((line, scale));
+            }
+        }
+    }
+    found.sort_by(|(_, s1), (_, s2)| s1.total_cmp(s2));
+    found
+}
+
+/// Returns the line and scale above 1 of the reflection-with-scaling that takes `p1` to `q1` and
+/// `p2` to `q2`, if it maps the points as required by `similarity_symmetries`, with scales and
+/// distances compared within `tol`.
+fn reflection_with_scaling(
+    points: &HashSet<Point>,
+    index: &PointIndex<'_, f64>,
+    (p1, p2): (&Point, &Point),
+    (q1, q2): (&Point, &Point),
+    tol: f64,
+) -> Option<(Line, f64)> {
+    // As a map of complex numbers, the transform is z -> a * conj(z) + b, with |a| the scale and
+    // the line at half the argument of a.
+    let (ux, uy, vx, vy) = (p1.x - p2.x, p1.y - p2.y, q1.x - q2.x, q1.y - q2.y);
+    let u2 = ux * ux + uy * uy;
+    let (ax, ay) = ((vx * ux - vy * uy) / u2, (vx * uy + vy * ux) / u2);
+    let scale = Float::hypot(ax, ay);
+    if scale <= 1. + tol {
+        return None;
+    }
+    let (bx, by) = (q1.x - (ax * p1.x + ay * p1.y), q1.y - (ay * p1.x - ax * p1.y));
+    let map = |p: &Point| Point::new(ax * p.x + ay * p.y + bx, ay * p.x - ax * p.y + by);
+
+    // The center is the fixed point of the map.
+    let det = 1. - scale * scale;
+    let center = Point::new((bx * (1. + ax) + ay * by) / det, ((1. - ax) * by + ay * bx) / det);
+    let outer = points.iter().map(|p| p.distance(&center)).fold(0., f64::max);
+
+    let mut mapped = 0;
+    for p in points {
+        let image = map(p);
+        if index.get(&image).is_some() {
+            mapped += 1;
+        } else if image.distance(&center) <= outer + tol {
+            return None;
+        }
+    }
+    if 2 * mapped <= points.len() {
+        return None;
+    }
+
+    let (sin, cos) = Float::sin_cos(Float::atan2(ay, ax) / 2.);
+    Some((Line::new(-sin, cos, sin * center.x - cos * center.y), scale))
+}
+
+/// Checks, for each of the given orientations, whether the line through the centroid of the
+/// points at that orientation is a line of symmetry.
+///
+/// Every line of symmetry passes through the centroid, so this decides whether the set is
+/// symmetric about an axis at exactly the given angle, e.g. to test a hypothesis, without
+/// searching for all its axes.
+///
+/// # Arguments
+///
+/// * `points` - A set of 2D points.
+/// * `angles` - The orientations to test, in radians counterclockwise from the horizontal.
+///
+/// # Returns
+///
+/// Each angle paired with whether the axis at that angle is a line of symmetry. An empty set
+/// has no centroid, so every angle is paired with `false`.
+pub fn test_orientations(points: &HashSet<Point>, angles: &[f64]) -> Vec<(f64, bool)> {
+    let center = centroid(&points.iter().collect::<Vec<_>>());
+
+    angles
+        .iter()
+        .map(|&angle| {
+            let is_axis = center.is_some_and(|center| {
+                let (sin, cos) = Float::sin_cos(angle);
+                verify_axis(points, &Line::new(-sin, cos, sin * center.x - cos * center.y))
+            });
+            (angle, is_axis)
+        })
+        .collect()
+}
+
+/// Counts the orientations of the lines, as given by `Line::angle_rad`, in `bins` bins of equal
+/// width spanning `[0, π)`, e.g. for the distribution of the directions of symmetry of the
+/// combined axes of many shapes.
+///
+/// Bin `i` counts the angles in `[i π / bins, (i + 1) π / bins)`. An angle within tolerance of π
+/// is nearly horizontal, so it is counted in the first bin with the horizontal lines. Lines that
+/// aren't valid have no orientation and aren't counted. Returns no bins if `bins` is 0.
+pub fn axis_angle_histogram(lines: &HashSet<Line>, bins: usize) -> Vec<usize> {
+    let mut histogram = vec![0; bins];
+    if bins == 0 {
+        return histogram;
+    }
+
+    let pi = core::f64::consts::PI;
+    for line in lines.iter().filter(|line| line.is_valid()) {
+        let angle = line.angle_rad();
+        let bin = if angle >= pi - config::default_epsilon::<f64>() { 0 } else { (angle / pi * bins as f64) as usize };
+        histogram[bin.min(bins - 1)] += 1;
+    }
+    histogram
+}
+
+/// Returns the center and radius of a circle on which all points lie, if there is one.
+///
+/// The circle is fitted to the points by algebraic least squares, then every point's
+/// distance to the fitted center is checked against the fitted radius.
+///
+/// # Arguments
+///
+/// * `points` - A set of 2D points.
+/// * `tol` - The maximum allowed difference between a point's distance to the center and the radius.
+///
+/// # Returns
+///
+/// `Some((center, radius))` if all points lie on a common circle within `tol`, or `None`
+/// if there are fewer than three points, the points are collinear, or no such circle exists.
+pub fn approximate_circular_symmetry(points: &HashSet<Point>, tol: f64) -> Option<(Point, f64)> {
+    if points.len() < 3 {
+        return None;
+    }
+
+    // Work relative to the mean to keep the normal equations well-conditioned.
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.y).sum::<f64>() / n;
+
+    let (mut suu, mut svv, mut suv) = (0.0, 0.0, 0.0);
+    let (mut suuu, mut svvv, mut suvv, mut svuu) = (0.0, 0.0, 0.0, 0.0);
+    for p in points {
+        let u = p.x - mean_x;
+        let v = p.y - mean_y;
+        suu += u * u;
+        svv += v * v;
+        suv += u * v;
+        suuu += u * u * u;
+        svvv += v * v * v;
+        suvv += u * v * v;
+        svuu += v * u * u;
+    }
+
+    /*
+     * Solve for the center (uc, vc) in the shifted frame:
+     *   suu * uc + suv * vc = (suuu + suvv) / 2
+     *   suv * uc + svv * vc = (svvv + svuu) / 2
+     */
+    let det = suu * svv - suv * suv;
+    if det.abs() < config::default_epsilon() {
+        // The points are collinear.
+        return None;
+    }
+
+    let rhs_u = 0.5 * (suuu + suvv);
+    let rhs_v = 0.5 * (svvv + svuu);
+    let uc = (rhs_u * svv - suv * rhs_v) / det;
+    let vc = (suu * rhs_v - suv * rhs_u) / det;
+
+    let center_x = uc + mean_x;
+    let center_y = vc + mean_y;
+    if !center_x.is_finite() || !center_y.is_finite() {
+        return None;
+    }
+
+    let center = Point::new(center_x, center_y);
+    let radius = points.iter().map(|p| p.distance(&center)).sum::<f64>() / n;
+
+    if points.iter().all(|p| (p.distance(&center) - radius).abs() <= tol) {
+        Some((center, radius))
+    } else {
+        None
+    }
+}
+
+/// Returns the sorted multiset of pairwise distances between the points of a set.
+///
+/// Two congruent sets (related by any combination of translations, rotations and
+/// reflections) have the same fingerprint, up to floating-point imprecision.
+pub fn distance_fingerprint(points: &HashSet<Point>) -> Vec<f64> {
+    let points_vec: Vec<&Point> = points.iter().collect();
+    let mut distances = Vec::with_capacity(points_vec.len() * points_vec.len().saturating_sub(1) / 2);
+
+    for i in 0..points_vec.len() {
+        for j in (i + 1)..points_vec.len() {
+            distances.push(points_vec[i].distance(points_vec[j]));
+        }
+    }
+
+    distances.sort_by(f64::total_cmp);
+    distances
+}
+
+/// Returns a canonical form of a set of points, equal for sets that are congruent up to scaling,
+/// i.e. related by translations, rotations, reflections and uniform scaling, e.g. to compare or
+/// deduplicate shapes.
+///
+/// The points are translated to put their centroid at the origin and scaled so that the farthest
+/// point from it is at distance 1. Each farthest point is then rotated onto the positive x-axis,
+/// with the set mirrored across it or not, and the points of each such placement sorted. The
+/// form is the least of these placements in lexicographic order, compared within floating-point
+/// tolerance. The forms of congruent sets are equal by `==` up to floating-point imprecision. A set
+/// whose points all coincide has all its points at the origin.
+pub fn canonical_form(points: &HashSet<Point>) -> Vec<Point> {
+    let Some(center) = Point::mean(points.iter().copied()) else {
+        return Vec::new();
+    };
+    let centered: Vec<(f64, f64)> = points.iter().map(|p| (p.x - center.x, p.y - center.y)).collect();
+    let radius = centered.iter().map(|&(x, y)| Float::hypot(x, y)).fold(0., f64::max);
+    let eps = config::default_epsilon();
+    if radius <= eps {
+        return vec![Point::new(0., 0.); points.len()];
+    }
+
+    let mut best: Option<Vec<Point>> = None;
+    let farthest = centered.iter().filter(|&&(x, y)| Float::hypot(x, y) / radius >= 1. - eps);
+    for &(fx, fy) in farthest {
+        let (cos, sin) = (fx / radius, fy / radius);
+        for mirrored in [false, true] {
+            let mut placement: Vec<Point> = centered
+                .iter()
+                .map(|&(x, y)| {
+                    let (u, v) = ((x * cos + y * sin) / radius, (y * cos - x * sin) / radius);
+                    Point::new(u, if mirrored { -v } else { v })
+                })
+                .collect();
+            placement.sort_by(|p, q| p.partial_cmp(q).unwrap_or(core::cmp::Ordering::Equal));
+            let is_less = |b: &Vec<Point>| {
+                placement.iter().partial_cmp(b.iter()) == Some(core::cmp::Ordering::Less)
+            };
+            if best.as_ref().is_none_or(is_less) {
+                best = Some(placement);
+            }
+        }
+    }
+
+    best.unwrap_or_default()
+}
+
+/// The tolerance used when comparing distances computed from points matched within `tol`: four
+/// times `tol`.
+fn fingerprint_tolerance<T: Scalar>(tol: T) -> T {
+    let two = T::one() + T::one();
+    two * two * tol
+}
+
+/// Checks cheaply whether the set could have a line of symmetry, e.g. to filter large batches of
+/// sets before searching them.
+///
+/// Returns `false` only if a necessary condition fails: the points whose distance profiles match
+/// no other point's, and which must therefore lie on any axis, aren't collinear. A `true` result
+/// doesn't guarantee an axis. This takes `O(n² log n)` time, compared to the `O(n³)` worst case of
+/// `get_lines_of_sym`, which runs the same check first. Distances are matched at
+/// `config::default_epsilon`; see `could_be_symmetric_tol`.
+pub fn could_be_symmetric<T: Scalar, S: BuildHasher>(points: &HashSet<Point<T>, S>) -> bool {
+    could_be_symmetric_tol(points, config::default_epsilon())
+}
+
+/// Like `could_be_symmetric`, but with points taken to match within `tol`, in their units, e.g.
+/// the `SymmetryOptions::epsilon` of the search to be filtered for.
+pub fn could_be_symmetric_tol<T: Scalar, S: BuildHasher>(points: &HashSet<Point<T>, S>, tol: T) -> bool {
+    let points_vec: Vec<&Point<T>> = points.iter().collect();
+    mirror_partners_possible(&points_vec, tol)
+}
+
+/// Checks a necessary condition for the existence of a line of symmetry.
+///
+/// A reflection is an isometry, so a point and its reflection have the same sorted distances
+/// to all points of a symmetric set. A point whose distance profile matches no other point's
+/// must therefore be its own reflection, i.e. lie on the axis. Returns `false` if those points
+/// are not collinear, in which case the set has no line of symmetry. Points are taken to match
+/// within `tol`.
+fn mirror_partners_possible<T: Scalar>(points: &[&Point<T>], tol: T) -> bool {
+    let tolerance = fingerprint_tolerance(tol);
+
+    // Distances are finite, so comparisons between them never fail.
+    let profiles: Vec<(T, Vec<T>)> = points
+        .iter()
+        .map(|p| {
+            let mut profile: Vec<T> = points.iter().map(|q| p.distance(q)).collect();
+            profile.sort_by(|x, y| x.partial_cmp(y).unwrap());
+            (profile.iter().fold(T::zero(), |sum, &d| sum + d), profile)
+        })
+        .collect();
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|&i, &j| profiles[i].0.partial_cmp(&profiles[j].0).unwrap());
+
+    let profiles_match = |a: &[T], b: &[T]| a.iter().zip(b).all(|(&x, &y)| (x - y).abs() <= tolerance);
+    // Profiles that match elementwise have sums within this distance of each other.
+    let sum_tolerance = tolerance * T::from(points.len()).unwrap();
+
+    let mut unmatched: Vec<&Point<T>> = Vec::new();
+    for (k, &i) in order.iter().enumerate() {
+        let (sum_i, profile_i) = &profiles[i];
+        let nearby = order[..k]
+            .iter()
+            .rev()
+            .take_while(|&&j| *sum_i - profiles[j].0 <= sum_tolerance)
+            .chain(order[k + 1..].iter().take_while(|&&j| profiles[j].0 - *sum_i <= sum_tolerance));
+
+        if !nearby.into_iter().any(|&j| profiles_match(profile_i, &profiles[j].1)) {
+            unmatched.push(points[i]);
+        }
+    }
+
+    if unmatched.len() <= 2 {
+        return true;
+    }
+
+    let axis = get_through_line(unmatched[0], unmatched[1]);
+    unmatched[2..].iter().all(|p| axis.is_point_on_line_within(p, tol))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    #[cfg(feature = "std")]
+    use std::time::Instant;
+
+    /// Returns the lines in order of orientation, e.g. to compare the axes found by two searches
+    /// line by line.
+    #[cfg(feature = "std")]
+    fn sorted_by_angle(lines: &HashSet<Line>) -> Vec<Line> {
+        let mut lines: Vec<Line> = lines.iter().copied().collect();
+        lines.sort_by(|l1, l2| l1.angle_rad().total_cmp(&l2.angle_rad()));
+        lines
+    }
+
+    /// Builds `n` scattered integer points and their mirror images across the y-axis. They pass
+    /// the fingerprint precheck, but almost every candidate line is invalid, so searches with a
+    /// high degree of partial symmetry expected check each one against the whole set.
+    fn mirrored_scatter(n: usize) -> HashSet<Point> {
+        let mut points = HashSet::new();
+        for k in 0..n {
+            let (x, y) = ((k * 37 % 1009 + 1) as f64, (k * 101 % 997) as f64);
+            points.insert(Point::new(x, y));
+            points.insert(Point::new(-x, y));
+        }
+        points
+    }
+
+    /// Tests that setting the cancellation flag while the search is running makes it return early
+    /// with `cancelled` set, at the next candidate. The flag is set by the search's own 100th
+    /// reflection rather than after a delay, so that it is set mid-run on any machine.
+    #[test]
+    fn test_cancel_flag_mid_run() {
+        let points = mirrored_scatter(400);
+        let flag = Arc::new(AtomicBool::new(false));
+        let opts = SymmetryOptions {
+            cancel_flag: Some(Arc::clone(&flag)),
+            ..SymmetryOptions::default()
+        };
+
+        let mut reflections = 0;
+        let reflect = |line: &Line, p: &Point| {
+            reflections += 1;
+            if reflections == 100 {
+                flag.store(true, Ordering::Relaxed);
+            }
+            line.try_reflected_point(p)
+        };
+        let (stats, ws) = (&mut SymmetryStats::default(), &mut Workspace::new());
+        let matcher = None::<&ToleranceMatcher>;
+        let result = search_lines_of_sym(&points, &opts, stats, None, reflect, matcher, ws, &mut keep_searching);
+
+        assert!(result.cancelled);
+        // The candidate being checked when the flag was set is finished, and no other is started.
+        assert!(reflections < 100 + points.len());
+    }
+
+    /// Tests that the phases of a search of a few dozen points are timed, with the time of
+    /// generating candidates from pairs nonzero, and that a collinear set only times the through
+    /// line.
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_get_lines_of_sym_timed() {
+        let points = mirrored_scatter(20);
+        let (result, timing) = get_lines_of_sym_timed(&points, &SymmetryOptions::default());
+        assert_eq!(result.lines, get_lines_of_sym(&points, None).unwrap());
+        assert!(timing.pair_generation > Duration::ZERO);
+        assert!(timing.candidate_evaluation > Duration::ZERO);
+
+        let collinear: HashSet<Point> = (0..5).map(|i| Point::new(i as f64, 0.)).collect();
+        let (result, timing) = get_lines_of_sym_timed(&collinear, &SymmetryOptions::default());
+        assert_eq!(result.lines.len(), 2);
+        assert_eq!(timing.pair_generation, Duration::ZERO);
+        assert_eq!(timing.candidate_evaluation, Duration::ZERO);
+    }
+
+    /// Tests that the axes yielded by `axes_iter` are those of the batch search, for planar,
+    /// collinear and asymmetric sets, also with pruning and sparse candidates, and that a cancelled
+    /// search yields none.
+    #[test]
+    fn test_axes_iter() {
+        let opts = SymmetryOptions::default();
+        let sets: [HashSet<Point>; 4] = [
+            centered_grid(3, 3),
+            centered_grid(4, 2),
+            HashSet::from([Point::new(0., 0.), Point::new(1., 0.), Point::new(3., 0.), Point::new(4., 0.)]),
+            HashSet::from([Point::new(0., 0.), Point::new(2., 0.), Point::new(0., 1.)]),
+        ];
+        for points in &sets {
+            let axes: HashSet<Line> = axes_iter(points, &opts).collect();
+            assert_eq!(axes, get_lines_of_sym_with(points, &opts).lines);
+        }
+        assert_eq!(axes_iter(&centered_grid(3, 3), &opts).count(), 4);
+
+        let pruned = SymmetryOptions {
+            prune_by_centroid: true,
+            prune_by_bounding_box: true,
+            candidate_neighbours: Some(3),
+            ..SymmetryOptions::default()
+        };
+        for points in &sets {
+            let axes: HashSet<Line> = axes_iter(points, &pruned).collect();
+            assert_eq!(axes, get_lines_of_sym_with(points, &pruned).lines);
+        }
+
+        let cancelled = SymmetryOptions {
+            cancel_flag: Some(Arc::new(AtomicBool::new(true))),
+            ..SymmetryOptions::default()
+        };
+        assert_eq!(axes_iter(&centered_grid(3, 3), &cancelled).next(), None);
+    }
+
+    /// Tests the set operations on a grid and a copy of it perturbed by less than the tolerance,
+    /// with one point moved away, which exact hashing finds to share no points. That is shown with
+    /// a fixed hasher, as with a random one a hash collision can match a few points by chance.
+    #[test]
+    fn test_set_operations() {
+        let grid: HashSet<Point> = centered_grid(3, 3);
+        let shift = |p: &Point| Point::new(p.x + 1e-11, p.y - 1e-11);
+        let mut perturbed: HashSet<Point> = grid.iter().map(shift).collect();
+        #[cfg(feature = "std")]
+        {
+            type Fixed = core::hash::BuildHasherDefault<std::collections::hash_map::DefaultHasher>;
+            let fixed_grid: HashSet<Point, Fixed> = grid.iter().copied().collect();
+            let fixed_perturbed: HashSet<Point, Fixed> = perturbed.iter().copied().collect();
+            assert!(grid.iter().all(|p| perturbed.iter().any(|q| q == p)));
+            assert_eq!(fixed_grid.intersection(&fixed_perturbed).count(), 0);
+            assert_eq!(set_intersection(&fixed_grid, &fixed_perturbed).len(), grid.len());
+        }
+        perturbed.remove(&shift(&Point::new(2., 2.)));
+        perturbed.insert(Point::new(5., 5.));
+
+        assert_eq!(set_difference(&grid, &perturbed), HashSet::from([Point::new(2., 2.)]));
+        assert_eq!(set_difference(&perturbed, &grid), HashSet::from([Point::new(5., 5.)]));
+        let common = set_intersection(&grid, &perturbed);
+        assert_eq!(common.len(), 8);
+        assert!(!common.contains(&Point::new(2., 2.)));
+        assert!(set_intersection(&grid, &HashSet::new()).is_empty());
+    }
+
+    /// Tests that merging a grid with a copy of it off by rounding keeps one point per location,
+    /// at the midpoint of each matched pair.
+    #[test]
+    fn test_merge_sets() {
+        let grid: HashSet<Point> = centered_grid(3, 3);
+        let copy: HashSet<Point> = grid.iter().map(|p| Point::new(p.x + 2e-10, p.y)).collect();
+        let merged = merge_sets(&grid, &copy);
+        assert_eq!(merged.len(), grid.len());
+        assert!(merged.iter().all(|p| grid.iter().any(|q| (p.x - q.x - 1e-10).abs() < 1e-15 && p.y == q.y)));
+
+        let extra = HashSet::from([Point::new(7., 7.)]);
+        assert_eq!(merge_sets(&grid, &extra).len(), grid.len() + 1);
+        assert_eq!(merge_sets(&HashSet::new(), &grid), grid);
+    }
+
+    /// Tests that the candidates of a square with a displaced corner are rejected for the points
+    /// left without partners, and that the axis the displacement doesn't break is kept.
+    #[test]
+    fn test_detect_verbose() {
+        let displaced = Point::new(1.2, 1.1);
+        let points = HashSet::from([Point::new(0., 0.), Point::new(1., 0.), Point::new(0., 1.), displaced]);
+        let (axes, rejected) = detect_verbose(&points);
+        assert_eq!(axes, get_lines_of_sym(&points, None).unwrap());
+
+        let offending = |line: &Line| rejected.iter().find(|r| r.line == *line).map(|r| r.offending_point);
+        // The diagonal through the displaced corner still swaps the other two corners.
+        assert_eq!(offending(&Line::new(1., -1., 0.)), Some(displaced));
+        // Across the vertical axis, (0, 1) has lost its partner before the displaced corner.
+        assert_eq!(offending(&Line::new(1., 0., -0.5)), Some(Point::new(0., 1.)));
+        assert!(rejected.iter().all(|r| !verify_axis(&points, &r.line)));
+    }
+
+    /// Tests that the sparse candidates of a large regular polygon still find all its axes, from
+    /// far fewer candidates than there are pairs of points.
+    #[test]
+    fn test_candidate_neighbours() {
+        let n = 1000;
+        let polygon: HashSet<Point> = (0..n)
+            .map(|k| {
+                let (sin, cos) = Float::sin_cos(k as f64 * core::f64::consts::TAU / n as f64);
+                Point::new(3. + 10. * cos, -2. + 10. * sin)
+            })
+            .collect();
+        let opts = SymmetryOptions {
+            candidate_neighbours: Some(4),
+            ..SymmetryOptions::default()
+        };
+        let (result, stats) = get_lines_of_sym_with_stats(&polygon, &opts);
+        assert_eq!(result.lines.len(), n);
+        assert!(result.lines.iter().all(|axis| axis.is_point_on_line(&Point::new(3., -2.))));
+        assert!(stats.candidates_generated < 6 * n);
+
+        let square: HashSet<Point> = centered_grid(2, 2);
+        assert_eq!(get_lines_of_sym_with(&square, &opts).lines, get_lines_of_sym(&square, None).unwrap());
+    }
+
+    /// Tests that the sparse candidates of a large shape that isn't a regular polygon, samples of
+    /// the six-lobed curve `r = 10 + cos 6θ`, find its six axes from far fewer candidates than there
+    /// are pairs of points.
+    #[test]
+    fn test_candidate_neighbours_lobed() {
+        let n = 480;
+        let lobed: HashSet<Point> = (0..n)
+            .map(|k| {
+                let angle = k as f64 * core::f64::consts::TAU / n as f64;
+                let (sin, cos) = Float::sin_cos(angle);
+                let r = 10. + Float::cos(6. * angle);
+                Point::new(3. + r * cos, -2. + r * sin)
+            })
+            .collect();
+        let opts = SymmetryOptions {
+            candidate_neighbours: Some(4),
+            ..SymmetryOptions::default()
+        };
+        let (result, stats) = get_lines_of_sym_with_stats(&lobed, &opts);
+        assert_eq!(result.lines.len(), 6);
+        assert!(result.lines.iter().all(|axis| axis.is_point_on_line(&Point::new(3., -2.))));
+        assert!(stats.candidates_generated < 6 * n);
+    }
+
+    /// Tests that a regular polygon's axes are taken without a search of its pairs, as the lines the
+    /// search generates for them, and that a square's aren't once a corner is moved along its circle
+    /// by less than the scalar's tolerance but more than the search's epsilon.
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_regular_polygon_shortcut() {
+        let polygon = crate::geometry::regular_polygon(12, &Point::new(3., -2.), 10., 0.2);
+        let (result, stats) = get_lines_of_sym_with_stats(&polygon, &SymmetryOptions::default());
+        assert_eq!(result.lines.len(), 12);
+        assert_eq!(stats.candidates_generated, 12);
+        assert!(result.lines.iter().all(|axis| verify_axis(&polygon, axis)));
+        let seeded = SymmetryOptions {
+            seed: Some(42),
+            ..SymmetryOptions::default()
+        };
+        assert_eq!(get_lines_of_sym_with(&polygon, &seeded).lines, result.lines);
+
+        let square = HashSet::from([
+            Point::new(1., 4e-10),
+            Point::new(0., 1.),
+            Point::new(-1., 0.),
+            Point::new(0., -1.),
+        ]);
+        let opts = SymmetryOptions {
+            epsilon: Some(2e-10),
+            ..SymmetryOptions::default()
+        };
+        assert_eq!(get_lines_of_sym(&square, None).unwrap().len(), 4);
+        let (result, stats) = get_lines_of_sym_with_stats(&square, &opts);
+        assert!(result.lines.is_empty());
+        assert_eq!(stats.candidates_generated, 6);
+    }
+
+    /// Tests that a workspace finds the same axes as a fresh search on each set of a batch, reusing
+    /// the allocations of its buffers from one search to the next.
+    #[test]
+    fn test_workspace_reuses_buffers() {
+        let opts = SymmetryOptions::default();
+        let mut ws: Workspace = Workspace::new();
+        let square: HashSet<Point> = centered_grid(6, 6);
+        assert_eq!(*ws.detect_into(&square, &opts), get_lines_of_sym_with(&square, &opts).lines);
+
+        let lines = ws.lines.capacity();
+        let unit_points = ws.unit_points.capacity();
+        let matched = ws.matched.as_ptr();
+        let covered = ws.covered.words.as_ptr();
+        for (w, h) in [(2, 3), (5, 1), (3, 3), (6, 6)] {
+            let grid: HashSet<Point> = centered_grid(w, h);
+            assert_eq!(*ws.detect_into(&grid, &opts), get_lines_of_sym_with(&grid, &opts).lines);
+            assert_eq!(ws.lines.capacity(), lines);
+            assert_eq!(ws.unit_points.capacity(), unit_points);
+            assert_eq!(ws.matched.as_ptr(), matched);
+            assert_eq!(ws.covered.words.as_ptr(), covered);
+        }
+    }
+
+    /// Tests that tracking the pairs swapped across earlier candidates, which then don't generate
+    /// candidates of their own, cuts the candidates evaluated on a grid with many partial
+    /// symmetries, in sparse mode as in the default one, compared to checking the pairs again.
+    #[test]
+    fn test_covered_pairs_skip_candidates() {
+        let grid: HashSet<Point> = centered_grid(12, 7);
+        for candidate_neighbours in [None, Some(4)] {
+            let tracked = SymmetryOptions {
+                high_degree_expected: true,
+                candidate_neighbours,
+                ..SymmetryOptions::default()
+            };
+            let untracked = SymmetryOptions {
+                low_memory: true,
+                ..tracked.clone()
+            };
+            let (result, stats) = get_lines_of_sym_with_stats(&grid, &tracked);
+            let (untracked_result, untracked_stats) = get_lines_of_sym_with_stats(&grid, &untracked);
+            assert_eq!(result.lines, untracked_result.lines);
+            assert!(stats.candidates_generated < untracked_stats.candidates_generated);
+        }
+    }
+
+    /// Tests that quiet mode writes nothing to stderr for a single point, plain, labeled, weighted or
+    /// in 3D, where the default mode warns, by running this test again in a child process whose
+    /// stderr is captured.
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_quiet() {
+        let point = HashSet::from([Point::new(1., 2.)]);
+        if let Ok(quiet) = std::env::var("RUST_SYMM_QUIET_CHILD") {
+            let opts = SymmetryOptions {
+                quiet: quiet == "1",
+                ..SymmetryOptions::default()
+            };
+            assert!(get_lines_of_sym_with(&point, &opts).lines.is_empty());
+            let labeled = HashSet::from([LabeledPoint::new(Point::new(1., 2.), 'a')]);
+            assert!(get_lines_of_sym_labeled_with(&labeled, &opts).is_empty());
+            let weighted = HashSet::from([WeightedPoint::new(Point::new(1., 2.), 3.)]);
+            assert!(get_lines_of_sym_weighted_with(&weighted, &opts).is_empty());
+            let point3 = std::collections::HashSet::from([crate::space::Point3::new(1., 2., 3.)]);
+            assert!(crate::space::get_planes_of_sym_with(&point3, &opts).is_empty());
+            return;
+        }
+
+        let stderr_of = |quiet: &str| {
+            let output = std::process::Command::new(std::env::current_exe().unwrap())
+                .args(["--exact", "alg::tests::test_quiet", "--nocapture", "--test-threads=1"])
+                .env("RUST_SYMM_QUIET_CHILD", quiet)
+                .output()
+                .unwrap();
+            assert!(output.status.success());
+            String::from_utf8(output.stderr).unwrap()
+        };
+        assert_eq!(stderr_of("1"), "");
+        assert!(stderr_of("0").contains("at least 2 points needed"));
+    }
+
+    /// Tests the queries of the axes of a square, including finding an axis from a scaled and
+    /// negated equation.
+    #[test]
+    fn test_axis_set() {
+        let square: HashSet<Point> = centered_grid(2, 2);
+        let axes = AxisSet::from(get_lines_of_sym(&square, None).unwrap());
+        assert_eq!(axes.len(), 4);
+        assert!(!axes.is_empty());
+
+        let quarter = core::f64::consts::FRAC_PI_4;
+        let angles = axes.angles();
+        assert_eq!(angles.len(), 4);
+        for (k, &angle) in angles.iter().enumerate() {
+            assert!(util::floats_equal_toler(angle, k as f64 * quarter));
+        }
+
+        assert!(axes.contains_axis(&Line::new(-3., 3., 0.)));
+        assert!(axes.contains_axis(&Line::new(0., 1e-3, 0.)));
+        assert!(!axes.contains_axis(&Line::new(1., 2., 0.)));
+        assert_eq!(axes.iter().count(), 4);
+        assert_eq!(axes.into_inner().len(), 4);
+        assert!(AxisSet::<f64>::from(HashSet::new()).is_empty());
+    }
+
+    /// Tests the scores of a square's axes once a corner of the eight points along its outline is
+    /// moved off, which breaks the corner and its partner across the axes that swap them, but only
+    /// the corner itself on the diagonal through it.
+    #[test]
+    fn test_self_overlap_score() {
+        let mut outline = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(0.5, 0.),
+            Point::new(1., 0.),
+            Point::new(1., 0.5),
+            Point::new(1., 1.),
+            Point::new(0.5, 1.),
+            Point::new(0., 1.),
+            Point::new(0., 0.5),
+        ]);
+        let vertical = Line::new(1., 0., -0.5);
+        assert_eq!(self_overlap_score(&outline, &vertical), 1.);
+
+        outline.remove(&Point::new(0., 1.));
+        outline.insert(Point::new(0., 1.3));
+        assert_eq!(self_overlap_score(&outline, &vertical), 0.75);
+        assert_eq!(self_overlap_score(&outline, &Line::new(1., 1., -1.)), 0.875);
+        assert_eq!(self_overlap_score(&outline, &Line::new(1., -1., 0.)), 0.75);
+        assert_eq!(self_overlap_score(&HashSet::new(), &vertical), 1.);
+    }
+
+    /// Tests that the four axes of a square, at 0°, 45°, 90° and 135°, fall into distinct bins.
+    #[test]
+    fn test_axis_angle_histogram() {
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+        ]);
+        let axes = get_lines_of_sym(&square, None).unwrap();
+        assert_eq!(axis_angle_histogram(&axes, 6), vec![1, 1, 0, 1, 1, 0]);
+        assert_eq!(axis_angle_histogram(&axes, 1), vec![4]);
+        assert!(axis_angle_histogram(&axes, 0).is_empty());
+
+        // A line at an angle just short of π is counted with the horizontal lines.
+        let nearly_horizontal = HashSet::from([Line::new(1e-12, 1., 0.), Line::new(0., 1., -1.)]);
+        assert_eq!(axis_angle_histogram(&nearly_horizontal, 6), vec![2, 0, 0, 0, 0, 0]);
+    }
+
+    /// Tests that a configured detector finds the same axes as the options it sets.
+    #[test]
+    fn test_detector() {
+        let segment = HashSet::from([Point::new(0., 0.), Point::new(1., 0.), Point::new(2., 0.)]);
+        let detector = Detector::new().tolerance(1e-6).high_degree(false).include_through_line(false);
+        let opts = SymmetryOptions {
+            epsilon: Some(1e-6),
+            high_degree_expected: false,
+            include_through_line: false,
+            ..SymmetryOptions::default()
+        };
+        let axes = detector.detect(&segment);
+        assert_eq!(axes.into_inner(), get_lines_of_sym_with(&segment, &opts).lines);
+        assert_eq!(detector.options().epsilon, Some(1e-6));
+
+        let axes = Detector::new().detect(&segment);
+        assert_eq!(axes.len(), 2);
+        assert!(axes.contains_axis(&Line::new(0., 1., 0.)));
+        assert!(axes.contains_axis(&Line::new(1., 0., -1.)));
+    }
+
+    /// Tests that the axes of a square are found despite a stray point, each with the stray point
+    /// as its one outlier.
+    #[test]
+    fn test_detect_robust() {
+        let points = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+            Point::new(3., 0.2),
+        ]);
+        let axes = detect_robust(&points, 0.2);
+        assert_eq!(axes.len(), 4);
+        assert!(axes.iter().all(|(_, outliers)| *outliers == 1));
+        let expected = [
+            Line::new(1., 0., -0.5),
+            Line::new(0., 1., -0.5),
+            Line::new(1., -1., 0.),
+            Line::new(1., 1., -1.),
+        ];
+        assert!(expected.iter().all(|axis| axes.iter().any(|(line, _)| line == axis)));
+
+        // Without outliers allowed, the stray point breaks every axis.
+        assert!(detect_robust(&points, 0.).is_empty());
+    }
+
+    /// Tests that requiring a point on each axis drops the axis of a rectangle with two points
+    /// added above and below it that runs between its points, but not the axes of a pentagon, each
+    /// of which passes through a vertex.
+    #[test]
+    fn test_require_axis_point() {
+        let points = HashSet::from([
+            Point::new(-2., -1.),
+            Point::new(2., -1.),
+            Point::new(2., 1.),
+            Point::new(-2., 1.),
+            Point::new(0., 3.),
+            Point::new(0., -3.),
+        ]);
+        let opts = SymmetryOptions {
+            require_axis_point: true,
+            ..SymmetryOptions::default()
+        };
+        assert_eq!(get_lines_of_sym_with(&points, &SymmetryOptions::default()).lines.len(), 2);
+        let (result, stats) = get_lines_of_sym_with_stats(&points, &opts);
+        assert_eq!(result.lines, HashSet::from([Line::new(1., 0., 0.)]));
+        assert_eq!(stats.axes_found, 1);
+
+        let pentagon: HashSet<Point> = (0..5)
+            .map(|k| {
+                let (sin, cos) = Float::sin_cos(k as f64 * core::f64::consts::TAU / 5.);
+                Point::new(cos, sin)
+            })
+            .collect();
+        assert_eq!(get_lines_of_sym_with(&pentagon, &opts).lines.len(), 5);
+    }
+
+    /// Tests that a zigzag whose points grow by 1.5 from one to the next, alternating between two
+    /// rays from the origin, has no line of symmetry but is mapped onto itself by reflecting
+    /// across the x-axis and scaling by 1.5.
+    #[test]
+    fn test_similarity_symmetries() {
+        let zigzag: HashSet<Point> = (0..6)
+            .map(|k| {
+                let r = 1.5f64.powi(k);
+                Point::new(r, if k % 2 == 0 { 0.5 * r } else { -0.5 * r })
+            })
+            .collect();
+        assert!(get_lines_of_sym_or_empty(&zigzag, None).is_empty());
+
+        // Only the outermost point is mapped beyond the zigzag.
+        let symmetries = similarity_symmetries(&zigzag);
+        assert_eq!(symmetries.len(), 1);
+        let (line, scale) = &symmetries[0];
+        assert_eq!(*line, Line::new(0., 1., 0.));
+        assert!((scale - 1.5).abs() < 1e-9, "{scale}");
+
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+        ]);
+        let symmetries = similarity_symmetries(&square);
+        assert_eq!(symmetries.len(), 4);
+        assert!(symmetries.iter().all(|(_, scale)| *scale == 1.));
+    }
+
+    /// Tests that masking out the point that breaks a square's symmetry reveals its axes.
+    #[test]
+    fn test_get_lines_of_sym_masked() {
+        let points = [
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+            Point::new(0.3, 0.8),
+        ];
+        let opts = SymmetryOptions::default();
+        assert!(get_lines_of_sym_masked(&points, &[true; 5], &opts).is_empty());
+
+        let lines = get_lines_of_sym_masked(&points, &[true, true, true, true, false], &opts);
+        assert_eq!(lines.len(), 4);
+        assert!(lines.contains(&Line::new(1., -1., 0.)));
+
+        // Without a corner too, the rest is an isosceles right triangle.
+        let lines = get_lines_of_sym_masked(&points, &[true, false, true, true, false], &opts);
+        assert_eq!(lines, HashSet::from([Line::new(1., 1., -1.)]));
+    }
+
+    /// Tests that a deadline in the past stops the search before any candidate is checked,
+    /// and that an uncancelled run reports `cancelled == false`.
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_deadline() {
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+        ]);
+
+        let expired = SymmetryOptions {
+            deadline: Some(Instant::now()),
+            ..SymmetryOptions::default()
+        };
+        let result = get_lines_of_sym_with(&square, &expired);
+        assert!(result.cancelled);
+        assert!(result.lines.is_empty());
+
+        let result = get_lines_of_sym_with(&square, &SymmetryOptions::default());
+        assert!(!result.cancelled);
+        assert_eq!(result.lines.len(), 4);
+    }
+
+    /// Tests that 12 points equally spaced on a circle are detected as lying on that circle,
+    /// and that displacing one of them breaks the detection.
+    #[test]
+    fn test_approximate_circular_symmetry() {
+        let mut points: HashSet<Point> = (0..12)
+            .map(|i| {
+                let angle = i as f64 * std::f64::consts::PI / 6.0;
+                Point::new(3.0 + 2.0 * angle.cos(), -1.0 + 2.0 * angle.sin())
+            })
+            .collect();
+
+        let (center, radius) = approximate_circular_symmetry(&points, 1e-6).unwrap();
+        assert_eq!(center, Point::new(3.0, -1.0));
+        assert!((radius - 2.0).abs() < 1e-9);
+
+        points.insert(Point::new(3.0, -1.0));
+        assert!(approximate_circular_symmetry(&points, 1e-6).is_none());
+    }
+
+    /// Tests that detection works on `f32` coordinates.
+    #[test]
+    fn test_f32_square() {
+        let square: HashSet<Point<f32>> = HashSet::from([
+            Point::new(0.0f32, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+        ]);
+
+        let lines = get_lines_of_sym(&square, None).unwrap();
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().all(|l| l.is_point_on_line(&Point::new(0.5f32, 0.5))));
+    }
+
+    /// Tests the core path on caller-provided `hashbrown` sets, with the crate built without `std`.
+    /// The test harness itself links `std`, so CI also builds the crate for a target without it.
+    #[test]
+    #[cfg(not(feature = "std"))]
+    fn test_no_std_square() {
+        let square: hashbrown::HashSet<Point> = [(0., 0.), (1., 0.), (1., 1.), (0., 1.)]
+            .into_iter()
+            .map(|(x, y)| Point::new(x, y))
+            .collect();
+
+        let lines = get_lines_of_sym(&square, None).unwrap();
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().all(|l| verify_axis(&square, l)));
+    }
+
+    /// Builds a `w` by `h` grid of integer points centered on the origin, so that its
+    /// reflections are exact. A non-square grid has the two coordinate axes as its axes.
+    fn centered_grid<S: BuildHasher + Default>(w: usize, h: usize) -> HashSet<Point, S> {
+        let mut points = HashSet::with_hasher(S::default());
+        for i in 0..w {
+            for j in 0..h {
+                points.insert(Point::new((2 * i) as f64 - (w - 1) as f64, (2 * j) as f64 - (h - 1) as f64));
+            }
+        }
+        points
+    }
+
+    /// Tests that a set built with a custom hasher yields the same axes as the default one.
+    #[test]
+    fn test_custom_hasher() {
+        let default: HashSet<Point> = centered_grid(4, 3);
+        let fast: HashSet<Point, foldhash::fast::RandomState> = centered_grid(4, 3);
+
+        let default_lines = get_lines_of_sym(&default, None).unwrap();
+        let fast_lines = get_lines_of_sym(&fast, None).unwrap();
+        assert_eq!(default_lines.len(), 2);
+        assert_eq!(fast_lines.len(), 2);
+        assert!(fast_lines.iter().all(|l| verify_axis(&default, l)));
+    }
+
+    /// Tests that across the diagonal through a displaced corner of a square, only that corner
+    /// lacks a reflection.
+    #[test]
+    fn test_explain_no_axis() {
+        let near_square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.1),
+        ]);
+        let anti_diagonal = Line::new(1., 1., -1.);
+        assert_eq!(explain_no_axis(&near_square, &anti_diagonal), [Point::new(0., 1.1)]);
+        assert!(!verify_axis(&near_square, &anti_diagonal));
+
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+        ]);
+        assert!(explain_no_axis(&square, &anti_diagonal).is_empty());
+    }
+
+    /// Tests the partitions of a square with a displaced corner across the diagonal through two of
+    /// its other corners, and across its vertical axis.
+    #[test]
+    fn test_partition_by_reflection() {
+        let broken_square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.1),
+        ]);
+        let diagonal = Line::new(1., -1., 0.);
+        let (pairs, on_axis, unmatched) = partition_by_reflection(&broken_square, &diagonal);
+        assert!(pairs.is_empty());
+        assert_eq!(on_axis, [Point::new(0., 0.), Point::new(1., 1.)]);
+        assert_eq!(unmatched, [Point::new(0., 1.1), Point::new(1., 0.)]);
+
+        let vertical = Line::new(1., 0., -0.5);
+        let (pairs, on_axis, unmatched) = partition_by_reflection(&broken_square, &vertical);
+        assert_eq!(pairs, [(Point::new(0., 0.), Point::new(1., 0.))]);
+        assert!(on_axis.is_empty());
+        assert_eq!(unmatched, [Point::new(0., 1.1), Point::new(1., 1.)]);
+    }
+
+    /// Tests that adjacent corners of a rectangle generate an axis, and opposite corners, which
+    /// aren't mirror partners across any axis, don't.
+    #[test]
+    fn test_axis_from_pair() {
+        let rectangle = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(2., 0.),
+            Point::new(2., 1.),
+            Point::new(0., 1.),
+        ]);
+        let axis = axis_from_pair(&rectangle, &Point::new(0., 0.), &Point::new(2., 0.));
+        assert_eq!(axis, Some(Line::new(1., 0., -1.)));
+        assert_eq!(axis_from_pair(&rectangle, &Point::new(0., 0.), &Point::new(2., 1.)), None);
+        assert_eq!(axis_from_pair(&rectangle, &Point::new(0., 0.), &Point::new(0., 0.)), None);
+    }
+
+    /// Tests a kite symmetric about the x-axis against markers on the x-axis and off it.
+    #[test]
+    fn test_is_symmetric_about_markers() {
+        let kite = HashSet::from([
+            Point::new(-1., 0.),
+            Point::new(0., 1.),
+            Point::new(3., 0.),
+            Point::new(0., -1.),
+        ]);
+        assert!(is_symmetric_about_markers(&kite, &Point::new(-5., 0.), &Point::new(10., 0.)));
+        assert!(is_symmetric_about_markers(&kite, &Point::new(3., 0.), &Point::new(-1., 0.)));
+        assert!(!is_symmetric_about_markers(&kite, &Point::new(0., -5.), &Point::new(0., 5.)));
+        assert!(!is_symmetric_about_markers(&kite, &Point::new(1., 0.), &Point::new(1., 0.)));
+    }
+
+    /// Tests recovering the mirror line between a cluster and its reflection, both when the
+    /// reflection is moved away and when it shares the cluster's centroid.
+    #[test]
+    fn test_mirror_plane_between() {
+        let cluster = HashSet::from([
+            Point::new(1., 0.),
+            Point::new(2., 0.5),
+            Point::new(1.5, 2.),
+            Point::new(3., 1.),
+        ]);
+        let axis = Line::new(1., -2., 1.);
+        let reflection: HashSet<Point> = cluster.iter().map(|p| axis.get_reflected_point(p)).collect();
+        assert_eq!(mirror_plane_between(&cluster, &reflection), Some(Line::new(1., -2., 1.)));
+        assert_eq!(mirror_plane_between(&reflection, &cluster), Some(Line::new(1., -2., 1.)));
+
+        // Across a line through the cluster's centroid, the reflection shares it.
+        let center = Point::mean(cluster.iter().copied()).unwrap();
+        let through = get_through_line(&center, &Point::new(center.x + 1., center.y + 3.));
+        let turned: HashSet<Point> = cluster.iter().map(|p| through.get_reflected_point(p)).collect();
+        assert_eq!(mirror_plane_between(&cluster, &turned), Some(through));
+
+        assert_eq!(mirror_plane_between(&cluster, &cluster), None);
+        let mut shifted = reflection.clone();
+        shifted.insert(Point::new(10., 10.));
+        assert_eq!(mirror_plane_between(&cluster, &shifted), None);
+    }
+
+    /// Tests that only the center of a pentagon is on all its axes, and that a single axis fixes
+    /// the points on it.
+    #[test]
+    fn test_fixed_points() {
+        let mut pentagon: HashSet<Point> = (0..5)
+            .map(|k| {
+                let (sin, cos) = Float::sin_cos(k as f64 * core::f64::consts::TAU / 5.);
+                Point::new(1. + cos, 2. + sin)
+            })
+            .collect();
+        assert!(fixed_points(&pentagon).is_empty());
+        pentagon.insert(Point::new(1., 2.));
+        assert_eq!(fixed_points(&pentagon), HashSet::from([Point::new(1., 2.)]));
+
+        let kite = HashSet::from([
+            Point::new(-1., 0.),
+            Point::new(0., 1.),
+            Point::new(3., 0.),
+            Point::new(0., -1.),
+        ]);
+        assert_eq!(fixed_points(&kite), HashSet::from([Point::new(-1., 0.), Point::new(3., 0.)]));
+        let triangle = HashSet::from([Point::new(0., 0.), Point::new(2., 0.), Point::new(0., 1.)]);
+        assert!(fixed_points(&triangle).is_empty());
+    }
+
+    /// Tests that of a square's axes and a line that isn't one, only the axes are confirmed.
+    #[test]
+    fn test_validate_axes() {
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+        ]);
+        let candidates = [
+            Line::new(1., 0., -0.5),
+            Line::new(1., 2., -1.5),
+            Line::new(0., 1., -0.5),
+            Line::new(1., -1., 0.),
+            Line::new(1., 1., -1.),
+        ];
+        let axes = validate_axes(&square, &candidates);
+        let expected = [&candidates[0], &candidates[2], &candidates[3], &candidates[4]];
+        assert!(axes.iter().eq(expected));
+        assert!(validate_axes(&square, &[]).is_empty());
+    }
+
+    /// Tests that a square yields only the pair of corners off its diagonal across the diagonal.
+    #[test]
+    fn test_symmetric_pairs() {
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+        ]);
+        let diagonal = Line::new(1., -1., 0.);
+        let pairs: Vec<_> = symmetric_pairs(&square, &diagonal).collect();
+        assert_eq!(pairs, [(&Point::new(0., 1.), &Point::new(1., 0.))]);
+
+        let near_square = HashSet::from([Point::new(0., 0.), Point::new(1., 0.), Point::new(0., 1.1)]);
+        assert_eq!(symmetric_pairs(&near_square, &diagonal).count(), 0);
+    }
+
+    /// Tests that an equilateral triangle's axes through its centroid are found at the angles of
+    /// its three axes only, with the results in the order of the angles given, and that an empty
+    /// set has no axis at any angle.
+    #[test]
+    fn test_orientations_of_triangle() {
+        use core::f64::consts::PI;
+
+        let triangle = HashSet::from([Point::new(0., 0.), Point::new(2., 0.), Point::new(1., 3f64.sqrt())]);
+        let angles = [PI / 6., PI / 2., 5. * PI / 6., 0., PI / 4.];
+        let results = test_orientations(&triangle, &angles);
+
+        let tested: Vec<f64> = results.iter().map(|&(angle, _)| angle).collect();
+        let axes: Vec<bool> = results.iter().map(|&(_, is_axis)| is_axis).collect();
+        assert_eq!(tested, angles);
+        assert_eq!(axes, [true, true, true, false, false]);
+
+        assert_eq!(test_orientations(&HashSet::new(), &[0.]), [(0., false)]);
+    }
+
+    /// Tests that an input is classified as empty, as a single point, including points that
+    /// coincide within tolerance, as collinear, including any two points, or as planar.
+    #[test]
+    fn test_input_shape_kind() {
+        assert_eq!(input_shape_kind(&HashSet::<Point>::new()), InputKind::Empty);
+        assert_eq!(input_shape_kind(&HashSet::from([Point::new(1., 2.)])), InputKind::Single);
+        let coincident = HashSet::from([Point::new(1., 2.), Point::new(1. + config::EPSILON / 2., 2.)]);
+        assert_eq!(input_shape_kind(&coincident), InputKind::Single);
+
+        let segment = HashSet::from([Point::new(0., 0.), Point::new(1., 1.), Point::new(3., 3.)]);
+        assert_eq!(input_shape_kind(&segment), InputKind::Collinear);
+        let pair = HashSet::from([Point::new(0., 0.), Point::new(1., 5.)]);
+        assert_eq!(input_shape_kind(&pair), InputKind::Collinear);
+
+        let triangle = HashSet::from([Point::new(0., 0.), Point::new(1., 0.), Point::new(0., 1.)]);
+        assert_eq!(input_shape_kind(&triangle), InputKind::Planar);
+    }
+
+    /// Tests the errors for inputs whose lines of symmetry can't be computed.
+    #[test]
+    fn test_too_few_points() {
+        let single = HashSet::from([Point::new(1., 2.)]);
+        assert_eq!(get_lines_of_sym(&single, None), Err(SymmError::TooFewPoints));
+        assert!(get_lines_of_sym_or_empty(&single, None).is_empty());
+        assert_eq!(get_lines_of_sym(&HashSet::<Point>::new(), None), Err(SymmError::TooFewPoints));
+
+        // Exactly the tolerance apart: not `==`, so both are kept, but within tolerance in distance.
+        let coincident = HashSet::from([Point::new(1., 0.), Point::new(1., config::EPSILON)]);
+        assert_eq!(coincident.len(), 2);
+        assert_eq!(get_lines_of_sym(&coincident, None), Err(SymmError::DegenerateInput));
+
+        // A set without axes is not an error.
+        let scalene = HashSet::from([Point::new(0., 0.), Point::new(4., 0.), Point::new(1., 2.)]);
+        assert_eq!(get_lines_of_sym(&scalene, None).unwrap().len(), 0);
+    }
+
+    /// Tests that the search finds exactly the candidate axes that pass `verify_axis`, on the
+    /// example inputs from `main.rs` and two symmetric sets.
+    #[test]
+    fn test_matches_brute_force() {
+        let cases: [HashSet<Point>; 6] = [
+            HashSet::from([Point::new(1., 0.), Point::new(0., 1.), Point::new(2., 0.), Point::new(0., 2.)]),
+            HashSet::from([Point::new(1., 0.), Point::new(0., 1.), Point::new(2., 1.), Point::new(1., 2.)]),
+            HashSet::from([Point::new(-2., -1.), Point::new(-1., -0.5), Point::new(0., 0.), Point::new(3., 1.5)]),
+            HashSet::from([Point::new(0., 0.)]),
+            HashSet::from([Point::new(0., 0.), Point::new(1., 0.), Point::new(1., 1.), Point::new(0., 1.)]),
+            centered_grid(5, 4),
+        ];
+
+        for case in &cases {
+            let lines = get_lines_of_sym_or_empty(case, None);
+            let expected: Vec<Line> = candidate_axes(case).filter(|l| verify_axis(case, l)).collect();
+
+            assert!(lines.iter().all(|l| expected.iter().any(|e| lines_within(l, e, 1e-9, 1e-9))));
+            assert!(expected.iter().all(|e| lines.iter().any(|l| lines_within(l, e, 1e-9, 1e-9))));
+        }
+    }
+
+    /// Tests that a square has four axes through its center and none through an off-center point.
+    #[test]
+    fn test_axes_through_center() {
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(2., 0.),
+            Point::new(2., 2.),
+            Point::new(0., 2.),
+        ]);
+
+        let axes = axes_through_center(&square, &Point::new(1., 1.));
+        assert_eq!(axes.len(), 4);
+        assert!(axes.iter().all(|l| verify_axis(&square, l)));
+
+        assert!(axes_through_center(&square, &Point::new(0.5, 0.25)).is_empty());
+        assert!(axes_through_center(&HashSet::from([Point::new(1., 1.)]), &Point::new(1., 1.)).is_empty());
+    }
+
+    /// Checks that the low-memory mode finds the same axes as the default mode on `points`.
+    fn assert_low_memory_matches_default(points: &HashSet<Point>) {
+        let opts = SymmetryOptions {
+            high_degree_expected: false,
+            ..SymmetryOptions::default()
+        };
+        let low_memory = SymmetryOptions {
+            low_memory: true,
+            ..opts.clone()
+        };
+
+        let eager = get_lines_of_sym_with(points, &opts).lines;
+        let lazy = get_lines_of_sym_with(points, &low_memory).lines;
+        assert_eq!(lazy.len(), eager.len());
+        assert!(lazy.iter().all(|l| eager.iter().any(|e| lines_within(l, e, 1e-9, 1e-9))));
+    }
+
+    /// Tests that the low-memory mode finds the same axes as the default mode on 300 points and on
+    /// a grid with several axes.
+    #[test]
+    fn test_low_memory_matches_default() {
+        let scatter = mirrored_scatter(150);
+        assert_eq!(scatter.len(), 300);
+        let square: HashSet<Point> = centered_grid(6, 6);
+
+        assert_low_memory_matches_default(&scatter);
+        assert_low_memory_matches_default(&square);
+        assert_eq!(get_lines_of_sym(&square, None).unwrap().len(), 4);
+    }
+
+    /// Tests that the low-memory mode finds the same axes as the default mode on 2000 points. This
+    /// takes about half a minute in a debug build, so it is ignored by default.
+    ///
+    /// Run with `cargo test --release -- --ignored test_low_memory_matches_default_2k`.
+    #[test]
+    #[ignore]
+    fn test_low_memory_matches_default_2k() {
+        let scatter = mirrored_scatter(1000);
+        assert_eq!(scatter.len(), 2000);
+        assert_low_memory_matches_default(&scatter);
+    }
+
+    /// Tests that a sampled parabola is symmetric about its vertex, and that a sampled line isn't.
+    #[test]
+    fn test_vertical_axis_of_symmetry() {
+        let parabola: HashSet<Point> = (-8..=8)
+            .map(|i| i as f64 / 4.)
+            .map(|x| Point::new(x, x * x))
+            .collect();
+        let k = vertical_axis_of_symmetry(&parabola).unwrap();
+        assert!(k.abs() < 1e-9);
+
+        let shifted: HashSet<Point> = parabola.iter().map(|p| Point::new(p.x + 3., p.y)).collect();
+        assert!((vertical_axis_of_symmetry(&shifted).unwrap() - 3.).abs() < 1e-9);
+
+        let line: HashSet<Point> = (0..10).map(|i| Point::new(i as f64, 2. * i as f64)).collect();
+        assert_eq!(vertical_axis_of_symmetry(&line), None);
+        assert_eq!(vertical_axis_of_symmetry::<f64, _>(&HashSet::new()), None);
+    }
+
+    /// Tests that a search whose candidate lines overflow the reflection formula returns instead of
+    /// panicking: a square that far out has no axes found in its own coordinates, which the cached
+    /// search uses, and its four once normalized.
+    #[test]
+    fn test_overflowing_reflections() {
+        let huge = HashSet::from([
+            Point::new(1e200, 1e200),
+            Point::new(-1e200, 1e200),
+            Point::new(-1e200, -1e200),
+            Point::new(1e200, -1e200),
+        ]);
+
+        let unnormalized = get_lines_of_sym_cached(&huge, &SymmetryOptions::default(), &mut ReflectionCache::new());
+        assert!(unnormalized.lines.is_empty());
+        assert!(!unnormalized.cancelled);
+
+        let lines = get_lines_of_sym(&huge, None).unwrap();
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().all(|l| verify_axis(&huge, l)));
+        assert!(!verify_axis(&huge, &Line::new(f64::MAX, f64::MAX, f64::MAX)));
+    }
+
+    /// Tests that a square's symmetry profile has minima of 0 at its four axis angles only.
+    #[test]
+    fn test_symmetry_profile() {
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+        ]);
+
+        let profile = symmetry_profile(&square, 8);
+        assert_eq!(profile.len(), 8);
+        for (k, (angle, defect)) in profile.iter().enumerate() {
+            assert!((angle - k as f64 * std::f64::consts::FRAC_PI_8).abs() < 1e-12);
+            if k % 2 == 0 {
+                assert!(defect.abs() < 1e-9, "axis at step {} has defect {}", k, defect);
+            } else {
+                assert!(*defect > 0.1, "non-axis at step {} has defect {}", k, defect);
+            }
+        }
+
+        assert!(symmetry_profile(&square, 0).is_empty());
+        assert!(symmetry_profile(&HashSet::new(), 8).is_empty());
+    }
+
+    /// Tests that evenly spaced collinear points have both the line through them and the
+    /// perpendicular axis, and that unevenly spaced ones have only the former.
+    #[test]
+    fn test_collinear_axes() {
+        let even: HashSet<Point> = (-2..=2).map(|x| Point::new(x as f64, 0.)).collect();
+        let lines = get_lines_of_sym(&even, None).unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().any(|l| l.approx_eq(&Line::new(0., 1., 0.), 1e-9)));
+        assert!(lines.iter().any(|l| l.approx_eq(&Line::new(1., 0., 0.), 1e-9)));
+
+        let pair = HashSet::from([Point::new(0., 0.), Point::new(2., 2.)]);
+        let lines = get_lines_of_sym(&pair, None).unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().any(|l| l.approx_eq(&Line::new(1., -1., 0.), 1e-9)));
+        assert!(lines.iter().any(|l| l.approx_eq(&Line::new(1., 1., -2.), 1e-9)));
+
+        let uneven = HashSet::from([Point::new(0., 0.), Point::new(1., 1.), Point::new(3., 3.)]);
+        let lines = get_lines_of_sym(&uneven, None).unwrap();
+        assert_eq!(lines.len(), 1);
+        assert!(lines.iter().next().unwrap().approx_eq(&Line::new(1., -1., 0.), 1e-9));
+
+        assert!(are_collinear(&even.iter().collect::<Vec<_>>()));
+        assert!(!are_collinear(&[&Point::new(0., 0.), &Point::new(1., 0.), &Point::new(0., 1.)]));
+        assert!(!are_collinear(&[&Point::new(0., 0.)]));
+    }
+
+    /// Tests that centroid pruning on a 3x3 grid of points, a filled square, rejects candidates
+    /// without changing the axes found.
+    #[test]
+    fn test_stats_with_centroid_pruning() {
+        let square: HashSet<Point> = centered_grid(3, 3);
+        let (unpruned, unpruned_stats) = get_lines_of_sym_with_stats(&square, &SymmetryOptions::default());
+        assert_eq!(unpruned_stats.candidates_pruned, 0);
+        assert_eq!(unpruned_stats.axes_found, 4);
+
+        let pruning = SymmetryOptions {
+            prune_by_centroid: true,
+            ..SymmetryOptions::default()
+        };
+        let (pruned, stats) = get_lines_of_sym_with_stats(&square, &pruning);
+        assert!(stats.candidates_pruned > 0);
+        assert!(stats.candidates_pruned <= stats.candidates_generated);
+        assert_eq!(stats.axes_found, 4);
+        assert_eq!(pruned.lines, unpruned.lines);
+    }
+
+    /// Tests that pruning compares the centroid at a tolerance covering its rounding: averaged in
+    /// this order, the points' small coordinates are rounded away, and their centroid lands a
+    /// quarter off their axis `x = 0`, far beyond the search's tolerance.
+    #[test]
+    fn test_pruning_at_large_coordinates() {
+        let points = [Point::new(1e16, 0.), Point::new(1., 1.), Point::new(-1e16, 0.), Point::new(-1., 1.)];
+        let points_vec: Vec<&Point> = points.iter().collect();
+        let center = centroid(&points_vec).unwrap();
+        assert_eq!(center.x, -0.25);
+
+        let reflected = Line::new(1., 0., 0.).try_reflected_point(&center).unwrap();
+        assert!(!reflected.approx_eq(&center, config::EPSILON));
+        assert!(reflected.approx_eq(&center, prune_tolerance(&points_vec, config::EPSILON)));
+    }
+
+    /// Tests that bounding-box pruning on a long thin rectangle of points rejects the candidates
+    /// generated by pairs across its diagonals, while its two axes survive.
+    #[test]
+    fn test_bounding_box_pruning() {
+        let rectangle: HashSet<Point> = centered_grid(9, 2);
+        let pruning = SymmetryOptions {
+            prune_by_bounding_box: true,
+            ..SymmetryOptions::default()
+        };
+        let (result, stats) = get_lines_of_sym_with_stats(&rectangle, &pruning);
+        assert!(stats.candidates_pruned > 0);
+        assert_eq!(result.lines, get_lines_of_sym(&rectangle, None).unwrap());
+        assert_eq!(stats.axes_found, 2);
+
+        // The bisector of a corner-to-corner diagonal is pruned; the axes are not.
+        let points_vec: Vec<&Point> = rectangle.iter().collect();
+        let bounding_box = BoundingBox::new(&points_vec);
+        let diagonal = get_equidistant_line(&Point::new(-8., -1.), &Point::new(8., 1.));
+        assert!(!bounding_box.extremes_reflect_inside(&diagonal, config::EPSILON));
+        assert!(result.lines.iter().all(|axis| bounding_box.extremes_reflect_inside(axis, config::EPSILON)));
+    }
+
+    proptest::proptest! {
+        /// Every axis returned maps the input set onto itself, and sets built to be mirror
+        /// symmetric have their mirror axis found. Small integer coordinates make partial and
+        /// full symmetries common.
+        #[test]
+        fn prop_axes_map_set_onto_itself(
+            coords in proptest::collection::vec((-3i32..=3, -3i32..=3), 1..8),
+            mirror in proptest::option::of(-3i32..=3),
+        ) {
+            let mut points: HashSet<Point> =
+                coords.iter().map(|&(x, y)| Point::new(x as f64, y as f64)).collect();
+            if let Some(m) = mirror {
+                let mirrored: Vec<Point> = points.iter().map(|p| Point::new(m as f64 - p.x, p.y)).collect();
+                points.extend(mirrored);
+            }
+
+            let lines = get_lines_of_sym_or_empty(&points, None);
+            for line in &lines {
+                proptest::prop_assert!(verify_axis(&points, line), "{:?} isn't an axis", line);
+            }
+
+            if let Some(m) = mirror {
+                let axis = Line::new(1., 0., -(m as f64) / 2.);
+                proptest::prop_assert!(points.len() == 1 || lines.iter().any(|l| l.approx_eq(&axis, 1e-9)));
+            }
+        }
+    }
+
+    /// Tests the summaries of shapes with each kind of symmetry.
+    #[test]
+    fn test_describe() {
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+        ]);
+        let summary = describe(&square);
+        assert_eq!((summary.reflective_axes, summary.rotational_order, summary.central), (4, 4, true));
+        assert_eq!(summary.label, "D4 (square-like)");
+
+        let triangle = HashSet::from([Point::new(0., 0.), Point::new(2., 0.), Point::new(1., 3f64.sqrt())]);
+        let summary = describe(&triangle);
+        assert_eq!((summary.reflective_axes, summary.rotational_order, summary.central), (3, 3, false));
+        assert_eq!(summary.label, "D3 (equilateral-triangle-like)");
+
+        let parallelogram = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(2., 0.),
+            Point::new(3., 1.),
+            Point::new(1., 1.),
+        ]);
+        let summary = describe(&parallelogram);
+        assert_eq!((summary.reflective_axes, summary.rotational_order, summary.central), (0, 2, true));
+        assert_eq!(summary.label, "C2 (parallelogram-like)");
+
+        let scalene = HashSet::from([Point::new(0., 0.), Point::new(4., 0.), Point::new(1., 2.)]);
+        let summary = describe(&scalene);
+        assert_eq!((summary.reflective_axes, summary.rotational_order, summary.central), (0, 1, false));
+        assert_eq!(summary.label, "C1 (asymmetric)");
+
+        assert_eq!(describe(&HashSet::from([Point::new(1., 1.)])).label, "D∞ (point-like)");
+    }
+
+    /// Tests that with radial point equality, a square corner displaced diagonally by less than the
+    /// tolerance in each coordinate, but more than it in distance, breaks the axes that move it.
+    #[test]
+    fn test_radial_point_equality() {
+        let offset = 0.9 * config::EPSILON;
+        let square = HashSet::from([
+            Point::new(offset, offset),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+        ]);
+        assert_eq!(get_lines_of_sym(&square, None).unwrap().len(), 4);
+
+        let radial = SymmetryOptions {
+            point_equality: PointEquality::Radial(config::EPSILON),
+            ..SymmetryOptions::default()
+        };
+        let lines = get_lines_of_sym_with(&square, &radial).lines;
+        assert_eq!(lines.len(), 1);
+        assert!(lines.iter().next().unwrap().approx_eq(&Line::new(1., -1., 0.), 1e3 * config::EPSILON));
+
+        let loose = SymmetryOptions {
+            point_equality: PointEquality::Radial(1e3 * config::EPSILON),
+            ..SymmetryOptions::default()
+        };
+        assert_eq!(get_lines_of_sym_with(&square, &loose).lines.len(), 4);
+    }
+
+    /// Tests that the check for mirror partners before a search matches points within the tolerance
+    /// of the search's point equality, so that a radial tolerance looser than `epsilon` doesn't
+    /// rule out axes it would accept.
+    #[test]
+    fn test_mirror_partners_within_radial_tolerance() {
+        // Displaced along a side, the corner leaves no two points with matching distances.
+        let offset = 100. * config::EPSILON;
+        let square = HashSet::from([
+            Point::new(offset, 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+        ]);
+        let points_vec: Vec<&Point> = square.iter().collect();
+        assert!(!mirror_partners_possible(&points_vec, config::EPSILON));
+        assert!(mirror_partners_possible(&points_vec, 1e3 * config::EPSILON));
+
+        let loose = SymmetryOptions {
+            point_equality: PointEquality::Radial(1e3 * config::EPSILON),
+            ..SymmetryOptions::default()
+        };
+        assert_eq!(get_lines_of_sym_with(&square, &loose).lines.len(), 4);
+    }
+
+    /// Tests the symmetry group orders of shapes with dihedral, cyclic and trivial groups.
+    #[test]
+    fn test_symmetry_group_order() {
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+        ]);
+        assert_eq!(symmetry_group_order(&square), 8);
+
+        let h = 3f64.sqrt() / 2.;
+        let triangle = HashSet::from([Point::new(0., 0.), Point::new(1., 0.), Point::new(0.5, h)]);
+        assert_eq!(symmetry_group_order(&triangle), 6);
+
+        let parallelogram = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(2., 0.),
+            Point::new(3., 1.),
+            Point::new(1., 1.),
+        ]);
+        assert_eq!(symmetry_group_order(&parallelogram), 2);
+
+        let scalene = HashSet::from([Point::new(0., 0.), Point::new(4., 0.), Point::new(1., 2.)]);
+        assert_eq!(symmetry_group_order(&scalene), 1);
+        assert_eq!(symmetry_group_order(&HashSet::from([Point::new(1., 1.)])), usize::MAX);
+    }
+
+    /// Tests that the detected axes of a square, a pinwheel and other shapes agree with their
+    /// rotational symmetry, and that those of a square distorted within the tolerance don't.
+    #[test]
+    fn test_is_consistent_dihedral() {
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+        ]);
+        assert_eq!(describe(&square).reflective_axes, 4);
+        assert!(is_consistent_dihedral(&square));
+
+        // Each vertex of a square carries a blade pointing counterclockwise: C4, without axes.
+        let quarter_turns = |p: Point| {
+            (0..4).map(move |k| p.rotate_about(&Point::new(0., 0.), k as f64 * core::f64::consts::FRAC_PI_2))
+        };
+        let pinwheel: HashSet<Point> =
+            quarter_turns(Point::new(1., 0.)).chain(quarter_turns(Point::new(1., 0.3))).collect();
+        let symmetry = describe(&pinwheel);
+        assert_eq!((symmetry.reflective_axes, symmetry.rotational_order), (0, 4));
+        assert!(is_consistent_dihedral(&pinwheel));
+
+        let pair = HashSet::from([Point::new(0., 0.), Point::new(1., 0.)]);
+        assert!(is_consistent_dihedral(&pair));
+        assert!(is_consistent_dihedral(&HashSet::from([Point::new(1., 1.)])));
+        assert!(is_consistent_dihedral(&HashSet::<Point>::new()));
+
+        // A corner nudged by less than the tolerance keeps the quarter turns, which move it by the
+        // nudge, but breaks the reflection across the x-axis, which moves it by twice as much.
+        let nudged = HashSet::from([
+            Point::new(1., 0.7 * config::EPSILON),
+            Point::new(0., 1.),
+            Point::new(-1., 0.),
+            Point::new(0., -1.),
+        ]);
+        let symmetry = describe(&nudged);
+        assert_eq!((symmetry.reflective_axes, symmetry.rotational_order), (3, 4));
+        assert!(!is_consistent_dihedral(&nudged));
+    }
+
+    /// Tests that a perfect square's axes have full confidence, and that displacing a corner lowers
+    /// the confidence of the axes that move it while they are still detected.
+    #[test]
+    fn test_detect_with_confidence() {
+        let square = |corner: Point| {
+            HashSet::from([Point::new(0., 0.), Point::new(1., 0.), Point::new(1., 1.), corner])
+        };
+        let detected = detect_with_confidence(&square(Point::new(0., 1.)), 1e-3);
+        assert_eq!(detected.len(), 4);
+        assert!(detected.iter().all(|(_, confidence)| (confidence - 1.).abs() < 1e-9));
+
+        let noisy = square(Point::new(0., 1.002));
+        assert!(get_lines_of_sym(&noisy, None).unwrap().len() < 4);
+        let detected = detect_with_confidence(&noisy, 1e-2);
+        assert_eq!(detected.len(), 4);
+        assert!(detected.iter().all(|(_, confidence)| (0.99..1.).contains(confidence)));
+        assert!(detected.windows(2).all(|w| w[0].1 >= w[1].1));
+
+        assert!(detect_with_confidence(&HashSet::from([Point::new(1., 1.)]), 1e-3).is_empty());
+    }
+
+    /// Tests that a nearly square rectangle has its two axes at every tolerance, and the diagonals
+    /// of a square only at the loosest.
+    #[test]
+    fn test_detect_multiscale() {
+        let rectangle = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.001),
+            Point::new(0., 1.001),
+        ]);
+        let scales = detect_multiscale(&rectangle, &[1e-2, 1e-8, 1e-4]);
+
+        let tolerances: Vec<f64> = scales.iter().map(|(tol, _)| *tol).collect();
+        assert_eq!(tolerances, [1e-8, 1e-4, 1e-2]);
+        let counts: Vec<usize> = scales.iter().map(|(_, lines)| lines.len()).collect();
+        assert_eq!(counts, [2, 2, 4]);
+
+        let robust = &scales[0].1;
+        let found_at = |lines: &HashSet<Line>, line: &Line| lines.iter().any(|l| l.approx_eq(line, 1e-6));
+        assert!(robust.iter().all(|line| scales.iter().all(|(_, lines)| found_at(lines, line))));
+    }
+
+    /// Tests that two separated squares, which together only share the diagonal through both, are
+    /// split into two clusters of four axes each.
+    #[test]
+    fn test_detect_clusters() {
+        let square = |x: f64, y: f64, side: f64| {
+            [(0., 0.), (1., 0.), (1., 1.), (0., 1.)].map(|(dx, dy)| Point::new(x + side * dx, y + side * dy))
+        };
+        let points: HashSet<Point> = square(0., 0., 1.).into_iter().chain(square(5., 5., 2.)).collect();
+        assert_eq!(get_lines_of_sym(&points, None).unwrap().len(), 1);
+
+        let clusters = detect_clusters(&points, 2.5);
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].0, HashSet::from(square(0., 0., 1.)));
+        assert_eq!(clusters[1].0, HashSet::from(square(5., 5., 2.)));
+        assert!(clusters.iter().all(|(_, lines)| lines.len() == 4));
+
+        // With a radius below the side of the smaller square, every point is its own cluster.
+        let singletons = detect_clusters(&points, 0.5);
+        assert_eq!(singletons.len(), 8);
+        assert!(singletons.iter().all(|(cluster, lines)| cluster.len() == 1 && lines.is_empty()));
+    }
+
+    /// Tests that a corner of a square split by noise into two points is merged back by
+    /// `min_feature_size`, restoring the square's axes.
+    #[test]
+    fn test_min_feature_size() {
+        let noisy_square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(2., 0.),
+            Point::new(0., 2.),
+            Point::new(2., 2.001),
+            Point::new(2., 1.999),
+        ]);
+        assert!(get_lines_of_sym_or_empty(&noisy_square, None).is_empty());
+
+        let opts = SymmetryOptions { min_feature_size: Some(0.01), ..SymmetryOptions::default() };
+        let lines = get_lines_of_sym_with(&noisy_square, &opts).lines;
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(2., 0.),
+            Point::new(2., 2.),
+            Point::new(0., 2.),
+        ]);
+        assert_eq!(lines, get_lines_of_sym_or_empty(&square, None));
+        assert_eq!(lines.len(), 4);
+
+        // A size below the noise merges nothing.
+        let opts = SymmetryOptions { min_feature_size: Some(0.001), ..SymmetryOptions::default() };
+        assert!(get_lines_of_sym_with(&noisy_square, &opts).lines.is_empty());
+    }
+
+    /// Tests finding the center of symmetric lists of values, and rejecting asymmetric ones.
+    #[test]
+    fn test_is_symmetric_1d() {
+        assert_eq!(is_symmetric_1d(&[-2., -1., 1., 2.], 1e-9), Some(0.));
+        assert_eq!(is_symmetric_1d(&[7., 3., 5.], 1e-9), Some(5.));
+        assert_eq!(is_symmetric_1d(&[4.], 1e-9), Some(4.));
+        assert_eq!(is_symmetric_1d(&[0., 1., 1., 2.], 1e-9), Some(1.));
+
+        assert_eq!(is_symmetric_1d(&[-2., -1., 1.05, 2.], 1e-9), None);
+        assert_eq!(is_symmetric_1d(&[-2., -1., 1.05, 2.], 0.1), Some(0.));
+        // A repeated value needs a partner of its own.
+        assert_eq!(is_symmetric_1d(&[0., 0., 2.], 1e-9), None);
+        assert_eq!(is_symmetric_1d(&[], 1e-9), None);
+        assert_eq!(is_symmetric_1d(&[0., f64::NAN], 1e-9), None);
+    }
+
+    /// Tests that a square frame with an asymmetric cutout inside has the square's axes once the
+    /// cutout's region is kept out.
+    #[test]
+    fn test_keep_out_region() {
+        let mut frame: HashSet<Point> = (0..4)
+            .flat_map(|i| {
+                let t = i as f64;
+                [Point::new(t, 0.), Point::new(4., t), Point::new(4. - t, 4.), Point::new(0., 4. - t)]
+            })
+            .collect();
+        assert_eq!(get_lines_of_sym_or_empty(&frame, None).len(), 4);
+        frame.extend([Point::new(1.5, 1.), Point::new(2.5, 2.2), Point::new(1., 3.)]);
+        assert!(get_lines_of_sym_or_empty(&frame, None).is_empty());
+
+        let opts = SymmetryOptions {
+            keep_out_region: Some((Point::new(0.5, 0.5), Point::new(3.5, 3.5))),
+            ..SymmetryOptions::default()
+        };
+        let lines = get_lines_of_sym_with(&frame, &opts).lines;
+        assert_eq!(lines.len(), 4);
+        assert!(lines.contains(&Line::new(1., -1., 0.)));
+    }
+
+    /// Tests that the Hausdorff distance to the reflection is zero across an axis, and is the
+    /// displacement of the worst point otherwise.
+    #[test]
+    fn test_reflection_hausdorff() {
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+        ]);
+        let vertical = Line::new(1., 0., -0.5);
+        assert!(reflection_hausdorff(&square, &vertical) < 1e-12);
+        assert!(reflection_hausdorff(&square, &Line::new(1., -1., 0.)) < 1e-12);
+
+        // Across a line off the axis by 0.1, every point moves by 0.2.
+        assert!((reflection_hausdorff(&square, &Line::new(1., 0., -0.6)) - 0.2).abs() < 1e-12);
+
+        // An extra point near a corner has no partner, but is close to the corner's reflection.
+        let mut extra = square.clone();
+        extra.insert(Point::new(0., 1.05));
+        assert!((reflection_hausdorff(&extra, &vertical) - 0.05).abs() < 1e-12);
+        assert_eq!(reflection_hausdorff(&HashSet::new(), &vertical), 0.);
+    }
+
+    /// Tests that squares with coordinates around `1e7`, whose reflections are off by more than the
+    /// tolerance in the input's coordinates, keep their four axes.
+    #[test]
+    fn test_large_coordinates() {
+        for (origin, side) in [(1e7 + 0.3, 0.7), (1.234567e7, 3.3), (-3.3e7, 0.1)] {
+            let square = HashSet::from([
+                Point::new(origin, origin),
+                Point::new(origin + side, origin),
+                Point::new(origin + side, origin + side),
+                Point::new(origin, origin + side),
+            ]);
+            let lines = get_lines_of_sym(&square, None).unwrap();
+
+            let middle = origin + side / 2.;
+            let axes = [
+                Line::new(1., 0., -middle),
+                Line::new(0., 1., -middle),
+                Line::new(1., -1., 0.),
+                Line::new(1., 1., -2. * middle),
+            ];
+            assert_eq!(lines.len(), 4);
+            assert!(axes.iter().all(|axis| lines.iter().any(|line| line.approx_eq(axis, 1e-6))));
+        }
+    }
+
+    /// Tests two point sets searched at different epsilons in one process, each with its own result.
+    #[test]
+    fn test_per_call_epsilon() {
+        let square = |offset: f64| {
+            HashSet::from([
+                Point::new(offset, offset),
+                Point::new(1., 0.),
+                Point::new(1., 1.),
+                Point::new(0., 1.),
+            ])
+        };
+        let with_epsilon = |epsilon: f64| SymmetryOptions {
+            epsilon: Some(epsilon),
+            ..SymmetryOptions::default()
+        };
+        let eps = config::EPSILON;
+        let (fine, coarse) = (square(100. * eps), square(1e4 * eps));
+
+        // Only the diagonal through the displaced corner survives at the default epsilon.
+        assert_eq!(config::default_epsilon::<f64>(), config::EPSILON);
+        assert_eq!(get_lines_of_sym(&fine, None).unwrap().len(), 1);
+        assert_eq!(get_lines_of_sym_with(&coarse, &with_epsilon(eps)).lines.len(), 1);
+
+        assert_eq!(get_lines_of_sym_with(&fine, &with_epsilon(1e3 * eps)).lines.len(), 4);
+        assert_eq!(get_lines_of_sym_with(&coarse, &with_epsilon(1e3 * eps)).lines.len(), 1);
+        assert_eq!(get_lines_of_sym_with(&coarse, &with_epsilon(1e5 * eps)).lines.len(), 4);
+
+        // A looser epsilon also makes nearly collinear points collinear.
+        let bent = HashSet::from([Point::new(0., 0.), Point::new(1., 100. * eps), Point::new(2., 0.)]);
+        assert_eq!(get_lines_of_sym(&bent, None).unwrap().len(), 1);
+        assert_eq!(get_lines_of_sym_with(&bent, &with_epsilon(1e4 * eps)).lines.len(), 2);
+
+        // The functions without options take the tolerance explicitly.
+        let vertical = Line::new(1., 0., -0.5);
+        assert!(!verify_axis(&fine, &vertical));
+        assert!(verify_axis_tol(&fine, &vertical, 1e3 * eps));
+        let center = Point::new(0.5, 0.5);
+        assert_eq!(axes_through_center(&fine, &center).len(), 1);
+        assert_eq!(axes_through_center_tol(&fine, &center, 1e3 * eps).len(), 4);
+        assert_eq!(input_shape_kind(&bent), InputKind::Planar);
+        assert_eq!(input_shape_kind_tol(&bent, 1e4 * eps), InputKind::Collinear);
+        let scalene = HashSet::from([Point::new(-1., 0.), Point::new(1. + 100. * eps, 0.), Point::new(0., 2.)]);
+        assert!(!could_be_symmetric(&scalene));
+        assert!(could_be_symmetric_tol(&scalene, 1e3 * eps));
+        #[cfg(feature = "std")]
+        {
+            assert_eq!(get_lines_of_sym_threaded(&fine).len(), 1);
+            assert_eq!(get_lines_of_sym_threaded_tol(&fine, 1e3 * eps).len(), 4);
+            assert_eq!(get_lines_of_sym_threaded_tol(&bent, 1e4 * eps).len(), 2);
+        }
+        #[cfg(feature = "rayon")]
+        assert_eq!(get_lines_of_sym_par_tol(&fine, 1e3 * eps).len(), 4);
+    }
+
+    /// Tests that an axis is found even when some of its generating pairs give differently scaled
+    /// candidates, across which reflections miss their partners' hashes by rounding.
+    #[test]
+    fn test_axis_survives_rounding_misses() {
+        let points = mirrored_scatter(60);
+        let lines = get_lines_of_sym(&points, None).unwrap();
+        assert_eq!(lines.len(), 1);
+        assert!(lines.iter().next().unwrap().approx_eq(&Line::new(1., 0., 0.), 1e-9));
+    }
+
+    /// Tests the lookup the search falls back on when a reflection misses the hash index: a point
+    /// farther than the scalar's tolerance from its partner, but within the search's, is found
+    /// among the points sorted by x, and a point outside the set's bounds is rejected before any
+    /// comparison.
+    #[test]
+    fn test_tolerant_lookup_after_hash_miss() {
+        let points = [Point::new(0., 0.), Point::new(1., 2.), Point::new(3., 1.)];
+        let points_vec: Vec<&Point> = points.iter().collect();
+        let index: QuantizedIndex<f64> = QuantizedIndex::new(&points_vec, config::EPSILON);
+        let by_x = SortedByX::new(&points_vec);
+        let window = 10. * config::EPSILON;
+        let same = |p: &Point, q: &Point| p.approx_eq(q, window);
+
+        let drifted = Point::new(1. + 3. * config::EPSILON, 2. - 3. * config::EPSILON);
+        assert_eq!(index.get(&points_vec, &drifted), None);
+        assert_eq!(by_x.find_within(&points_vec, &drifted, window, same), Some(1));
+
+        let far = Point::new(1. + 20. * config::EPSILON, 2.);
+        assert_eq!(by_x.find_within(&points_vec, &far, window, same), None);
+        let outside = Point::new(4., 1.);
+        assert_eq!(by_x.find_within(&points_vec, &outside, window, |_, _| true), None);
+    }
+
+    /// Tests that the search on threads finds the same axes as the sequential one, on more than
+    /// one thread.
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_threaded_matches_sequential() {
+        for points in [centered_grid::<DefaultHashBuilder>(4, 4), centered_grid(5, 3), mirrored_scatter(60)] {
+            let sequential = sorted_by_angle(&get_lines_of_sym(&points, None).unwrap());
+            let (threaded, threads) = search_on_threads(&points, 4, config::EPSILON);
+            let threaded = sorted_by_angle(&threaded);
+
+            assert_eq!(threads, 4);
+            assert_eq!(threaded.len(), sequential.len());
+            assert!(threaded.iter().zip(&sequential).all(|(t, s)| t.approx_eq(s, 1e-9)));
+            assert_eq!(sorted_by_angle(&get_lines_of_sym_threaded(&points)).len(), sequential.len());
+        }
+    }
+
+    /// Tests that the parallel search finds the same axes as the sequential one, and returns the
+    /// same coefficients however the input set was built.
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_matches_sequential() {
+        for points in [centered_grid::<DefaultHashBuilder>(4, 4), centered_grid(5, 3), mirrored_scatter(60)] {
+            let sequential = sorted_by_angle(&get_lines_of_sym(&points, None).unwrap());
+            let parallel = sorted_by_angle(&get_lines_of_sym_par(&points));
+
+            assert_eq!(parallel.len(), sequential.len());
+            assert!(parallel.iter().zip(&sequential).all(|(p, s)| p.approx_eq(s, 1e-9)));
+
+            let mut order: Vec<Point> = points.iter().copied().collect();
+            order.reverse();
+            let rebuilt: HashSet<Point> = order.into_iter().collect();
+            let again = sorted_by_angle(&get_lines_of_sym_par(&rebuilt));
+            assert_eq!(again.len(), parallel.len());
+            assert!(again.iter().zip(&parallel).all(|(p, q)| p.to_array() == q.to_array()));
+        }
+    }
+
+    /// Tests that a single point and a cluster of coincident points short-circuit to infinitely
+    /// many axes, and that regular polygons take the fast path.
+    #[test]
+    fn test_lines_of_sym_or_infinite() {
+        let single = HashSet::from([Point::new(3., -1.)]);
+        assert!(matches!(get_lines_of_sym_or_infinite(&single), LinesOfSym::Infinite));
+
+        let cluster = HashSet::from([
+            Point::new(1., 1.),
+            Point::new(1. + 1e-12, 1.),
+            Point::new(1., 1. - 1e-12),
+        ]);
+        assert!(matches!(get_lines_of_sym_or_infinite(&cluster), LinesOfSym::Infinite));
+
+        let hexagon: HashSet<Point> = (0..6)
+            .map(|k| {
+                let (sin, cos) = Float::sin_cos(k as f64 * core::f64::consts::FRAC_PI_3);
+                Point::new(cos, sin)
+            })
+            .collect();
+        assert_eq!(rotational_symmetry_order(&hexagon), 6);
+        let LinesOfSym::Finite(axes) = get_lines_of_sym_or_infinite(&hexagon) else {
+            panic!("a hexagon has finitely many axes");
+        };
+        assert_eq!(axes.len(), 6);
+        assert!(axes.iter().all(|axis| verify_axis(&hexagon, axis)));
+
+        let rectangle: HashSet<Point> = centered_grid(3, 2);
+        assert_eq!(rotational_symmetry_order(&rectangle), 2);
+        let LinesOfSym::Finite(axes) = get_lines_of_sym_or_infinite(&rectangle) else {
+            panic!("a rectangle has finitely many axes");
+        };
+        assert_eq!(axes, get_lines_of_sym(&rectangle, None).unwrap());
+
+        let empty: HashSet<Point> = HashSet::new();
+        assert!(matches!(get_lines_of_sym_or_infinite(&empty), LinesOfSym::Finite(axes) if axes.is_empty()));
+    }
+
+    /// Tests checking single orders of rotational symmetry of a square, with and without a point
+    /// at its center, and of an asymmetric set.
+    #[test]
+    fn test_has_rotational_symmetry() {
+        let mut square: HashSet<Point> = centered_grid(2, 2);
+        assert!(has_rotational_symmetry(&square, 4));
+        assert!(has_rotational_symmetry(&square, 2));
+        assert!(!has_rotational_symmetry(&square, 3));
+        assert!(!has_rotational_symmetry(&square, 8));
+
+        square.insert(Point::new(0., 0.));
+        assert!(has_rotational_symmetry(&square, 4));
+
+        let triangle = HashSet::from([Point::new(0., 0.), Point::new(2., 0.), Point::new(0., 1.)]);
+        assert!(!has_rotational_symmetry(&triangle, 3));
+        assert!(has_rotational_symmetry(&triangle, 1));
+        assert!(has_rotational_symmetry(&HashSet::<Point>::new(), 5));
+    }
+
+    /// Tests that the line through collinear points has the same coefficients, to the bit, in
+    /// whatever order the points are visited.
+    #[test]
+    fn test_through_line_deterministic() {
+        let points: Vec<Point> =
+            [0.3, -1.7, 2.9, 0.8, -0.4].iter().map(|&x| Point::new(x, 2. * x + 1.)).collect();
+        let mut refs: Vec<&Point> = points.iter().collect();
+        let expected = collinear_through_line(&refs, config::EPSILON).unwrap().to_array();
+        for _ in 0..refs.len() {
+            refs.rotate_left(1);
+            assert_eq!(collinear_through_line(&refs, config::EPSILON).unwrap().to_array(), expected);
+            refs.reverse();
+            assert_eq!(collinear_through_line(&refs, config::EPSILON).unwrap().to_array(), expected);
+        }
+
+        let set: HashSet<Point> = points.iter().copied().collect();
+        assert_eq!(get_lines_of_sym_or_empty(&set, None), HashSet::from([Line::new(2., -1., 1.)]));
+    }
+
+    /// Tests that two distinct points have exactly two axes: their perpendicular bisector and the
+    /// line through them.
+    #[test]
+    fn test_two_points() {
+        let pair = HashSet::from([Point::new(0., 0.), Point::new(2., 0.)]);
+        let lines = get_lines_of_sym(&pair, None).unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().any(|l| l.approx_eq(&Line::new(1., 0., -1.), 1e-9)));
+        assert!(lines.iter().any(|l| l.approx_eq(&Line::new(0., 1., 0.), 1e-9)));
+    }
+
+    /// Tests that `include_through_line` toggles only the line through collinear points.
+    #[test]
+    fn test_include_through_line() {
+        let even: HashSet<Point> = (-2..=2).map(|x| Point::new(x as f64, 0.)).collect();
+        let without = SymmetryOptions {
+            include_through_line: false,
+            ..SymmetryOptions::default()
+        };
+
+        let lines = get_lines_of_sym_with(&even, &without).lines;
+        assert_eq!(lines.len(), 1);
+        assert!(lines.iter().next().unwrap().approx_eq(&Line::new(1., 0., 0.), 1e-9));
+
+        let lines = get_lines_of_sym_with(&even, &SymmetryOptions::default()).lines;
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().any(|l| l.approx_eq(&Line::new(0., 1., 0.), 1e-9)));
+    }
+
+    /// Tests that a square's diagonal passes verification and a deliberately-wrong line fails it.
+    #[test]
+    fn test_verify_axis() {
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+        ]);
+
+        assert!(verify_axis(&square, &Line::new(1., -1., 0.)));
+        assert!(verify_axis(&square, &Line::new(0., 1., -0.5)));
+        // Passes through two vertices but isn't an axis.
+        assert!(!verify_axis(&square, &Line::new(1., 0., 0.)));
+        assert!(!verify_axis(&square, &Line::new(1., -1., 0.25)));
+
+        let lines = get_lines_of_sym(&square, None).unwrap();
+        assert!(lines.iter().all(|l| verify_axis(&square, l)));
+    }
+
+    /// Tests that axes `1.5 * EPSILON` apart, too far apart to be `==`, collapse to one at a
+    /// tolerance of `2 * EPSILON`, while distinct axes are kept, and that the representative of
+    /// each group is the same whatever the order the lines are given in.
+    #[test]
+    fn test_cluster_lines() {
+        let group = [
+            Line::new(1., 0., -0.5),
+            // The same vertical axis, scaled and shifted by 1.5 * EPSILON.
+            Line::new(2., 0., -1. - 3. * config::EPSILON),
+            Line::new(0., 1., -0.5),
+        ];
+        let lines = HashSet::from(group);
+        assert_eq!(lines.len(), 3);
+
+        let clustered = cluster_lines(lines, 2. * config::EPSILON);
+        assert_eq!(clustered.len(), 2);
+        assert!(clustered.iter().any(|l| l.is_point_on_line(&Point::new(0.5, 3.))));
+        assert!(clustered.iter().any(|l| l.is_point_on_line(&Point::new(3., 0.5))));
+
+        let bits = |lines: Vec<Line>| {
+            let mut bits: Vec<[u64; 3]> = lines.iter().map(|l| l.to_array().map(f64::to_bits)).collect();
+            bits.sort();
+            bits
+        };
+        let mut reversed = group.to_vec();
+        reversed.reverse();
+        let first = bits(cluster_line_vec(group.to_vec(), 2. * config::EPSILON, 2. * config::EPSILON));
+        assert_eq!(bits(cluster_line_vec(reversed, 2. * config::EPSILON, 2. * config::EPSILON)), first);
+        assert!(first.contains(&Line::new(1., 0., -0.5).to_array().map(f64::to_bits)));
+    }
+
+    /// Tests that a regular hexagon's axes through opposite vertices come before its axes through
+    /// the midpoints of opposite edges.
+    #[test]
+    fn test_axes_by_on_line_count() {
+        let hexagon: HashSet<Point> = (0..6)
+            .map(|i| {
+                let angle = core::f64::consts::FRAC_PI_3 * i as f64;
+                Point::new(angle.cos(), angle.sin())
+            })
+            .collect();
+        let axes = axes_by_on_line_count(&hexagon);
+        let counts: Vec<usize> = axes.iter().map(|(_, count)| *count).collect();
+        assert_eq!(counts, [2, 2, 2, 0, 0, 0]);
+        // The horizontal axis through the vertices at (±1, 0) has the lowest orientation.
+        assert_eq!(axes[0].0, Line::new(0., 1., 0.));
+        assert!(axes[3..].iter().all(|(line, _)| !line.is_point_on_line(&Point::new(1., 0.))));
+    }
+
+    /// Tests that proportional axes are merged into one, and that the angle and offset tolerances
+    /// apply separately.
+    #[test]
+    fn test_dedup_axes() {
+        let proportional: HashSet<Line> =
+            [Line::new(1., -1., 0.), Line::new(-3., 3., 0.)].into_iter().collect();
+        assert_eq!(proportional.len(), 1);
+
+        // Proportional up to rounding in the offset, which puts them more than EPSILON apart.
+        let offset = 100. * config::EPSILON;
+        let lines = HashSet::from([Line::new(1., -1., 0.), Line::new(2., -2., offset), Line::new(1., 1., -1.)]);
+        assert_eq!(lines.len(), 3);
+        let deduped = dedup_axes(lines, 1e3 * config::EPSILON, 1e3 * config::EPSILON);
+        assert_eq!(deduped.len(), 2);
+        // Either of the merged lines may represent their group.
+        assert!(deduped.iter().any(|l| l.a == -l.b && l.c.abs() <= offset));
+        assert!(deduped.contains(&Line::new(1., 1., -1.)));
+
+        let parallel = HashSet::from([Line::new(1., 0., 0.), Line::new(1., 0., -0.01)]);
+        assert_eq!(dedup_axes(parallel, 1., 1e-3).len(), 2);
+        let crossing = || HashSet::from([Line::new(1., 0., 0.), Line::new(1., 0.01, 0.)]);
+        assert_eq!(dedup_axes(crossing(), 1e-3, 1.).len(), 2);
+        assert_eq!(dedup_axes(crossing(), 0.1, 1e-3).len(), 1);
+    }
+
+    /// Tests that the candidates generated for an axis by differently scaled pairs share a key,
+    /// that clustering finds near lines across the wrap of normal angles, and that the axes
+    /// collected by key take in each axis once.
+    #[test]
+    fn test_axis_key() {
+        let grid = AxisGrid::new(1e-6, 1e-6).unwrap();
+        let near_pair = perpendicular_bisector(&Point::new(0., 0.), &Point::new(2., 0.)).unwrap();
+        let far_pair = perpendicular_bisector(&Point::new(-3., 5.), &Point::new(5., 5.)).unwrap();
+        assert_ne!(near_pair.to_array(), far_pair.to_array());
+        assert_eq!(grid.key(&near_pair), grid.key(&far_pair));
+        assert_eq!(grid.key(&Line::new(-1., 0., 1.)), grid.key(&near_pair));
+        assert_ne!(grid.key(&Line::new(1., 0., -1.1)), grid.key(&near_pair));
+        assert!(grid.key(&Line::new(0., 0., 1.)).is_none());
+
+        // The canonical normal of one of these points up and the other's down, so their keys are
+        // in the last and first angle buckets.
+        let (up, down) = (Line::new(1e-3, 1., 2.), Line::new(-1e-3, 1., 2.));
+        let grid = AxisGrid::new(0.01, 0.01).unwrap();
+        let (up_key, down_key) = (grid.key(&up).unwrap(), grid.key(&down).unwrap());
+        assert_eq!((up_key.angle_bucket, down_key.angle_bucket), (grid.angle_buckets - 1, 0));
+        assert!(grid.neighbours(&down).unwrap().contains(&up_key));
+        assert_eq!(dedup_axes(HashSet::from([up, down]), 0.01, 0.01).len(), 1);
+
+        // As the search collects axes: an axis found again from a pair off by rounding is known.
+        let mut found = AxisBuckets::new(1e-6, 1e-6);
+        assert!(found.insert(near_pair));
+        assert!(!found.insert(Line::new(far_pair.a, far_pair.b, far_pair.c * (1. + 1e-12))));
+        assert!(found.insert(Line::new(1., 0., -1.1)));
+        assert_eq!(found.lines.len(), 2);
+    }
+
+    /// Tests that an asymmetric shape, its mirror image moved, rotated and scaled, and a square
+    /// and its mirror image have equal canonical forms, and that different shapes don't.
+    #[test]
+    fn test_canonical_form() {
+        let shape = [(0., 0.), (3., 0.), (3., 1.), (1., 2.), (0., 1.5)];
+        let (sin, cos) = 0.7f64.sin_cos();
+        let original: HashSet<Point> = shape.iter().map(|&(x, y)| Point::new(x, y)).collect();
+        let mirrored: HashSet<Point> = shape
+            .iter()
+            .map(|&(x, y)| (-2.5 * x, 2.5 * y))
+            .map(|(x, y)| Point::new(10. + x * cos - y * sin, -4. + x * sin + y * cos))
+            .collect();
+        let form = canonical_form(&original);
+        assert_eq!(form.len(), 5);
+        assert_eq!(form, canonical_form(&mirrored));
+
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+        ]);
+        let mirrored_square: HashSet<Point> = square.iter().map(|p| Point::new(-p.x, p.y)).collect();
+        assert_eq!(canonical_form(&square), canonical_form(&mirrored_square));
+        assert_ne!(canonical_form(&square), canonical_form(&centered_grid(2, 1)));
+        assert_ne!(form, canonical_form(&original.iter().map(|p| Point::new(p.x, 2. * p.y)).collect()));
+
+        assert!(canonical_form(&HashSet::new()).is_empty());
+        assert_eq!(canonical_form(&HashSet::from([Point::new(3., 4.)])), [Point::new(0., 0.)]);
+    }
+
+    /// Tests that congruent sets share a distance fingerprint and non-congruent sets don't.
+    #[test]
+    fn test_distance_fingerprint() {
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+        ]);
+        // The same square rotated by 45 degrees and translated.
+        let h = std::f64::consts::FRAC_1_SQRT_2;
+        let moved_square = HashSet::from([
+            Point::new(5., 5.),
+            Point::new(5. + h, 5. + h),
+            Point::new(5., 5. + 2. * h),
+            Point::new(5. - h, 5. + h),
+        ]);
+        let rectangle = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(2., 0.),
+            Point::new(2., 1.),
+            Point::new(0., 1.),
+        ]);
+
+        let fp_square = distance_fingerprint(&square);
+        let fp_moved = distance_fingerprint(&moved_square);
+        let fp_rectangle = distance_fingerprint(&rectangle);
+
+        assert_eq!(fp_square.len(), 6);
+        assert!(fp_square.iter().zip(&fp_moved).all(|(a, b)| (a - b).abs() < 1e-9));
+        assert!(fp_square.iter().zip(&fp_rectangle).any(|(a, b)| (a - b).abs() > 1e-9));
+    }
+
+    /// Tests that the fingerprint-based early rejection flags a scalene triangle but not
+    /// symmetric or collinear sets.
+    #[test]
+    fn test_mirror_partners_possible() {
+        let scalene = [Point::new(0., 0.), Point::new(4., 0.), Point::new(1., 3.)];
+        assert!(!mirror_partners_possible(&scalene.iter().collect::<Vec<_>>(), config::EPSILON));
+
+        let kite = [Point::new(0., 0.), Point::new(1., 2.), Point::new(-1., 2.), Point::new(0., 5.)];
+        assert!(mirror_partners_possible(&kite.iter().collect::<Vec<_>>(), config::EPSILON));
+
+        let collinear = [Point::new(-2., -1.), Point::new(-1., -0.5), Point::new(0., 0.), Point::new(3., 1.5)];
+        assert!(mirror_partners_possible(&collinear.iter().collect::<Vec<_>>(), config::EPSILON));
+    }
+
+    /// Tests that the sets of the example binary, which all have lines of symmetry, pass the quick
+    /// check, and that a scalene triangle fails it.
+    #[test]
+    fn test_could_be_symmetric() {
+        let cases = [
+            HashSet::from([Point::new(1., 0.), Point::new(0., 1.), Point::new(2., 0.), Point::new(0., 2.)]),
+            HashSet::from([Point::new(1., 0.), Point::new(0., 1.), Point::new(2., 1.), Point::new(1., 2.)]),
+            HashSet::from([
+                Point::new(-2., -1.),
+                Point::new(-1., -0.5),
+                Point::new(0., 0.),
+                Point::new(3., 1.5),
+            ]),
+            HashSet::from([Point::new(0., 0.)]),
+        ];
+        for case in &cases {
+            assert!(could_be_symmetric(case));
+            let has_axis = match get_lines_of_sym_or_infinite(case) {
+                LinesOfSym::Finite(lines) => !lines.is_empty(),
+                LinesOfSym::Infinite => true,
+            };
+            assert!(has_axis);
+        }
+
+        let scalene = HashSet::from([Point::new(0., 0.), Point::new(4., 0.), Point::new(1., 3.)]);
+        assert!(!could_be_symmetric(&scalene));
+        assert!(get_lines_of_sym_or_empty(&scalene, Some(true)).is_empty());
+    }
+
+    /// Tests that the perpendicular bisector is equidistant from both endpoints and
+    /// perpendicular to the segment, and that coincident points have no bisector.
+    #[test]
+    fn test_perpendicular_bisector() {
+        let p1 = Point::new(1., 2.);
+        let p2 = Point::new(4., -3.);
+        let bisector = perpendicular_bisector(&p1, &p2).unwrap();
+
+        let distance = |p: &Point| (bisector.a * p.x + bisector.b * p.y + bisector.c).abs() / bisector.a.hypot(bisector.b);
+        assert!((distance(&p1) - distance(&p2)).abs() < 1e-9);
+        assert!(bisector.is_point_on_line(&Point::new(2.5, -0.5)));
+
+        // The normal (a, b) is parallel to the segment direction.
+        let (dx, dy) = (p2.x - p1.x, p2.y - p1.y);
+        assert!((bisector.a * dy - bisector.b * dx).abs() < 1e-9);
+
+        assert!(perpendicular_bisector(&p1, &Point::new(1., 2.)).is_none());
+    }
+
+    /// Tests that the candidate iterator yields one bisector per pair of points, plus the
+    /// through-line only when the points are collinear, also for a set with another hasher.
+    #[test]
+    fn test_candidate_axes() {
+        let n = 7;
+        let points: HashSet<Point> = (0..n).map(|i| Point::new(i as f64, (i * i) as f64)).collect();
+        assert_eq!(candidate_axes(&points).count(), n * (n - 1) / 2);
+        #[cfg(feature = "std")]
+        {
+            type Fixed = core::hash::BuildHasherDefault<std::collections::hash_map::DefaultHasher>;
+            let fixed: HashSet<Point, Fixed> = points.iter().copied().collect();
+            assert_eq!(candidate_axes(&fixed).count(), n * (n - 1) / 2);
+        }
+
+        let collinear: HashSet<Point> = (0..n).map(|i| Point::new(i as f64, 2. * i as f64)).collect();
+        let candidates: Vec<Line> = candidate_axes(&collinear).collect();
+        assert_eq!(candidates.len(), n * (n - 1) / 2 + 1);
+        assert!(collinear.iter().all(|p| candidates.last().unwrap().is_point_on_line(p)));
+
+        assert_eq!(candidate_axes(&HashSet::from([Point::new(0., 0.)])).count(), 0);
+    }
+
+    /// Tests that on a two-colored square, the edge bisectors are rejected because they
+    /// swap colors, while the diagonals are kept.
+    #[test]
+    fn test_labeled_symmetry_rejects_color_swap() {
+        let checkerboard = HashSet::from([
+            LabeledPoint::new(Point::new(0., 0.), "black"),
+            LabeledPoint::new(Point::new(1., 0.), "white"),
+            LabeledPoint::new(Point::new(1., 1.), "black"),
+            LabeledPoint::new(Point::new(0., 1.), "white"),
+        ]);
+
+        let lines = get_lines_of_sym_labeled(&checkerboard);
+        assert_eq!(lines.len(), 2);
+        for line in &lines {
+            // Both diagonals go through the center and a pair of like-colored corners.
+            assert!(line.is_point_on_line(&Point::new(0.5, 0.5)));
+            assert!(!line.is_point_on_line(&Point::new(0.5, 0.)));
+        }
+
+        let uniform: HashSet<LabeledPoint<u8>> =
+            checkerboard.iter().map(|lp| LabeledPoint::new(Point::new(lp.point.x, lp.point.y), 0)).collect();
+        assert_eq!(get_lines_of_sym_labeled(&uniform).len(), 4);
+    }
+
+    /// A matcher that only matches a reflection to a point with the same label as the reflected
+    /// point.
+    struct LabelMatcher<'l> {
+        labels: &'l HashMap<Point, &'static str>,
+    }
+
+    impl ReflectionMatcher for LabelMatcher<'_> {
+        fn matches<'a>(&self, point: &Point, reflected: &Point, set: &PointIndex<'a>) -> Option<&'a Point> {
+            let partner = set.get(reflected)?;
+            (self.labels.get(point) == self.labels.get(partner)).then_some(partner)
+        }
+    }
+
+    /// Tests that a label-aware matcher finds the same lines as the labeled search, and that the
+    /// default matcher finds the same lines as `get_lines_of_sym`.
+    #[test]
+    fn test_custom_reflection_matcher() {
+        let labels: HashMap<Point, &str> = [
+            (Point::new(0., 0.), "black"),
+            (Point::new(1., 0.), "white"),
+            (Point::new(1., 1.), "black"),
+            (Point::new(0., 1.), "white"),
+        ]
+        .into_iter()
+        .collect();
+        let square: HashSet<Point> = labels.keys().copied().collect();
+
+        let lines: HashSet<Line> = get_lines_of_sym_with_matcher(&square, &LabelMatcher { labels: &labels });
+        let labeled: HashSet<LabeledPoint<&str>> =
+            labels.iter().map(|(p, l)| LabeledPoint::new(*p, *l)).collect();
+        assert_eq!(lines, get_lines_of_sym_labeled(&labeled));
+        assert_eq!(lines.len(), 2);
+
+        let unlabeled: HashSet<Line> = get_lines_of_sym_with_matcher(&square, &ToleranceMatcher);
+        assert_eq!(unlabeled, get_lines_of_sym(&square, None).unwrap());
+        assert_eq!(unlabeled.len(), 4);
+
+        let collinear = HashSet::from([Point::new(0., 0.), Point::new(1., 1.), Point::new(2., 2.)]);
+        let lines: HashSet<Line> = get_lines_of_sym_with_matcher(&collinear, &ToleranceMatcher);
+        assert_eq!(lines, get_lines_of_sym(&collinear, None).unwrap());
+    }
+
+    /// Tests that a point a third of the tolerance from a registered one, across the edge of its
+    /// cell, is found as that point, which an exact hash lookup misses.
+    #[test]
+    fn test_quantized_index() {
+        // Just below the edge of its cell in x.
+        let registered = Point::new(5. * config::EPSILON - 1e-4 * config::EPSILON, -2.);
+        let points = [Point::new(0., 0.), registered, Point::new(1., 1.)];
+        let points_vec: Vec<&Point> = points.iter().collect();
+        let index: QuantizedIndex<f64> = QuantizedIndex::new(&points_vec, config::EPSILON);
+
+        let drifted = Point::new(registered.x + config::EPSILON / 3., registered.y);
+        assert_ne!(index.cell(&drifted), index.cell(&registered));
+        let exact: HashSet<Point> = points.into_iter().collect();
+        assert!(!exact.contains(&drifted));
+        assert_eq!(index.get(&points_vec, &drifted), Some(1));
+        assert_eq!(index.get(&points_vec, &registered), Some(1));
+        assert_eq!(index.get(&points_vec, &Point::new(registered.x + 2. * config::EPSILON, -2.)), None);
+        assert_eq!(index.get(&points_vec, &Point::new(f64::MAX, 0.)), None);
+    }
+
+    /// Tests that the reflection of a corner of a slightly skewed square is in the index under a
+    /// loose tolerance but not a tight one, and finding the nearest point to it.
+    #[test]
+    fn test_point_index_tolerance() {
+        let skewed = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.01),
+        ]);
+        let index = PointIndex::new(&skewed);
+        let reflected = Line::new(1., 0., -0.5).get_reflected_point(&Point::new(1., 1.));
+        assert!(index.get(&reflected).is_none());
+        assert!(index.contains_within(&reflected, 0.02));
+        assert!(!index.contains_within(&reflected, 0.005));
+
+        let (nearest, distance) = index.nearest(&reflected).unwrap();
+        assert_eq!(*nearest, Point::new(0., 1.01));
+        assert!((distance - 0.01).abs() < 1e-12);
+        assert_eq!(index.nearest(&Point::new(0.6, -5.)).unwrap().0, &Point::new(1., 0.));
+
+        let empty: HashSet<Point> = HashSet::new();
+        assert!(PointIndex::new(&empty).nearest(&reflected).is_none());
+    }
+
+    /// Tests that an otherwise-symmetric layout with mismatched weights has no axis, and
+    /// that matching weights restore it.
+    #[test]
+    fn test_weighted_symmetry_requires_matching_weights() {
+        let triangle = HashSet::from([
+            WeightedPoint::new(Point::new(-1., 0.), 2.0),
+            WeightedPoint::new(Point::new(1., 0.), 3.0),
+            WeightedPoint::new(Point::new(0., 2.), 1.0),
+        ]);
+        assert!(get_lines_of_sym_weighted(&triangle).is_empty());
+
+        let balanced = HashSet::from([
+            WeightedPoint::new(Point::new(-1., 0.), 2.0),
+            WeightedPoint::new(Point::new(1., 0.), 2.0 + config::EPSILON / 10.0),
+            WeightedPoint::new(Point::new(0., 2.), 1.0),
+        ]);
+        let lines = get_lines_of_sym_weighted(&balanced);
+        assert_eq!(lines.len(), 1);
+        assert!(lines.iter().next().unwrap().is_point_on_line(&Point::new(0., 7.)));
+    }
 }
@@ -0,0 +1,127 @@
+use crate::collections::HashMap;
+use crate::config::Scalar;
+use crate::model::{Line, Point};
+
+/// The bit patterns of a point's coordinates and a line's coefficients.
+type Key = ([u64; 2], [u64; 3]);
+
+/// A memo of reflections of points across lines, reusable across searches.
+///
+/// Pass a cache to `alg::get_lines_of_sym_cached` when running detection on many overlapping
+/// point sets, e.g. sliding windows over a larger set: pairs of points shared between the sets
+/// generate the same candidate lines, so their reflections are looked up rather than recomputed.
+///
+/// Entries are keyed on the exact bit patterns of the point's coordinates and the line's
+/// coefficients, so lookups never confuse distinct lines. The cache is never evicted: every
+/// reflection a search computes stays in memory until `clear` is called, which for a search over
+/// `n` points can approach one entry per candidate line and point. A cache only pays off when
+/// searches overlap; for unrelated inputs, it costs memory and hashing for no hits.
+#[derive(Debug)]
+pub struct ReflectionCache<T = f64> {
+    reflections: HashMap<Key, Option<Point<T>>>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<T: Scalar> ReflectionCache<T> {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self {
+            reflections: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns the reflection of `p` across `line` as given by `Line::try_reflected_point`,
+    /// computing and storing it if it isn't cached.
+    pub fn try_reflected_point(&mut self, line: &Line<T>, p: &Point<T>) -> Option<Point<T>> {
+        let key: Key = ([bits(p.x), bits(p.y)], [bits(line.a), bits(line.b), bits(line.c)]);
+
+        if let Some(reflection) = self.reflections.get(&key) {
+            self.hits += 1;
+            return *reflection;
+        }
+
+        self.misses += 1;
+        let reflection = line.try_reflected_point(p);
+        self.reflections.insert(key, reflection);
+        reflection
+    }
+
+    /// Returns the number of lookups answered from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Returns the number of lookups that had to compute the reflection.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Returns the number of cached reflections.
+    pub fn len(&self) -> usize {
+        self.reflections.len()
+    }
+
+    /// Returns true if no reflections are cached.
+    pub fn is_empty(&self) -> bool {
+        self.reflections.is_empty()
+    }
+
+    /// Removes all cached reflections and resets the hit and miss counts.
+    pub fn clear(&mut self) {
+        self.reflections.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+}
+
+impl<T: Scalar> Default for ReflectionCache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the bit pattern of a coordinate, widened to `f64`.
+fn bits<T: Scalar>(v: T) -> u64 {
+    v.to_f64().unwrap().to_bits()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alg::get_lines_of_sym_cached;
+    use crate::collections::HashSet;
+    use crate::config::SymmetryOptions;
+
+    /// Tests that a second identical search is answered entirely from the cache. The set is a
+    /// rectangle, as a regular polygon's axes are found without reflecting points across candidates.
+    #[test]
+    fn test_cache_hits_on_repeated_call() {
+        let rectangle = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(2., 0.),
+            Point::new(2., 1.),
+            Point::new(0., 1.),
+        ]);
+        let opts = SymmetryOptions::default();
+        let mut cache = ReflectionCache::new();
+
+        let first = get_lines_of_sym_cached(&rectangle, &opts, &mut cache);
+        assert_eq!(first.lines.len(), 2);
+        assert_eq!(cache.hits(), 0);
+        let misses = cache.misses();
+        assert!(misses > 0);
+        assert_eq!(cache.len(), misses);
+
+        let second = get_lines_of_sym_cached(&rectangle, &opts, &mut cache);
+        assert_eq!(second.lines, first.lines);
+        assert_eq!(cache.misses(), misses);
+        assert_eq!(cache.hits(), misses);
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.hits(), 0);
+    }
+}
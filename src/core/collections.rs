@@ -0,0 +1,14 @@
+//! The hash-based collections used by the public API.
+//!
+//! These are the `std::collections` types, or their `hashbrown` equivalents when the crate is
+//! built without the `std` feature.
+
+#[cfg(feature = "std")]
+pub use std::collections::{HashMap, HashSet};
+
+/// The hasher used by `HashSet` and `HashMap` unless another is given.
+#[cfg(feature = "std")]
+pub use std::collections::hash_map::RandomState as DefaultHashBuilder;
+
+#[cfg(not(feature = "std"))]
+pub use hashbrown::{DefaultHashBuilder, HashMap, HashSet};
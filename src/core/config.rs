@@ -1 +1,280 @@
-pub const EPSILON: f64 = 1e-9;
+use crate::model::Point;
+use num_traits::Float;
+use alloc::sync::Arc;
+use core::fmt::Debug;
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// The default tolerance for `f64` coordinates: `1e-9`, unless the `SYMM_EPSILON` environment
+/// variable is set when the crate is built, e.g. to `1e-6` for noisy sensor data, so that embedded
+/// users can bake in another default without passing one at runtime. A value that isn't a positive
+/// decimal number, such as `0.001` or `2.5e-7`, fails the build.
+///
+/// This is only the default: `SymmetryOptions::epsilon`, where given, overrides it for a search.
+/// `f32` coordinates use `EPSILON_F32` whether or not `SYMM_EPSILON` is set.
+pub const EPSILON: f64 = match option_env!("SYMM_EPSILON") {
+    Some(value) => parse_epsilon(value),
+    None => 1e-9,
+};
+
+/// Parses the value of `SYMM_EPSILON` at compile time: decimal digits with an optional fraction
+/// and an optional exponent. Panics, failing the build, if the value is malformed or not positive.
+const fn parse_epsilon(value: &str) -> f64 {
+    let bytes = value.as_bytes();
+    let mut i = 0;
+    let mut mantissa = 0.0;
+    let mut digits = 0;
+    let mut exponent: i32 = 0;
+
+    while i < bytes.len() && bytes[i].is_ascii_digit() {
+        mantissa = mantissa * 10.0 + (bytes[i] - b'0') as f64;
+        digits += 1;
+        i += 1;
+    }
+    if i < bytes.len() && bytes[i] == b'.' {
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            mantissa = mantissa * 10.0 + (bytes[i] - b'0') as f64;
+            digits += 1;
+            exponent -= 1;
+            i += 1;
+        }
+    }
+    if i < bytes.len() && (bytes[i] == b'e' || bytes[i] == b'E') {
+        i += 1;
+        let negative = i < bytes.len() && bytes[i] == b'-';
+        if i < bytes.len() && (bytes[i] == b'-' || bytes[i] == b'+') {
+            i += 1;
+        }
+        let start = i;
+        let mut written: i32 = 0;
+        while i < bytes.len() && bytes[i].is_ascii_digit() && written < 1000 {
+            written = written * 10 + (bytes[i] - b'0') as i32;
+            i += 1;
+        }
+        assert!(i > start, "SYMM_EPSILON has an exponent without digits");
+        exponent += if negative { -written } else { written };
+    }
+    assert!(digits > 0 && i == bytes.len(), "SYMM_EPSILON is not a decimal number");
+
+    // Powers of ten up to 1e22 are exact, so a single division rounds the usual values correctly.
+    let mut power = 1.0;
+    let mut remaining = exponent.unsigned_abs();
+    while remaining > 0 {
+        power *= 10.0;
+        remaining -= 1;
+    }
+    if exponent < 0 {
+        mantissa /= power;
+    } else {
+        mantissa *= power;
+    }
+    assert!(mantissa > 0.0 && mantissa.is_finite(), "SYMM_EPSILON must be positive and finite");
+    mantissa
+}
+
+/// Returns the default tolerance for comparing values of type `T`: `EPSILON` for `f64` and
+/// `EPSILON_F32` for `f32`.
+///
+/// Searches use this unless `SymmetryOptions::epsilon` overrides it for a single call. The
+/// searches of `alg::get_lines_of_sym_with` apply it to the points scaled to an extent near 1.
+pub fn default_epsilon<T: Scalar>() -> T {
+    T::TOLERANCE
+}
+
+/// The tolerance used for `f32` coordinates, whose precision is far coarser than `EPSILON`.
+///
+/// `SYMM_EPSILON` doesn't change it: the override only applies to `f64` coordinates.
+pub const EPSILON_F32: f32 = 1e-5;
+
+/// A floating-point type usable as a coordinate, together with the tolerance used to
+/// compare values of that type.
+pub trait Scalar: Float + Debug {
+    /// The tolerance used for approximate comparisons.
+    const TOLERANCE: Self;
+}
+
+impl Scalar for f64 {
+    const TOLERANCE: f64 = EPSILON;
+}
+
+impl Scalar for f32 {
+    const TOLERANCE: f32 = EPSILON_F32;
+}
+
+/// How a point's reflection is matched to a point of the set during a search.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PointEquality {
+    /// Coordinates are compared independently within the scalar's `TOLERANCE`, as by `Point`'s
+    /// `==`, so the tolerance is a box around each point.
+    #[default]
+    Box,
+    /// Points are equal if at most the given distance apart, as by `Point::approx_eq_radial`, so
+    /// the tolerance is a disk around each point.
+    Radial(f64),
+}
+
+/// Options controlling a run of `alg::get_lines_of_sym_with`.
+#[derive(Debug, Clone)]
+pub struct SymmetryOptions {
+    /// Whether the input set is expected to have a high degree of partial symmetry.
+    /// See `alg::get_lines_of_sym`. Defaults to `true`.
+    pub high_degree_expected: bool,
+    /// A flag that, once set, makes the search stop at the next candidate line.
+    pub cancel_flag: Option<Arc<AtomicBool>>,
+    /// A point in time after which the search stops at the next candidate line.
+    /// Not available without the `std` feature.
+    #[cfg(feature = "std")]
+    pub deadline: Option<Instant>,
+    /// If set, near-duplicate lines are merged with `alg::cluster_lines` at this tolerance
+    /// before the result is returned. Defaults to `None`.
+    pub cluster_tolerance: Option<f64>,
+    /// Whether to skip tracking which pairs of points are covered by earlier candidate lines.
+    ///
+    /// Candidate pairs are always generated lazily, but tracking covered pairs takes one bit per
+    /// pair, i.e. `O(n²)` memory. With this set, memory beyond the input is `O(n)` and pairs are
+    /// instead checked again, which costs more time on sets with many partial symmetries.
+    /// Defaults to `false`.
+    pub low_memory: bool,
+    /// Whether the line through a set of collinear points, which maps every point onto itself,
+    /// is reported as a line of symmetry. With this unset, only axes that swap points are
+    /// reported. Defaults to `true`.
+    pub include_through_line: bool,
+    /// Whether to skip candidate lines that miss the centroid of the points before checking them.
+    /// Every line of symmetry passes through the centroid, so this only affects speed, but a
+    /// skipped candidate isn't used to skip later pairs of points either. The centroid is compared
+    /// within the tolerance of the search widened by its rounding, which grows with the magnitude
+    /// and the number of the points, so that a true axis isn't skipped. Defaults to `false`.
+    pub prune_by_centroid: bool,
+    /// Whether to skip candidate lines across which a point on the bounding box of the points
+    /// reflects outside the box, before checking all the points. Like `prune_by_centroid`, this
+    /// only affects speed, with the same widened tolerance, and defaults to `false`.
+    pub prune_by_bounding_box: bool,
+    /// How reflections are matched to points when checking candidate lines generated from pairs
+    /// of points, and when verifying the lines found. The axes of collinear inputs are checked
+    /// with the default `PointEquality::Box`. Defaults to `PointEquality::Box`.
+    pub point_equality: PointEquality,
+    /// The tolerance used for this search in place of `default_epsilon`: for matching reflections
+    /// to points under `PointEquality::Box`, and for checking whether points are on a line, e.g.
+    /// whether they are collinear. It is in the units of the input, like the tolerance of
+    /// `PointEquality::Radial`. Defaults to `None`, i.e. `default_epsilon`.
+    ///
+    /// The searches configured by these options honour it: `alg::get_lines_of_sym_with` and the
+    /// entry points built on it, such as `Detector`, `alg::axes_iter` and
+    /// `alg::Workspace::detect_into`, as well as the labeled and weighted searches. Functions that
+    /// take no options, such as `alg::verify_axis`, `alg::axes_through_center`,
+    /// `alg::are_collinear`, `alg::could_be_symmetric`, `alg::input_shape_kind`,
+    /// `alg::get_lines_of_sym_par` and `alg::get_lines_of_sym_threaded`, compare at
+    /// `default_epsilon`, and each has a `_tol` variant taking the tolerance explicitly.
+    pub epsilon: Option<f64>,
+    /// If set, points closer than this distance, in the input's units, are merged into their mean
+    /// before the search, so that noise splitting a point into near-duplicates doesn't leave them
+    /// without mirror partners. Points are merged by single linkage, i.e. also along chains of
+    /// close points, which takes `O(n²)` time. Unlike snapping to a grid, this leaves points that
+    /// are far enough apart unchanged. Applies to `alg::get_lines_of_sym_with` and
+    /// `alg::get_lines_of_sym_with_stats`. Defaults to `None`.
+    pub min_feature_size: Option<f64>,
+    /// If set, the points inside this box, given by its lower-left and upper-right corners as
+    /// returned by `geometry::bounding_box`, are left out of the search, e.g. an asymmetric cutout
+    /// in an otherwise symmetric part. Points on the boundary of the box are left out too, and the
+    /// axes found need only be axes of the remaining points. Applies to the same searches as
+    /// `min_feature_size`, before it. Defaults to `None`.
+    pub keep_out_region: Option<(Point, Point)>,
+    /// If set, candidate lines are generated only from the pairs of each point with this many of
+    /// its nearest neighbours and with the point nearest to its reflection through the centroid,
+    /// instead of from all pairs, for inputs too large for the `O(n²)` pairs, e.g. 100k points.
+    ///
+    /// This is approximate: an axis is found only if it swaps some point with one of these
+    /// partners. For well-behaved shapes, such as regular polygons or dense outlines, a few
+    /// neighbours suffice, since each axis swaps points that are close to each other where it
+    /// crosses the shape. Covered pairs are tracked only among these partners, and the quick
+    /// check that every point can have a mirror partner, which compares all pairs of points, is
+    /// skipped, so memory stays `O(n)` beyond the input. Defaults to `None`.
+    pub candidate_neighbours: Option<usize>,
+    /// Whether to leave out the warning printed to stderr when fewer than two points are given,
+    /// for embedders that can't have a library write to stderr. The search returns no lines either
+    /// way. Defaults to `false`.
+    pub quiet: bool,
+    /// Whether to report only the axes that at least one of the points lies on, e.g. a vertex of
+    /// a polygon, for definitions of reflective symmetry that expect such a point. Points are on an
+    /// axis within the tolerance of the search, as by `Line::is_point_on_line_tol`. Every axis of
+    /// a set of an odd number of points passes through one of them, so this only drops axes of
+    /// even sets. Applies to the same searches as `min_feature_size`. Defaults to `false`.
+    pub require_axis_point: bool,
+    /// The seed of the random number generator used by randomized steps of a search, e.g. the
+    /// smallest enclosing circle by which regular polygons are recognised, so that equal sets give
+    /// bit-identical results in every run. With `None`, those steps follow the iteration order of
+    /// the set, which differs between runs. Defaults to `None`.
+    pub seed: Option<u64>,
+}
+
+impl SymmetryOptions {
+    /// Returns true if the cancellation flag is set or the deadline has passed.
+    pub fn is_cancelled(&self) -> bool {
+        if let Some(flag) = &self.cancel_flag {
+            if flag.load(Ordering::Relaxed) {
+                return true;
+            }
+        }
+
+        #[cfg(feature = "std")]
+        if matches!(self.deadline, Some(deadline) if Instant::now() >= deadline) {
+            return true;
+        }
+
+        false
+    }
+}
+
+impl Default for SymmetryOptions {
+    fn default() -> Self {
+        Self {
+            high_degree_expected: true,
+            cancel_flag: None,
+            #[cfg(feature = "std")]
+            deadline: None,
+            cluster_tolerance: None,
+            low_memory: false,
+            include_through_line: true,
+            prune_by_centroid: false,
+            prune_by_bounding_box: false,
+            point_equality: PointEquality::Box,
+            epsilon: None,
+            min_feature_size: None,
+            keep_out_region: None,
+            candidate_neighbours: None,
+            quiet: false,
+            require_axis_point: false,
+            seed: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that the tolerance set at build time, if any, is the default one, used by `==` on
+    /// points when no tolerance is given at runtime.
+    #[test]
+    fn test_default_epsilon() {
+        let expected = option_env!("SYMM_EPSILON").map_or(1e-9, |value| value.parse().unwrap());
+        assert!((EPSILON - expected).abs() <= expected * 1e-12);
+        assert_eq!(default_epsilon::<f64>(), EPSILON);
+        assert_eq!(SymmetryOptions::default().epsilon, None);
+
+        let origin = Point::new(0., 0.);
+        assert_eq!(origin, Point::new(EPSILON / 2., 0.));
+        assert_ne!(origin, Point::new(EPSILON * 2., 0.));
+    }
+
+    /// Tests the values `SYMM_EPSILON` can take.
+    #[test]
+    fn test_parse_epsilon() {
+        assert_eq!(parse_epsilon("1e-6"), 1e-6);
+        assert_eq!(parse_epsilon("0.001"), 0.001);
+        assert!((parse_epsilon("2.5E-7") - 2.5e-7).abs() < 1e-20);
+        assert_eq!(parse_epsilon("3e+0"), 3.);
+    }
+}
@@ -0,0 +1,188 @@
+//! Reading point sets from CSV text, one point per row.
+//!
+//! Each row holds `x,y` and optionally a third column with the point's label, e.g. a color or
+//! type, for `alg::get_lines_of_sym_labeled`. Fields are trimmed of surrounding whitespace. Blank
+//! rows and rows starting with `#` are skipped, and a first row neither of whose coordinates is a
+//! number is taken to be a header. A first row with one numeric coordinate is malformed data.
+
+use crate::alg;
+use crate::config::SymmetryOptions;
+use crate::error::SymmError;
+use crate::model::{LabeledPoint, Line, Point};
+use std::collections::HashSet;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// Parses labeled points from CSV text.
+///
+/// Rows with and without the label column may be mixed; a row without one, or with an empty one,
+/// gets the empty label. Returns `SymmError::Parse` naming the first malformed row, or
+/// `SymmError::NonFinitePoint` if a coordinate is NaN or infinite.
+pub fn parse_labeled_points(text: &str) -> Result<HashSet<LabeledPoint<String>>, SymmError> {
+    let mut points = HashSet::new();
+    let mut first_row = true;
+
+    for (index, row) in text.lines().enumerate() {
+        let row = row.trim();
+        if row.is_empty() || row.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = row.split(',').map(str::trim).collect();
+        if fields.len() < 2 || fields.len() > 3 {
+            return Err(SymmError::Parse(format!("line {}: expected 2 or 3 columns", index + 1)));
+        }
+
+        let (x, y) = match (fields[0].parse::<f64>(), fields[1].parse::<f64>()) {
+            (Ok(x), Ok(y)) => (x, y),
+            (Err(_), Err(_)) if first_row => {
+                first_row = false;
+                continue;
+            }
+            _ => return Err(SymmError::Parse(format!("line {}: invalid coordinates", index + 1))),
+        };
+        first_row = false;
+
+        let label = fields.get(2).copied().unwrap_or_default();
+        points.insert(LabeledPoint::new(Point::from_array([x, y])?, label.to_string()));
+    }
+
+    Ok(points)
+}
+
+/// Parses points from CSV text like `parse_labeled_points`, ignoring any labels.
+pub fn parse_points(text: &str) -> Result<HashSet<Point>, SymmError> {
+    Ok(parse_labeled_points(text)?.into_iter().map(|lp| lp.point).collect())
+}
+
+/// Reads labeled points from the CSV file at `path`. See `parse_labeled_points`.
+///
+/// Returns `SymmError::Io` if the file can't be read.
+pub fn read_labeled_points<P: AsRef<Path>>(path: P) -> Result<HashSet<LabeledPoint<String>>, SymmError> {
+    parse_labeled_points(&std::fs::read_to_string(path)?)
+}
+
+/// Reads points from the CSV file at `path`, ignoring any labels. See `parse_labeled_points`.
+pub fn read_points<P: AsRef<Path>>(path: P) -> Result<HashSet<Point>, SymmError> {
+    parse_points(&std::fs::read_to_string(path)?)
+}
+
+/// Reads points as CSV from `reader`, finds their lines of symmetry as configured by `opts`, and
+/// writes the lines to `writer` as CSV, returning the number of lines.
+///
+/// The points are read as by `parse_points`. The output has a header row `a,b,c`, then one row
+/// per line with its coefficients, in order of the lines' orientation. Returns `SymmError::Io` if
+/// reading or writing fails, or the error of `parse_labeled_points` if the input is malformed.
+pub fn axes_from_csv<R: Read, W: Write>(
+    mut reader: R,
+    mut writer: W,
+    opts: &SymmetryOptions,
+) -> Result<usize, SymmError> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let points = parse_points(&text)?;
+
+    let mut lines: Vec<Line> = alg::get_lines_of_sym_with(&points, opts).lines.into_iter().collect();
+    lines.sort_by(|l1, l2| l1.angle_rad().total_cmp(&l2.angle_rad()).then(l1.c.total_cmp(&l2.c)));
+
+    writeln!(writer, "a,b,c")?;
+    for line in &lines {
+        writeln!(writer, "{},{},{}", line.a, line.b, line.c)?;
+    }
+    writer.flush()?;
+
+    Ok(lines.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alg::get_lines_of_sym_labeled;
+    use crate::model::Line;
+
+    /// Tests parsing a file that mixes rows with and without labels, and feeding the result to the
+    /// labeled search.
+    #[test]
+    fn test_parse_mixed_rows() {
+        let text = "x,y,label\n\
+                    0,0,black\n\
+                    1, 1 , black\n\
+                    \n\
+                    # the unlabeled corners\n\
+                    1,0\n\
+                    0,1,\n";
+        let points = parse_labeled_points(text).unwrap();
+
+        let expected = HashSet::from([
+            LabeledPoint::new(Point::new(0., 0.), String::from("black")),
+            LabeledPoint::new(Point::new(1., 1.), String::from("black")),
+            LabeledPoint::new(Point::new(1., 0.), String::new()),
+            LabeledPoint::new(Point::new(0., 1.), String::new()),
+        ]);
+        assert_eq!(points, expected);
+
+        // Only the diagonals of the square map each label onto an equal label.
+        let lines = get_lines_of_sym_labeled(&points);
+        assert_eq!(lines, HashSet::from([Line::new(1., -1., 0.), Line::new(1., 1., -1.)]));
+        assert_eq!(parse_points(text).unwrap().len(), 4);
+    }
+
+    /// Tests piping a square from an in-memory CSV to an in-memory buffer of axes.
+    #[test]
+    fn test_axes_from_csv() {
+        let input = "x,y\n0,0\n2,0\n2,2\n0,2\n";
+        let mut output: Vec<u8> = Vec::new();
+        let count = axes_from_csv(input.as_bytes(), &mut output, &SymmetryOptions::default()).unwrap();
+        assert_eq!(count, 4);
+
+        let output = String::from_utf8(output).unwrap();
+        let mut rows = output.lines();
+        assert_eq!(rows.next(), Some("a,b,c"));
+        let axes: Vec<Line> = rows
+            .map(|row| {
+                let coefficients: Vec<f64> = row.split(',').map(|c| c.parse().unwrap()).collect();
+                Line::new(coefficients[0], coefficients[1], coefficients[2])
+            })
+            .collect();
+        assert_eq!(axes.len(), 4);
+        let expected = [
+            Line::new(1., 0., -1.),
+            Line::new(0., 1., -1.),
+            Line::new(1., -1., 0.),
+            Line::new(1., 1., -2.),
+        ];
+        assert!(expected.iter().all(|axis| axes.contains(axis)));
+
+        let malformed = axes_from_csv("0,0\n1\n".as_bytes(), Vec::new(), &SymmetryOptions::default());
+        assert!(matches!(malformed, Err(SymmError::Parse(_))));
+    }
+
+    /// Tests that a header is recognised after blank and comment rows, that only a first row is
+    /// taken for one, and that a first row with a numeric coordinate is reported as malformed.
+    #[test]
+    fn test_header_row() {
+        let points = parse_points("# a square\n\nx,y\n0,0\n1,1\n").unwrap();
+        assert_eq!(points, HashSet::from([Point::new(0., 0.), Point::new(1., 1.)]));
+        assert!(parse_points("x,y\n").unwrap().is_empty());
+
+        let second_header = parse_points("x,y\nx,y\n");
+        assert!(matches!(second_header, Err(SymmError::Parse(reason)) if reason == "line 2: invalid coordinates"));
+        let half_numeric = parse_points("1,y\n0,0\n");
+        assert!(matches!(half_numeric, Err(SymmError::Parse(reason)) if reason == "line 1: invalid coordinates"));
+    }
+
+    /// Tests that malformed rows are reported with their line number.
+    #[test]
+    fn test_parse_errors() {
+        let too_many = parse_labeled_points("0,0\n1,2,a,b\n");
+        let expected = "line 2: expected 2 or 3 columns";
+        assert!(matches!(too_many, Err(SymmError::Parse(reason)) if reason == expected));
+
+        let not_a_number = parse_labeled_points("0,0\n1,y\n");
+        let expected = "line 2: invalid coordinates";
+        assert!(matches!(not_a_number, Err(SymmError::Parse(reason)) if reason == expected));
+
+        assert!(matches!(parse_points("0,inf\n"), Err(SymmError::NonFinitePoint)));
+        assert!(matches!(read_points("/nonexistent/points.csv"), Err(SymmError::Io(_))));
+    }
+}
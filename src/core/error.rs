@@ -0,0 +1,135 @@
+//! The crate's error type, returned by its fallible APIs.
+
+use alloc::string::String;
+use core::fmt;
+
+/// The cause of a failed read or write, as reported by the standard library.
+#[cfg(feature = "std")]
+pub type IoError = std::io::Error;
+
+/// The cause of a failed read or write: without the `std` feature there is no `std::io::Error`,
+/// so a description of the failure.
+#[cfg(not(feature = "std"))]
+pub type IoError = String;
+
+/// An error raised by the crate's fallible APIs.
+///
+/// Errors compare equal if they are the same variant with equal data, except that `Io` errors
+/// compare by `std::io::Error::kind`, as `std::io::Error` itself isn't comparable.
+#[derive(Debug)]
+pub enum SymmError {
+    /// A point coordinate was NaN or infinite.
+    NonFinitePoint,
+    /// A line's normal `(a, b)` is (near) zero, so it has no direction (see `Line::is_valid`).
+    DegenerateLine,
+    /// A line coefficient was NaN or infinite (see `Line::try_new`).
+    NonFiniteLine,
+    /// Fewer than two points were given, which can't define a line of symmetry.
+    TooFewPoints,
+    /// All points coincide within floating-point tolerance, so every line through them is a line
+    /// of symmetry (see `alg::get_lines_of_sym_or_infinite`).
+    DegenerateInput,
+    /// A point equals a point given earlier within floating-point tolerance, and duplicates are
+    /// rejected (see `model::PointSetBuilder::reject_duplicates`).
+    DuplicatePoint,
+    /// Reading or writing point data failed.
+    Io(IoError),
+    /// Point data couldn't be parsed, for the given reason.
+    Parse(String),
+}
+
+impl fmt::Display for SymmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SymmError::NonFinitePoint => write!(f, "Point coordinates must be finite and non-NaN"),
+            SymmError::DegenerateLine => write!(f, "Invalid line: (a, b) must not be (near) zero"),
+            SymmError::NonFiniteLine => write!(f, "Line coefficients must be finite and non-NaN"),
+            SymmError::TooFewPoints => write!(f, "At least 2 points needed to find lines of symmetry"),
+            SymmError::DegenerateInput => {
+                write!(f, "All points coincide, so every line through them is a line of symmetry")
+            }
+            SymmError::DuplicatePoint => write!(f, "Duplicate point: it equals an earlier point"),
+            SymmError::Io(e) => write!(f, "I/O error: {}", e),
+            SymmError::Parse(reason) => write!(f, "Parse error: {}", reason),
+        }
+    }
+}
+
+impl core::error::Error for SymmError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            SymmError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for SymmError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            #[cfg(feature = "std")]
+            (SymmError::Io(e1), SymmError::Io(e2)) => e1.kind() == e2.kind(),
+            #[cfg(not(feature = "std"))]
+            (SymmError::Io(e1), SymmError::Io(e2)) => e1 == e2,
+            (SymmError::Parse(r1), SymmError::Parse(r2)) => r1 == r2,
+            _ => core::mem::discriminant(self) == core::mem::discriminant(other),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for SymmError {
+    fn from(e: std::io::Error) -> Self {
+        SymmError::Io(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    /// Tests that each variant formats a distinct message.
+    #[test]
+    fn test_display_is_distinct() {
+        let errors = [
+            SymmError::NonFinitePoint,
+            SymmError::DegenerateLine,
+            SymmError::NonFiniteLine,
+            SymmError::TooFewPoints,
+            SymmError::DegenerateInput,
+            SymmError::DuplicatePoint,
+            #[cfg(feature = "std")]
+            SymmError::from(std::io::Error::new(std::io::ErrorKind::NotFound, "points.csv")),
+            #[cfg(not(feature = "std"))]
+            SymmError::Io(String::from("points.csv: not found")),
+            SymmError::Parse(String::from("line 3: expected 2 columns")),
+        ];
+        let mut messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert!(messages.iter().all(|m| !m.is_empty()));
+
+        messages.sort();
+        messages.dedup();
+        assert_eq!(messages.len(), errors.len());
+    }
+
+    /// Tests that errors compare by variant and data, with I/O errors compared by kind.
+    #[test]
+    fn test_partial_eq() {
+        assert_eq!(SymmError::TooFewPoints, SymmError::TooFewPoints);
+        assert_ne!(SymmError::TooFewPoints, SymmError::DegenerateInput);
+        assert_eq!(SymmError::Parse(String::from("a")), SymmError::Parse(String::from("a")));
+        assert_ne!(SymmError::Parse(String::from("a")), SymmError::Parse(String::from("b")));
+
+        #[cfg(feature = "std")]
+        {
+            use std::io::{Error, ErrorKind};
+            let not_found = |path: &str| SymmError::from(Error::new(ErrorKind::NotFound, path));
+            assert_eq!(not_found("a.csv"), not_found("b.csv"));
+            assert_ne!(not_found("a.csv"), SymmError::from(Error::new(ErrorKind::PermissionDenied, "a.csv")));
+            assert_ne!(not_found("a.csv"), SymmError::Parse(String::from("a.csv")));
+        }
+    }
+}
@@ -0,0 +1,294 @@
+//! Export of point sets and lines of symmetry to formats understood by other tools.
+
+use crate::alg;
+use crate::config::SymmetryOptions;
+use crate::error::SymmError;
+use crate::geometry::{axis_endpoints, bounding_box};
+use crate::model::{Line, Point};
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Returns a GeoJSON `FeatureCollection` holding the points and the lines of symmetry.
+///
+/// Each point becomes a `Point` feature. Each line becomes a `LineString` feature clipped to the
+/// bounding box of the points, with the line's coefficients as the `a`, `b` and `c` properties.
+/// Lines that miss the bounding box are left out. Requires the `serde` feature.
+#[cfg(feature = "serde")]
+pub fn to_geojson(points: &HashSet<Point>, lines: &HashSet<Line>) -> String {
+    use serde_json::{json, Value};
+
+    let mut features: Vec<Value> = points
+        .iter()
+        .map(|p| {
+            json!({
+                "type": "Feature",
+                "geometry": { "type": "Point", "coordinates": [p.x, p.y] },
+                "properties": {},
+            })
+        })
+        .collect();
+
+    if let Some(bbox) = bounding_box(points) {
+        for line in lines {
+            if let Some((start, end)) = axis_endpoints(line, bbox) {
+                features.push(json!({
+                    "type": "Feature",
+                    "geometry": {
+                        "type": "LineString",
+                        "coordinates": [[start.x, start.y], [end.x, end.y]],
+                    },
+                    "properties": { "a": line.a, "b": line.b, "c": line.c },
+                }));
+            }
+        }
+    }
+
+    json!({ "type": "FeatureCollection", "features": features }).to_string()
+}
+
+/// Returns a Graphviz DOT graph of how `line` pairs up the points: one node per point, labeled
+/// with its coordinates, and an edge between each pair of mirror partners across the line.
+///
+/// Points on the line, which are their own partners, are drawn as double circles, and points
+/// without a partner, which keep `line` from being a line of symmetry, are drawn in red. The
+/// nodes are numbered in order of the points by x, then y, so the output is deterministic. Render
+/// it with e.g. `dot -Tsvg`.
+pub fn reflection_graph_dot(points: &HashSet<Point>, line: &Line) -> String {
+    let mut sorted: Vec<&Point> = points.iter().collect();
+    sorted.sort_by(|p, q| p.x.total_cmp(&q.x).then(p.y.total_cmp(&q.y)));
+    let index = |p: &Point| sorted.iter().position(|q| *q == p).unwrap();
+
+    let edges: Vec<(usize, usize)> =
+        alg::symmetric_pairs(points, line).map(|(p, q)| (index(p), index(q))).collect();
+    let paired: HashSet<usize> = edges.iter().flat_map(|&(i, j)| [i, j]).collect();
+
+    let mut dot = String::from("graph reflection {\n");
+    for (i, p) in sorted.iter().enumerate() {
+        let style = if line.is_point_on_line(p) {
+            ", shape=doublecircle"
+        } else if !paired.contains(&i) {
+            ", color=red"
+        } else {
+            ""
+        };
+        writeln!(dot, "    n{} [label=\"({}, {})\"{}];", i, p.x, p.y, style).unwrap();
+    }
+    for (i, j) in edges {
+        writeln!(dot, "    n{} -- n{};", i.min(j), i.max(j)).unwrap();
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Returns a self-contained HTML page reporting the symmetries of the points, for sharing an
+/// analysis: a summary with the classification label and the rotational order, as by
+/// `alg::describe`, a table of the lines of symmetry found as configured by `opts`, in the polar
+/// form of `Line::to_polar` and in order of orientation, and an SVG drawing of the points and the
+/// lines clipped to the area around them.
+pub fn to_html_report(points: &HashSet<Point>, opts: &SymmetryOptions) -> String {
+    let mut lines: Vec<Line> = alg::get_lines_of_sym_with(points, opts).lines.into_iter().collect();
+    lines.sort_by(|l1, l2| l1.angle_rad().total_cmp(&l2.angle_rad()).then(l1.c.total_cmp(&l2.c)));
+    let summary = alg::describe(points);
+
+    let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>Symmetry report</title>\n</head>\n<body>\n<h1>Symmetry report</h1>\n");
+    writeln!(html, "<p>{} points, classified as {}.</p>", points.len(), summary.label).unwrap();
+    writeln!(html, "<p>Rotational order: {}.</p>", summary.rotational_order).unwrap();
+
+    writeln!(html, "<h2>{} lines of symmetry</h2>", lines.len()).unwrap();
+    html.push_str("<table>\n<tr><th>θ (rad)</th><th>ρ</th><th>a</th><th>b</th><th>c</th></tr>\n");
+    for line in &lines {
+        let (theta, rho) = line.to_polar();
+        writeln!(
+            html,
+            "<tr><td>{:.6}</td><td>{:.6}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            theta, rho, line.a, line.b, line.c
+        )
+        .unwrap();
+    }
+    html.push_str("</table>\n");
+
+    html.push_str(&svg_drawing(points, &lines));
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Returns an SVG drawing of the points, as dots, and the lines, clipped to the bounding box of the
+/// points with a margin of a tenth of its size, with the y axis pointing up.
+fn svg_drawing(points: &HashSet<Point>, lines: &[Line]) -> String {
+    let Some((min, max)) = bounding_box(points) else {
+        return String::from("<svg xmlns=\"http://www.w3.org/2000/svg\"></svg>\n");
+    };
+    let size = (max.x - min.x).max(max.y - min.y).max(1e-9);
+    let margin = size / 10.;
+    let (min, max) = (Point::new(min.x - margin, min.y - margin), Point::new(max.x + margin, max.y + margin));
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"400\" viewBox=\"{} {} {} {}\">",
+        min.x,
+        -max.y,
+        max.x - min.x,
+        max.y - min.y
+    )
+    .unwrap();
+    svg.push_str("<g transform=\"scale(1, -1)\">\n");
+    for line in lines {
+        if let Some((start, end)) = axis_endpoints(line, (min, max)) {
+            writeln!(
+                svg,
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"steelblue\" stroke-width=\"{}\"/>",
+                start.x,
+                start.y,
+                end.x,
+                end.y,
+                size / 200.
+            )
+            .unwrap();
+        }
+    }
+    for p in points {
+        writeln!(svg, "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"black\"/>", p.x, p.y, size / 100.).unwrap();
+    }
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+/// The size in bytes of one encoded line: three little-endian `f64` coefficients.
+const LINE_BYTES: usize = 24;
+
+/// Encodes lines as bytes, for caching results compactly: the number of lines as a little-endian
+/// `u64`, followed by the coefficients `a`, `b` and `c` of each line as little-endian `f64`s.
+///
+/// The coefficients are written as given, so decoding with `axes_from_bytes` restores each line
+/// exactly. The order of the lines is that of the set's iteration.
+pub fn axes_to_bytes(lines: &HashSet<Line>) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(8 + LINE_BYTES * lines.len());
+    bytes.extend_from_slice(&(lines.len() as u64).to_le_bytes());
+    for line in lines {
+        for coefficient in line.to_array() {
+            bytes.extend_from_slice(&coefficient.to_le_bytes());
+        }
+    }
+    bytes
+}
+
+/// Decodes lines encoded by `axes_to_bytes`.
+///
+/// Returns `SymmError::Parse` if the length of `bytes` doesn't match the count of lines they
+/// start with, or `SymmError::DegenerateLine` if a decoded line isn't valid, see `Line::is_valid`.
+pub fn axes_from_bytes(bytes: &[u8]) -> Result<HashSet<Line>, SymmError> {
+    let Some((count, body)) = bytes.split_first_chunk::<8>() else {
+        return Err(SymmError::Parse(String::from("missing the count of lines")));
+    };
+    let count = u64::from_le_bytes(*count);
+    if body.len() % LINE_BYTES != 0 || (body.len() / LINE_BYTES) as u64 != count {
+        let reason = format!("expected {} lines, found {} bytes of lines", count, body.len());
+        return Err(SymmError::Parse(reason));
+    }
+
+    body.chunks_exact(LINE_BYTES)
+        .map(|chunk| {
+            let coefficient = |i: usize| f64::from_le_bytes(chunk[8 * i..8 * i + 8].try_into().unwrap());
+            let line = Line::new(coefficient(0), coefficient(1), coefficient(2));
+            if line.is_valid() {
+                Ok(line)
+            } else {
+                Err(SymmError::DegenerateLine)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alg::get_lines_of_sym;
+    use crate::model::Point;
+
+    /// Tests that the axes of a hexagon survive encoding and decoding, and that truncated or
+    /// degenerate data is rejected.
+    #[test]
+    fn test_axes_bytes_round_trip() {
+        let hexagon: HashSet<Point> = (0..6)
+            .map(|i| {
+                let angle = std::f64::consts::FRAC_PI_3 * i as f64;
+                Point::new(angle.cos(), angle.sin())
+            })
+            .collect();
+        let lines = get_lines_of_sym(&hexagon, None).unwrap();
+        assert_eq!(lines.len(), 6);
+
+        let bytes = axes_to_bytes(&lines);
+        assert_eq!(bytes.len(), 8 + 6 * LINE_BYTES);
+        let decoded = axes_from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, lines);
+        assert!(decoded.iter().all(|line| lines.iter().any(|l| l.to_array() == line.to_array())));
+
+        assert!(axes_from_bytes(&axes_to_bytes(&HashSet::new())).unwrap().is_empty());
+        assert!(matches!(axes_from_bytes(&bytes[..bytes.len() - 1]), Err(SymmError::Parse(_))));
+        assert!(matches!(axes_from_bytes(&bytes[..4]), Err(SymmError::Parse(_))));
+
+        let degenerate = axes_to_bytes(&HashSet::from([Line::new(0., 0., 1.)]));
+        assert!(matches!(axes_from_bytes(&degenerate), Err(SymmError::DegenerateLine)));
+    }
+
+    /// Tests that a square's graph across a diagonal has one edge between the corners off the
+    /// diagonal, with the corners on it marked, and that an unpaired point is marked too.
+    #[test]
+    fn test_reflection_graph_dot() {
+        let mut square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+        ]);
+        let diagonal = Line::new(1., -1., 0.);
+        let dot = reflection_graph_dot(&square, &diagonal);
+        assert!(dot.starts_with("graph reflection {\n") && dot.ends_with("}\n"));
+        assert_eq!(dot.matches(" -- ").count(), 1);
+        assert!(dot.contains("    n1 -- n2;\n"));
+        assert_eq!(dot.matches("doublecircle").count(), 2);
+        assert!(dot.contains("    n0 [label=\"(0, 0)\", shape=doublecircle];\n"));
+
+        square.insert(Point::new(2., 0.));
+        let dot = reflection_graph_dot(&square, &diagonal);
+        assert_eq!(dot.matches(" -- ").count(), 1);
+        assert!(dot.contains("    n4 [label=\"(2, 0)\", color=red];\n"));
+    }
+
+    /// Tests that the report on a square embeds a drawing of its points and four axes, and lists
+    /// the axes with the square's classification.
+    #[test]
+    fn test_to_html_report() {
+        let square = HashSet::from([Point::new(0., 0.), Point::new(2., 0.), Point::new(2., 2.), Point::new(0., 2.)]);
+        let html = to_html_report(&square, &SymmetryOptions::default());
+        assert!(html.starts_with("<!DOCTYPE html>\n") && html.ends_with("</html>\n"));
+        assert!(html.contains("<svg ") && html.contains("</svg>"));
+        assert_eq!(html.matches("<circle ").count(), 4);
+        assert_eq!(html.matches("<line ").count(), 4);
+        assert!(html.contains("<h2>4 lines of symmetry</h2>"));
+        assert_eq!(html.matches("<tr><td>").count(), 4);
+        assert!(html.contains("D4 (square-like)") && html.contains("Rotational order: 4."));
+
+        let html = to_html_report(&HashSet::new(), &SymmetryOptions::default());
+        assert!(html.contains("<h2>0 lines of symmetry</h2>") && html.contains("</svg>"));
+    }
+
+    /// Tests that a square and its axes export as valid GeoJSON with one feature per point and per axis.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_geojson_square() {
+        let square = HashSet::from([Point::new(0., 0.), Point::new(1., 0.), Point::new(1., 1.), Point::new(0., 1.)]);
+        let lines = get_lines_of_sym(&square, None).unwrap();
+
+        let geojson: serde_json::Value = serde_json::from_str(&to_geojson(&square, &lines)).unwrap();
+        assert_eq!(geojson["type"], "FeatureCollection");
+
+        let features = geojson["features"].as_array().unwrap();
+        assert_eq!(features.len(), 8);
+        let line_strings = features.iter().filter(|f| f["geometry"]["type"] == "LineString");
+        assert_eq!(line_strings.count(), 4);
+    }
+}
@@ -0,0 +1,68 @@
+//! Conversions between this crate's types and the `geo` crate's types, enabled by the `geo` feature.
+//!
+//! The `geo` crate re-exports its geometry types from `geo-types`, which is the crate this
+//! module depends on, so the conversions apply to `geo::Coord`, `geo::MultiPoint` and `geo::Line`.
+
+use crate::geometry::axis_endpoints;
+use crate::model::{Line, Point};
+use std::collections::HashSet;
+
+impl From<geo_types::Coord<f64>> for Point {
+    /// Converts a `geo` coordinate into a point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either coordinate is NaN or infinite, like `Point::new`.
+    fn from(coord: geo_types::Coord<f64>) -> Self {
+        Point::new(coord.x, coord.y)
+    }
+}
+
+impl From<Point> for geo_types::Coord<f64> {
+    fn from(p: Point) -> Self {
+        geo_types::coord! { x: p.x, y: p.y }
+    }
+}
+
+/// Converts a `geo` multi-point into a point set, merging coincident points.
+///
+/// # Panics
+///
+/// Panics if any coordinate is NaN or infinite, like `Point::new`.
+pub fn points_from_multi_point(multi_point: &geo_types::MultiPoint<f64>) -> HashSet<Point> {
+    multi_point.iter().map(|p| Point::from(p.0)).collect()
+}
+
+/// Exports a line of symmetry as a `geo` line segment, clipped to the box with corners `min` and `max`.
+///
+/// Returns `None` if the line misses the box.
+pub fn axis_to_geo_line(line: &Line, min: &Point, max: &Point) -> Option<geo_types::Line<f64>> {
+    let (start, end) = axis_endpoints(line, (*min, *max))?;
+    Some(geo_types::Line::new(start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alg::get_lines_of_sym;
+    use crate::geometry::bounding_box;
+
+    /// Tests converting a `geo` multi-point square, detecting its 4 axes, and exporting them
+    /// as `geo` lines clipped to the square.
+    #[test]
+    fn test_multi_point_square() {
+        let multi_point = geo_types::MultiPoint::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.)]);
+        let points = points_from_multi_point(&multi_point);
+        assert_eq!(points.len(), 4);
+
+        let lines = get_lines_of_sym(&points, None).unwrap();
+        assert_eq!(lines.len(), 4);
+
+        let (min, max) = bounding_box(&points).unwrap();
+        for line in &lines {
+            let segment = axis_to_geo_line(line, &min, &max).unwrap();
+            assert!(line.is_point_on_line(&Point::from(segment.start)));
+            assert!(line.is_point_on_line(&Point::from(segment.end)));
+        }
+    }
+}
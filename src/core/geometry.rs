@@ -0,0 +1,617 @@
+use crate::alg;
+use crate::model::{Line, Point};
+use crate::util;
+use std::collections::HashSet;
+
+/// Returns the convex hull of a set of points, using Andrew's monotone chain algorithm.
+///
+/// Every line of symmetry of a point set is also a line of symmetry of its convex hull, so
+/// the hull can be used to reason about the boundary of a shape independently of its interior.
+///
+/// # Returns
+///
+/// The hull vertices in counter-clockwise order, starting from the lowest point by the
+/// `Point` ordering (smallest x, then smallest y). Points lying on a hull edge are not included.
+/// If all points are collinear, the two extreme points are returned; a single point is returned as is.
+pub fn convex_hull(points: &HashSet<Point>) -> Vec<Point> {
+    let mut sorted: Vec<Point> = points.iter().copied().collect();
+    sorted.sort_by(|p, q| p.partial_cmp(q).unwrap());
+
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    // Whether the last two points of the chain followed by p make a strict counter-clockwise turn.
+    let turns_left = |chain: &[Point], p: &Point| {
+        let (o, a) = (&chain[chain.len() - 2], &chain[chain.len() - 1]);
+        let cross = (a.x - o.x) * (p.y - o.y) - (a.y - o.y) * (p.x - o.x);
+        util::floats_lt_toler(0.0, cross)
+    };
+
+    let mut lower: Vec<Point> = Vec::new();
+    for p in &sorted {
+        while lower.len() >= 2 && !turns_left(&lower, p) {
+            lower.pop();
+        }
+        lower.push(*p);
+    }
+
+    let mut upper: Vec<Point> = Vec::new();
+    for p in sorted.iter().rev() {
+        while upper.len() >= 2 && !turns_left(&upper, p) {
+            upper.pop();
+        }
+        upper.push(*p);
+    }
+
+    // The last point of each chain is the first point of the other.
+    lower.pop();
+    upper.pop();
+    lower.extend(upper);
+    lower
+}
+
+/// Returns the center and radius of the smallest circle enclosing all points, using Welzl's algorithm.
+///
+/// The smallest enclosing circle is unique, so every isometry mapping the set onto itself
+/// fixes its center: all lines of symmetry pass through it, and it is the center of any
+/// rotational symmetry. This makes it a strong hint for restricting the candidate axes.
+///
+/// The points are processed in the iteration order of the set, which serves as the random
+/// permutation of the expected-linear-time variant of the algorithm. That order differs between
+/// runs, and with it the rounding of the result; see `min_enclosing_circle_seeded` for a
+/// reproducible one.
+///
+/// # Returns
+///
+/// `Some((center, radius))`, or `None` if the set is empty.
+pub fn min_enclosing_circle(points: &HashSet<Point>) -> Option<(Point, f64)> {
+    min_enclosing_circle_seeded(points, None)
+}
+
+/// Like `min_enclosing_circle`, but with the points processed in an order shuffled by a random
+/// number generator seeded with `seed`, if given, so that equal sets give bit-identical results
+/// in every run. With `None`, the iteration order of the set is used, as by `min_enclosing_circle`.
+pub fn min_enclosing_circle_seeded(points: &HashSet<Point>, seed: Option<u64>) -> Option<(Point, f64)> {
+    let mut points_vec: Vec<&Point> = points.iter().collect();
+    if let Some(seed) = seed {
+        // Start from an order independent of the set's, then shuffle it (Fisher-Yates).
+        points_vec.sort_by(|p, q| p.x.total_cmp(&q.x).then(p.y.total_cmp(&q.y)));
+        let mut rng = SplitMix64(seed);
+        for i in (1..points_vec.len()).rev() {
+            points_vec.swap(i, (rng.next() % (i as u64 + 1)) as usize);
+        }
+    }
+    let first = points_vec.first()?;
+
+    let mut circle = (**first, 0.0);
+
+    for i in 0..points_vec.len() {
+        if circle_contains(&circle, points_vec[i]) {
+            continue;
+        }
+
+        // points_vec[i] lies on the boundary of the smallest circle enclosing points_vec[..=i].
+        circle = (*points_vec[i], 0.0);
+        for j in 0..i {
+            if circle_contains(&circle, points_vec[j]) {
+                continue;
+            }
+
+            // Both points_vec[i] and points_vec[j] lie on the boundary.
+            circle = diametral_circle(points_vec[i], points_vec[j]);
+            for k in 0..j {
+                if !circle_contains(&circle, points_vec[k]) {
+                    circle = circumcircle(points_vec[i], points_vec[j], points_vec[k])
+                        .unwrap_or_else(|| widest_diametral_circle(points_vec[i], points_vec[j], points_vec[k]));
+                }
+            }
+        }
+    }
+
+    Some(circle)
+}
+
+/// Returns the vertices of a regular `n`-gon with the given center and circumradius, the first
+/// vertex at `rotation` radians counterclockwise from the positive x direction, e.g. as a test
+/// shape whose symmetry group is known to be `Dn`.
+///
+/// Vertices that coincide, e.g. all of them for a radius of 0, are kept once.
+pub fn regular_polygon(n: usize, center: &Point, radius: f64, rotation: f64) -> HashSet<Point> {
+    (0..n)
+        .map(|k| {
+            let (sin, cos) = (rotation + std::f64::consts::TAU * k as f64 / n as f64).sin_cos();
+            Point::new(center.x + radius * cos, center.y + radius * sin)
+        })
+        .collect()
+}
+
+/// Returns the lines of symmetry of a regular polygon without searching for them, or `None` if the
+/// points aren't the vertices of one.
+///
+/// The points are taken to be the vertices of a regular `n`-gon if their rotational symmetry
+/// order is the number of points `n`, with `n ≥ 3`, and they all lie on their smallest enclosing
+/// circle. Its axes are then the `n` lines through the circle's center at angles `π / n` apart,
+/// one of them through each vertex.
+pub fn regular_polygon_axes(points: &HashSet<Point>) -> Option<HashSet<Line>> {
+    regular_polygon_axes_seeded(points, None)
+}
+
+/// Like `regular_polygon_axes`, but with the smallest enclosing circle found as by
+/// `min_enclosing_circle_seeded`, and the first axis through the least vertex by the `Point`
+/// ordering, so that with a seed equal sets give bit-identical axes in every run.
+pub fn regular_polygon_axes_seeded(points: &HashSet<Point>, seed: Option<u64>) -> Option<HashSet<Line>> {
+    let n = points.len();
+    if n < 3 || alg::rotational_symmetry_order(points) != n {
+        return None;
+    }
+
+    let (center, radius) = min_enclosing_circle_seeded(points, seed)?;
+    if !points.iter().all(|p| util::floats_equal_toler(center.distance(p), radius)) {
+        return None;
+    }
+
+    let vertex = match seed {
+        Some(_) => points.iter().min_by(|p, q| p.x.total_cmp(&q.x).then(p.y.total_cmp(&q.y)))?,
+        None => points.iter().next()?,
+    };
+    let first_angle = (vertex.y - center.y).atan2(vertex.x - center.x);
+    let axes = (0..n)
+        .map(|k| {
+            // The line through the center in the direction of the angle, with normal (sin, -cos).
+            let (sin, cos) = (first_angle + std::f64::consts::PI * k as f64 / n as f64).sin_cos();
+            Line::new(sin, -cos, cos * center.y - sin * center.x)
+        })
+        .collect();
+    Some(axes)
+}
+
+/// Returns all lines of symmetry of a set of points, taking the shortcut of
+/// `regular_polygon_axes` for regular polygons and searching with `alg::get_lines_of_sym_or_empty`
+/// otherwise.
+pub fn get_lines_of_sym_fast(points: &HashSet<Point>) -> HashSet<Line> {
+    regular_polygon_axes(points).unwrap_or_else(|| alg::get_lines_of_sym_or_empty(points, None))
+}
+
+/// Returns the major and minor principal axes of a set of points, or `None` if the set is empty.
+///
+/// The principal axes are the lines through the centroid along the eigenvectors of the points'
+/// covariance matrix: the major axis is the direction along which the points spread the most, and
+/// the minor axis is perpendicular to it. The axes of symmetry of many shapes, e.g. of rectangles
+/// and ellipses, are principal axes, so they make good first candidates to check with
+/// `alg::verify_axis`. If the points spread equally in all directions, e.g. for a square, every
+/// direction is principal and the axes returned are the coordinate directions.
+pub fn principal_axes(points: &HashSet<Point>) -> Option<(Line, Line)> {
+    let center = Point::mean(points.iter().copied())?;
+    let (mut sxx, mut syy, mut sxy) = (0.0, 0.0, 0.0);
+    for p in points {
+        let (dx, dy) = (p.x - center.x, p.y - center.y);
+        sxx += dx * dx;
+        syy += dy * dy;
+        sxy += dx * dy;
+    }
+
+    // The direction of the eigenvector of the larger eigenvalue of [[sxx, sxy], [sxy, syy]].
+    let angle = 0.5 * (2.0 * sxy).atan2(sxx - syy);
+    let (sin, cos) = angle.sin_cos();
+    // The lines through the center along (cos, sin) and along the perpendicular (-sin, cos).
+    let major = Line::new(sin, -cos, cos * center.y - sin * center.x);
+    let minor = Line::new(cos, sin, -cos * center.x - sin * center.y);
+    Some((major, minor))
+}
+
+/// Returns the lower-left and upper-right corners of the bounding box of a set of points,
+/// or `None` if the set is empty.
+pub fn bounding_box(points: &HashSet<Point>) -> Option<(Point, Point)> {
+    let first = points.iter().next()?;
+    let (mut min, mut max) = (*first, *first);
+
+    for p in points {
+        min.x = min.x.min(p.x);
+        min.y = min.y.min(p.y);
+        max.x = max.x.max(p.x);
+        max.y = max.y.max(p.y);
+    }
+
+    Some((min, max))
+}
+
+/// Returns the union of a set of points and its reflection across `line`.
+///
+/// The result is symmetric about `line` by construction, which is useful for generating test
+/// data or completing a shape of which only one half was sampled. Points on or within tolerance
+/// of `line` are their own reflections and are not duplicated.
+///
+/// # Panics
+///
+/// Panics if `line` isn't valid (see `Line::is_valid`).
+pub fn symmetrize(points: &HashSet<Point>, line: &Line) -> HashSet<Point> {
+    let mut symmetric = points.clone();
+    for p in points {
+        let reflection = line.get_reflected_point(p);
+        if !points.iter().any(|q| *q == reflection) {
+            symmetric.insert(reflection);
+        }
+    }
+    symmetric
+}
+
+/// Returns the two points where a line crosses the boundary of an axis-aligned box, or `None`
+/// if the line misses the box or only touches it at a single point.
+///
+/// The box is given by its lower-left and upper-right corners, as returned by `bounding_box`.
+/// This clips an infinite line of symmetry to a drawable segment, e.g. for plotting it with
+/// matplotlib or exporting it with `export::to_geojson`.
+pub fn axis_endpoints(line: &Line, bbox: (Point, Point)) -> Option<(Point, Point)> {
+    let (min, max) = bbox;
+    let mut crossings: Vec<Point> = Vec::new();
+    let mut add = |x: f64, y: f64| {
+        let in_box = !util::floats_lt_toler(x, min.x)
+            && !util::floats_lt_toler(max.x, x)
+            && !util::floats_lt_toler(y, min.y)
+            && !util::floats_lt_toler(max.y, y);
+        if in_box && x.is_finite() && y.is_finite() {
+            let p = Point::new(x, y).clamp_to_box(&min, &max);
+            if !crossings.contains(&p) {
+                crossings.push(p);
+            }
+        }
+    };
+
+    // Intersections with the vertical sides (x fixed) and the horizontal sides (y fixed).
+    if !util::floats_equal_toler(line.b, 0.0) {
+        for x in [min.x, max.x] {
+            add(x, -(line.a * x + line.c) / line.b);
+        }
+    }
+    if !util::floats_equal_toler(line.a, 0.0) {
+        for y in [min.y, max.y] {
+            add(-(line.b * y + line.c) / line.a, y);
+        }
+    }
+
+    // A line through a corner meets two sides there; keep the farthest-apart pair.
+    let mut best: Option<(Point, Point)> = None;
+    for i in 0..crossings.len() {
+        for j in (i + 1)..crossings.len() {
+            let d = crossings[i].distance(&crossings[j]);
+            if best.is_none_or(|(p, q)| p.distance(&q) < d) {
+                best = Some((crossings[i], crossings[j]));
+            }
+        }
+    }
+
+    best
+}
+
+/// Returns the fraction of `perturbations` random perturbations of the points under which `line`
+/// is still detected as an axis, as a measure of how robust the axis is, e.g. to tell a real
+/// axis from a numerical artifact.
+///
+/// Each perturbation moves every coordinate of every point by an offset drawn uniformly from
+/// `[-magnitude, magnitude]`. The axis survives it if every moved point's reflection across the
+/// line is within distance `magnitude` of a moved point, i.e. if a search of the moved points for
+/// near-symmetries within the noise level, like `alg::detect_with_confidence` at that tolerance,
+/// would accept the line. A true axis thus survives more often than a line the points are only
+/// nearly symmetric about.
+///
+/// The offsets are drawn from a generator with a fixed seed, applied to the points in order of
+/// their coordinates, so equal inputs give equal results. Returns 1 if `perturbations` is 0, like
+/// a line that survives every perturbation tried.
+pub fn stress_test_axis(points: &HashSet<Point>, line: &Line, perturbations: usize, magnitude: f64) -> f64 {
+    if perturbations == 0 {
+        return 1.0;
+    }
+
+    let mut sorted: Vec<&Point> = points.iter().collect();
+    sorted.sort_by(|p, q| p.x.total_cmp(&q.x).then(p.y.total_cmp(&q.y)));
+
+    let mut rng = SplitMix64(0);
+    let mut offset = || magnitude * ((rng.next() >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0);
+    let survived = (0..perturbations)
+        .filter(|_| {
+            let moved: HashSet<Point> =
+                sorted.iter().map(|p| Point::new(p.x + offset(), p.y + offset())).collect();
+            let index = alg::PointIndex::new(&moved);
+            moved.iter().all(|p| {
+                line.try_reflected_point(p)
+                    .is_some_and(|reflection| index.contains_within(&reflection, magnitude))
+            })
+        })
+        .count();
+
+    survived as f64 / perturbations as f64
+}
+
+/// The SplitMix64 generator, a small and fast seedable source of pseudo-random numbers.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Checks whether a point lies inside a circle, with tolerance.
+fn circle_contains(circle: &(Point, f64), p: &Point) -> bool {
+    !util::floats_lt_toler(circle.1, circle.0.distance(p))
+}
+
+/// Returns the circle having the segment from `p1` to `p2` as a diameter.
+fn diametral_circle(p1: &Point, p2: &Point) -> (Point, f64) {
+    let center = Point::new(0.5 * (p1.x + p2.x), 0.5 * (p1.y + p2.y));
+    (center, 0.5 * p1.distance(p2))
+}
+
+/// Returns the largest of the diametral circles of three points, which encloses all three
+/// when they are collinear.
+fn widest_diametral_circle(p1: &Point, p2: &Point, p3: &Point) -> (Point, f64) {
+    [diametral_circle(p1, p2), diametral_circle(p1, p3), diametral_circle(p2, p3)]
+        .into_iter()
+        .max_by(|c1, c2| c1.1.total_cmp(&c2.1))
+        .unwrap()
+}
+
+/// Returns the circle through three points, or `None` if they are collinear.
+fn circumcircle(p1: &Point, p2: &Point, p3: &Point) -> Option<(Point, f64)> {
+    let (bx, by) = (p2.x - p1.x, p2.y - p1.y);
+    let (cx, cy) = (p3.x - p1.x, p3.y - p1.y);
+    let d = 2.0 * (bx * cy - by * cx);
+
+    if util::floats_equal_toler(d, 0.0) {
+        return None;
+    }
+
+    let b2 = bx * bx + by * by;
+    let c2 = cx * cx + cy * cy;
+    let center = Point::new(p1.x + (cy * b2 - by * c2) / d, p1.y + (bx * c2 - cx * b2) / d);
+    let radius = center.distance(p1);
+
+    Some((center, radius))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that the hull of a square with interior and edge points is its four corners,
+    /// in counter-clockwise order.
+    #[test]
+    fn test_convex_hull_square() {
+        let points = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(2., 0.),
+            Point::new(2., 2.),
+            Point::new(0., 2.),
+            Point::new(1., 1.),
+            Point::new(1., 0.),
+        ]);
+
+        let hull = convex_hull(&points);
+        assert_eq!(
+            hull,
+            vec![Point::new(0., 0.), Point::new(2., 0.), Point::new(2., 2.), Point::new(0., 2.)]
+        );
+    }
+
+    #[test]
+    fn test_convex_hull_collinear() {
+        let points: HashSet<Point> = (0..5).map(|i| Point::new(i as f64, 2. * i as f64)).collect();
+        assert_eq!(convex_hull(&points), vec![Point::new(0., 0.), Point::new(4., 8.)]);
+    }
+
+    /// Tests that the smallest enclosing circle of a square is centered at its centroid,
+    /// with the half-diagonal as radius, regardless of interior points.
+    #[test]
+    fn test_min_enclosing_circle_square() {
+        let points = HashSet::from([
+            Point::new(1., 1.),
+            Point::new(3., 1.),
+            Point::new(3., 3.),
+            Point::new(1., 3.),
+            Point::new(2.5, 2.),
+        ]);
+
+        let (center, radius) = min_enclosing_circle(&points).unwrap();
+        assert_eq!(center, Point::new(2., 2.));
+        assert!(util::floats_equal_toler(radius, std::f64::consts::SQRT_2));
+
+        assert!(min_enclosing_circle(&HashSet::new()).is_none());
+        let (center, radius) = min_enclosing_circle(&HashSet::from([Point::new(5., 5.)])).unwrap();
+        assert_eq!(center, Point::new(5., 5.));
+        assert_eq!(radius, 0.0);
+    }
+
+    /// Tests that seeded runs on equal sets built in different orders give bit-identical circles.
+    #[test]
+    fn test_min_enclosing_circle_seeded() {
+        let coordinates: Vec<(f64, f64)> =
+            (0..60).map(|k| ((k * 37 % 101) as f64 / 7., (k * 53 % 89) as f64 / 3.)).collect();
+        let forwards: HashSet<Point> = coordinates.iter().map(|&(x, y)| Point::new(x, y)).collect();
+        let backwards: HashSet<Point> = coordinates.iter().rev().map(|&(x, y)| Point::new(x, y)).collect();
+
+        let bits = |(center, radius): (Point, f64)| {
+            [center.x.to_bits(), center.y.to_bits(), radius.to_bits()]
+        };
+        let first = bits(min_enclosing_circle_seeded(&forwards, Some(42)).unwrap());
+        assert_eq!(bits(min_enclosing_circle_seeded(&forwards, Some(42)).unwrap()), first);
+        assert_eq!(bits(min_enclosing_circle_seeded(&backwards, Some(42)).unwrap()), first);
+
+        let (center, radius) = min_enclosing_circle(&forwards).unwrap();
+        let (seeded_center, seeded_radius) = min_enclosing_circle_seeded(&forwards, Some(7)).unwrap();
+        assert_eq!(center, seeded_center);
+        assert!(util::floats_equal_toler(radius, seeded_radius));
+        assert!(min_enclosing_circle_seeded(&HashSet::new(), Some(42)).is_none());
+    }
+
+    #[test]
+    fn test_min_enclosing_circle_triangle() {
+        // An acute triangle's enclosing circle is its circumcircle.
+        let points = HashSet::from([Point::new(-1., 0.), Point::new(1., 0.), Point::new(0., 3.)]);
+        let (center, radius) = min_enclosing_circle(&points).unwrap();
+        assert_eq!(center, Point::new(0., 4. / 3.));
+        assert!(util::floats_equal_toler(radius, 5. / 3.));
+
+        // An obtuse triangle's enclosing circle has its longest side as a diameter.
+        let points = HashSet::from([Point::new(-2., 0.), Point::new(2., 0.), Point::new(0., 1.)]);
+        let (center, radius) = min_enclosing_circle(&points).unwrap();
+        assert_eq!(center, Point::new(0., 0.));
+        assert!(util::floats_equal_toler(radius, 2.));
+    }
+
+    /// Tests that a regular hexagon's axes are found by the shortcut and match the search's, and
+    /// that a rectangle and a hexagon with its center aren't taken for regular polygons.
+    #[test]
+    fn test_regular_polygon_axes() {
+        let hexagon = regular_polygon(6, &Point::new(5., -1.), 2., 0.3);
+        let axes = regular_polygon_axes(&hexagon).unwrap();
+        assert_eq!(axes.len(), 6);
+        assert_eq!(axes, alg::get_lines_of_sym_or_empty(&hexagon, None));
+        assert_eq!(get_lines_of_sym_fast(&hexagon), axes);
+
+        let mut centered = hexagon.clone();
+        centered.insert(Point::new(5., -1.));
+        assert!(regular_polygon_axes(&centered).is_none());
+        assert_eq!(get_lines_of_sym_fast(&centered), axes);
+
+        let rectangle = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(2., 0.),
+            Point::new(2., 1.),
+            Point::new(0., 1.),
+        ]);
+        assert!(regular_polygon_axes(&rectangle).is_none());
+        assert_eq!(get_lines_of_sym_fast(&rectangle).len(), 2);
+    }
+
+    /// Tests that seeded runs on equal regular polygons built in different orders give
+    /// bit-identical axes, and the same axes as an unseeded run within tolerance.
+    #[test]
+    fn test_regular_polygon_axes_seeded() {
+        let vertices: Vec<Point> = regular_polygon(9, &Point::new(-3., 8.), 4., 0.1).into_iter().collect();
+        let forwards: HashSet<Point> = vertices.iter().copied().collect();
+        let backwards: HashSet<Point> = vertices.iter().rev().copied().collect();
+
+        let bits = |axes: HashSet<Line>| {
+            let mut bits: Vec<[u64; 3]> = axes.iter().map(|l| l.to_array().map(f64::to_bits)).collect();
+            bits.sort();
+            bits
+        };
+        let first = bits(regular_polygon_axes_seeded(&forwards, Some(42)).unwrap());
+        assert_eq!(bits(regular_polygon_axes_seeded(&forwards, Some(42)).unwrap()), first);
+        assert_eq!(bits(regular_polygon_axes_seeded(&backwards, Some(42)).unwrap()), first);
+        assert_eq!(regular_polygon_axes_seeded(&forwards, Some(42)), regular_polygon_axes(&forwards));
+    }
+
+    /// Tests that a regular pentagon has five axes and five-fold rotational symmetry, with its
+    /// first vertex where requested.
+    #[test]
+    fn test_regular_polygon() {
+        let origin = Point::new(0., 0.);
+        let pentagon = regular_polygon(5, &origin, 1., 0.);
+        assert_eq!(pentagon.len(), 5);
+        // The vertices are rounded, which can miss the hash lookup.
+        let has_vertex = |points: &HashSet<Point>, x, y| points.iter().any(|p| *p == Point::new(x, y));
+        assert!(has_vertex(&pentagon, 1., 0.));
+        assert_eq!(alg::get_lines_of_sym(&pentagon, None).unwrap().len(), 5);
+        assert_eq!(alg::rotational_symmetry_order(&pentagon), 5);
+
+        let square = regular_polygon(4, &Point::new(1., 1.), 2f64.sqrt(), std::f64::consts::FRAC_PI_4);
+        assert!(has_vertex(&square, 2., 2.) && has_vertex(&square, 0., 0.));
+        assert_eq!(regular_polygon(3, &origin, 0., 0.).len(), 1);
+        assert!(regular_polygon(0, &origin, 1., 0.).is_empty());
+    }
+
+    /// Tests that a rotated rectangle's principal axes are its axes of symmetry, with the major
+    /// axis along its long side.
+    #[test]
+    fn test_principal_axes() {
+        let (sin, cos) = (std::f64::consts::PI / 6.).sin_cos();
+        let corners_and_midpoints = [(-2., -0.5), (2., -0.5), (2., 0.5), (-2., 0.5), (0., -0.5), (0., 0.5)];
+        let rectangle: HashSet<Point> = corners_and_midpoints
+            .into_iter()
+            .map(|(x, y)| Point::new(1. + x * cos - y * sin, 2. + x * sin + y * cos))
+            .collect();
+
+        let (major, minor) = principal_axes(&rectangle).unwrap();
+        assert!(alg::verify_axis(&rectangle, &major));
+        assert!(alg::verify_axis(&rectangle, &minor));
+        assert!(major.is_point_on_line(&Point::new(1. + 2. * cos, 2. + 2. * sin)));
+        assert!(minor.is_point_on_line(&Point::new(1. - 0.5 * sin, 2. + 0.5 * cos)));
+
+        assert!(principal_axes(&HashSet::new()).is_none());
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let points = HashSet::from([Point::new(1., -2.), Point::new(-3., 4.), Point::new(0., 0.)]);
+        assert_eq!(bounding_box(&points), Some((Point::new(-3., -2.), Point::new(1., 4.))));
+        assert!(bounding_box(&HashSet::new()).is_none());
+    }
+
+    /// Tests that completing the left half of a square across its vertical axis gives the square,
+    /// in which the axis is then found, and that points on the axis aren't duplicated.
+    #[test]
+    fn test_symmetrize() {
+        let half_square = HashSet::from([Point::new(0., 0.), Point::new(0., 2.)]);
+        let axis = Line::new(1., 0., -1.);
+
+        let square = symmetrize(&half_square, &axis);
+        let corners = [Point::new(0., 0.), Point::new(0., 2.), Point::new(2., 0.), Point::new(2., 2.)];
+        assert_eq!(square, HashSet::from(corners));
+        let lines = crate::alg::get_lines_of_sym(&square, None).unwrap();
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().any(|l| l.approx_eq(&axis, 1e-9)));
+
+        let on_axis = HashSet::from([Point::new(1., 5.)]);
+        assert_eq!(symmetrize(&on_axis, &axis), on_axis);
+    }
+
+    #[test]
+    fn test_axis_endpoints() {
+        let bbox = (Point::new(0., 0.), Point::new(2., 1.));
+
+        let (p, q) = axis_endpoints(&Line::new(1., 0., -1.), bbox).unwrap();
+        let (lower, upper) = if p.y < q.y { (p, q) } else { (q, p) };
+        assert_eq!(lower, Point::new(1., 0.));
+        assert_eq!(upper, Point::new(1., 1.));
+
+        assert!(axis_endpoints(&Line::new(0., 1., -5.), bbox).is_none());
+    }
+
+    /// Tests that a diagonal of the unit square is clipped to the two corners it passes through.
+    #[test]
+    fn test_axis_endpoints_diagonal() {
+        let unit_square = (Point::new(0., 0.), Point::new(1., 1.));
+
+        let (p, q) = axis_endpoints(&Line::new(1., -1., 0.), unit_square).unwrap();
+        let (lower, upper) = if p.y < q.y { (p, q) } else { (q, p) };
+        assert_eq!(lower, Point::new(0., 0.));
+        assert_eq!(upper, Point::new(1., 1.));
+    }
+
+    /// Tests that a true axis of a square survives small perturbations more often than a line
+    /// slightly off it, and that the results are reproducible.
+    #[test]
+    fn test_stress_test_axis() {
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+        ]);
+        let axis = Line::new(1., 0., -0.5);
+        let marginal = Line::new(1., 0., -0.503);
+
+        let axis_survival = stress_test_axis(&square, &axis, 200, 0.01);
+        let marginal_survival = stress_test_axis(&square, &marginal, 200, 0.01);
+        assert!(axis_survival > marginal_survival, "{axis_survival} <= {marginal_survival}");
+        assert_eq!(stress_test_axis(&square, &axis, 200, 0.01), axis_survival);
+        assert_eq!(stress_test_axis(&square, &Line::new(1., 0., -0.4), 50, 0.01), 0.0);
+        assert_eq!(stress_test_axis(&square, &axis, 0, 0.01), 1.0);
+    }
+}
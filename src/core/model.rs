@@ -1,41 +1,165 @@
-use crate::config;
+use crate::alg;
+use crate::collections::HashSet;
+use crate::config::Scalar;
+use crate::error::SymmError;
 use crate::util;
-use std::cmp::Ordering;
-use std::hash::{Hash, Hasher};
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use alloc::vec::Vec;
+// Called by path on `f64`, whose inherent float methods need `std`.
+use num_traits::Float;
 
 /// A point in 2D space with floating-point coordinates.
 ///
 /// Points are compared using a tolerance-based comparison to handle floating-point imprecision.
-/// Coordinates must be finite and non-NaN.
-#[derive(Debug)]
-pub struct Point {
-    pub x: f64,
-    pub y: f64,
+/// Coordinates must be finite and non-NaN. The coordinate type defaults to `f64`; `f32` is
+/// also supported, with the tolerance given by `config::EPSILON_F32`.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point<T = f64> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Point {
+impl<T: Scalar> Point<T> {
     /// Creates a new Point with the given coordinates.
     ///
     /// # Panics
     ///
     /// Panics if either coordinate is NaN or infinite.
-    pub fn new(x: f64, y: f64) -> Self {
+    pub fn new(x: T, y: T) -> Self {
         if !x.is_finite() || !y.is_finite() {
-            panic!("Point coordinates must be finite and non-NaN");
+            panic!("{}", SymmError::NonFinitePoint);
         }
         Self { x, y }
     }
+
+    /// Checks whether both coordinates are finite, as they are for points built with `new` or
+    /// `from_array`.
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite()
+    }
+
+    /// Returns the Euclidean distance between this point and `other`.
+    pub fn distance(&self, other: &Point<T>) -> T {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+
+    /// Checks whether each coordinate of this point differs from `other`'s by less than `tol`.
+    ///
+    /// This is `==` with an explicit tolerance in place of the scalar's `TOLERANCE`.
+    pub fn approx_eq(&self, other: &Point<T>, tol: T) -> bool {
+        (self.x - other.x).abs() < tol && (self.y - other.y).abs() < tol
+    }
+
+    /// Checks whether each coordinate of this point equals `other`'s within a tolerance relative
+    /// to their magnitude, or within `abs_tol`, as by `util::floats_equal_rel`.
+    ///
+    /// Unlike `==` and `approx_eq`, whose tolerance is absolute, this is as precise for points far
+    /// from the origin as their coordinates allow.
+    pub fn approx_eq_rel(&self, other: &Point<T>, rel_tol: T, abs_tol: T) -> bool {
+        util::floats_equal_rel(self.x, other.x, rel_tol, abs_tol)
+            && util::floats_equal_rel(self.y, other.y, rel_tol, abs_tol)
+    }
+
+    /// Checks whether this point and `other` are at most `tol` apart.
+    ///
+    /// Unlike `==`, which compares each coordinate within the scalar's tolerance, i.e. within a
+    /// box, this compares within a disk. The squared distance is compared, to avoid a square root.
+    pub fn approx_eq_radial(&self, other: &Point<T>, tol: T) -> bool {
+        let (dx, dy) = (self.x - other.x, self.y - other.y);
+        dx * dx + dy * dy <= tol * tol
+    }
+
+    /// Returns the point as a JSON object `{"x": x, "y": y}` with numeric coordinates. Requires
+    /// the `serde` feature.
+    ///
+    /// This shape is the crate's stable wire format for points, independent of the layout of the
+    /// struct and of its `Serialize` implementation.
+    #[cfg(feature = "serde")]
+    pub fn to_json_value(&self) -> serde_json::Value {
+        serde_json::json!({ "x": to_f64(self.x), "y": to_f64(self.y) })
+    }
+
+    /// Returns this point rotated counterclockwise by `angle_rad` radians about `center`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rotated coordinates aren't finite, e.g. because they overflowed. See
+    /// `checked_rotate_about` for a non-panicking version.
+    pub fn rotate_about(&self, center: &Point<T>, angle_rad: T) -> Point<T> {
+        match self.checked_rotate_about(center, angle_rad) {
+            Ok(rotated) => rotated,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Returns this point rotated counterclockwise by `angle_rad` radians about `center`, or
+    /// `SymmError::NonFinitePoint` if the rotated coordinates aren't finite.
+    pub fn checked_rotate_about(&self, center: &Point<T>, angle_rad: T) -> Result<Point<T>, SymmError> {
+        let (sin, cos) = Float::sin_cos(angle_rad);
+        let (dx, dy) = (self.x - center.x, self.y - center.y);
+        Point::from_array([center.x + dx * cos - dy * sin, center.y + dx * sin + dy * cos])
+    }
+
+    /// Returns the point of the axis-aligned box with corners `min` and `max` nearest to this one,
+    /// i.e. this point with each coordinate clamped into the box's range, e.g. to clip a
+    /// reflected point to a drawing area.
+    ///
+    /// Unlike `f64::clamp`, this doesn't panic if a coordinate of `min` exceeds that of `max`;
+    /// the coordinate is then that of `max`.
+    pub fn clamp_to_box(&self, min: &Point<T>, max: &Point<T>) -> Point<T> {
+        Point::new(self.x.max(min.x).min(max.x), self.y.max(min.y).min(max.y))
+    }
+
+    /// Returns the mean of the given points, i.e. their centroid, or `None` if there are none or
+    /// the mean isn't finite.
+    ///
+    /// The mean is accumulated as a running average rather than a sum, so it doesn't overflow
+    /// for points whose coordinates are near the largest finite value.
+    pub fn mean<I: IntoIterator<Item = Point<T>>>(iter: I) -> Option<Point<T>> {
+        let (mut x, mut y, mut count) = (T::zero(), T::zero(), T::zero());
+        for p in iter {
+            count = count + T::one();
+            x = x + (p.x - x) / count;
+            y = y + (p.y - y) / count;
+        }
+
+        if count == T::zero() {
+            return None;
+        }
+        Point::from_array([x, y]).ok()
+    }
+
+    /// Returns the coordinates as `[x, y]`.
+    pub fn to_array(&self) -> [T; 2] {
+        [self.x, self.y]
+    }
+
+    /// Creates a point from coordinates given as `[x, y]`.
+    ///
+    /// Returns `SymmError::NonFinitePoint` if either coordinate is NaN or infinite.
+    pub fn from_array(coords: [T; 2]) -> Result<Self, SymmError> {
+        let [x, y] = coords;
+        if !x.is_finite() || !y.is_finite() {
+            return Err(SymmError::NonFinitePoint);
+        }
+        Ok(Self { x, y })
+    }
 }
 
-impl PartialEq for Point {
+/// Points with a non-finite coordinate, which can only be built by setting the fields directly,
+/// are equal to no point, including themselves.
+impl<T: Scalar> PartialEq for Point<T> {
     fn eq(&self, other: &Self) -> bool {
-        util::floats_equal_toler(self.x, other.x) && util::floats_equal_toler(self.y, other.y)
+        self.is_finite() && other.is_finite() && self.approx_eq(other, T::TOLERANCE)
     }
 }
 
-impl Eq for Point {}
+impl<T: Scalar> Eq for Point<T> {}
 
-impl PartialOrd for Point {
+impl<T: Scalar> PartialOrd for Point<T> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         let x_cmp = util::float_partial_cmp_tolerance(&self.x, &other.x);
         match x_cmp {
@@ -45,95 +169,907 @@ impl PartialOrd for Point {
     }
 }
 
-impl Hash for Point {
+impl<T: Scalar> Hash for Point<T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.x.to_bits().hash(state);
-        self.y.to_bits().hash(state);
+        to_f64(self.x).to_bits().hash(state);
+        to_f64(self.y).to_bits().hash(state);
     }
 }
 
-/// A line in 2D space represented by the equation ax + by + c = 0.
+/// Widens a coordinate to `f64`, which is lossless for the supported scalar types.
+fn to_f64<T: Scalar>(v: T) -> f64 {
+    v.to_f64().unwrap()
+}
+
+/// A displacement in 2D space, e.g. the translation part of a glide reflection.
+///
+/// Vectors are compared using a tolerance-based comparison, like points.
+#[derive(Debug, Clone, Copy)]
+pub struct Vector2 {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Vector2 {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the Euclidean length of the vector.
+    pub fn length(&self) -> f64 {
+        Float::hypot(self.x, self.y)
+    }
+}
+
+impl PartialEq for Vector2 {
+    fn eq(&self, other: &Self) -> bool {
+        util::floats_equal_toler(self.x, other.x) && util::floats_equal_toler(self.y, other.y)
+    }
+}
+
+/// A point carrying a label (e.g. a color or type).
+///
+/// When searching for lines of symmetry among labeled points, a point's reflection must
+/// carry the same label as the point itself.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct LabeledPoint<T> {
+    pub point: Point,
+    pub label: T,
+}
+
+impl<T> LabeledPoint<T> {
+    pub fn new(point: Point, label: T) -> Self {
+        Self { point, label }
+    }
+}
+
+/// A point carrying a weight (e.g. a mass or a count of repeated measurements).
+///
+/// When searching for lines of symmetry among weighted points, a point's reflection must
+/// carry the same weight, within floating-point tolerance.
 #[derive(Debug)]
-pub struct Line {
+pub struct WeightedPoint {
+    pub point: Point,
+    pub weight: f64,
+}
+
+impl WeightedPoint {
+    /// Creates a new WeightedPoint.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the weight is NaN or infinite.
+    pub fn new(point: Point, weight: f64) -> Self {
+        if !weight.is_finite() {
+            panic!("Point weight must be finite and non-NaN");
+        }
+        Self { point, weight }
+    }
+}
+
+impl PartialEq for WeightedPoint {
+    fn eq(&self, other: &Self) -> bool {
+        self.point == other.point && util::floats_equal_toler(self.weight, other.weight)
+    }
+}
+
+impl Eq for WeightedPoint {}
+
+impl Hash for WeightedPoint {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Weights are compared with a tolerance, so only the location is hashed.
+        self.point.hash(state);
+    }
+}
+
+/// A line in 2D space represented by the equation ax + by + c = 0.
+///
+/// The coefficients are kept as given. `==` and `Hash` compare the unit coefficients, computed on
+/// each comparison unless the line is already in that form, e.g. after `normalize_in_place`.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Line<T = f64> {
     // ax + by + c = 0
-    pub a: f64,
-    pub b: f64,
-    pub c: f64,
+    pub a: T,
+    pub b: T,
+    pub c: T,
 }
 
-impl Line {
-    pub fn new(a: f64, b: f64, c: f64) -> Self {
+impl<T: Scalar> Line<T> {
+    /// Creates the line `ax + by + c = 0` without checking the coefficients.
+    ///
+    /// A line with a non-finite coefficient or a (near) zero normal can be built, but isn't valid
+    /// (see `is_valid`), and methods such as `get_reflected_point` panic on it. Use `try_new` for
+    /// coefficients from untrusted input.
+    pub fn new(a: T, b: T, c: T) -> Self {
         Self { a, b, c }
     }
 
+    /// Creates the line `ax + by + c = 0`, checking that it is well-defined.
+    ///
+    /// Returns `SymmError::NonFiniteLine` if a coefficient is NaN or infinite, or
+    /// `SymmError::DegenerateLine` if the normal `(a, b)` is (near) zero (see `is_valid`).
+    pub fn try_new(a: T, b: T, c: T) -> Result<Self, SymmError> {
+        if !a.is_finite() || !b.is_finite() || !c.is_finite() {
+            return Err(SymmError::NonFiniteLine);
+        }
+
+        let line = Self::new(a, b, c);
+        if !line.is_valid() {
+            return Err(SymmError::DegenerateLine);
+        }
+        Ok(line)
+    }
+
+    /// Returns the line through `p1` and `p2`, or `None` if they coincide within floating-point
+    /// tolerance, in which case it is undefined.
+    pub fn through_points(p1: &Point<T>, p2: &Point<T>) -> Option<Line<T>> {
+        if p1 == p2 {
+            return None;
+        }
+
+        Some(alg::get_through_line(p1, p2))
+    }
+
     /// Returns a hash value for the line, using the custom hash implementation.
+    #[cfg(feature = "std")]
     pub fn get_hash(&self) -> u64 {
         let mut hasher = std::collections::hash_map::DefaultHasher::new();
         self.hash(&mut hasher);
         hasher.finish()
     }
 
+    /// Checks whether all three coefficients are finite.
+    pub fn is_finite(&self) -> bool {
+        self.a.is_finite() && self.b.is_finite() && self.c.is_finite()
+    }
+
+    /// Checks whether the line is well-defined, i.e. its normal `(a, b)` is longer than the
+    /// floating-point tolerance. The length is compared rather than `a² + b²`, which would reject
+    /// the bisectors of points that are distinct but closer than the square root of the tolerance.
+    pub fn is_valid(&self) -> bool {
+        self.a.hypot(self.b) > T::TOLERANCE
+    }
+
     /// Returns the reflection of the given point `p` across this line.
-    pub fn get_reflected_point(&self, p: &Point) -> Point {
-        let denom = self.a.powf(2.0) + self.b.powf(2.0);
-        if denom == 0.0 {
-            panic!("Invalid line: a^2 + b^2 cannot be zero");
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line isn't valid (see `is_valid`). Use `try_reflected_point` to get `None`
+    /// instead.
+    pub fn get_reflected_point(&self, p: &Point<T>) -> Point<T> {
+        if !self.is_valid() {
+            panic!("{}", SymmError::DegenerateLine);
         }
 
-        let factor = 2.0 * (self.a * p.x + self.b * p.y + self.c) / denom;
+        let denom = self.a.powi(2) + self.b.powi(2);
+        let factor = (T::one() + T::one()) * (self.a * p.x + self.b * p.y + self.c) / denom;
         let x_reflected = p.x - factor * self.a;
         let y_reflected = p.y - factor * self.b;
 
         Point::new(x_reflected, y_reflected)
     }
 
-    /// Checks if the given point lies on this line, within floating-point tolerance.
-    pub fn is_point_on_line(&self, p: &Point) -> bool {
-        util::floats_equal_toler(self.a * p.x + self.b * p.y + self.c, 0.0)
+    /// Returns the reflection of `p` across this line, or the reason it can't be computed:
+    /// `SymmError::DegenerateLine` if the line isn't valid (see `is_valid`), or
+    /// `SymmError::NonFinitePoint` if the reflection's coordinates aren't finite.
+    pub fn checked_reflected_point(&self, p: &Point<T>) -> Result<Point<T>, SymmError> {
+        if !self.is_valid() {
+            return Err(SymmError::DegenerateLine);
+        }
+
+        self.try_reflected_point(p).ok_or(SymmError::NonFinitePoint)
+    }
+
+    /// Returns the reflection of `p` across this line, or `None` if the line isn't valid (see
+    /// `is_valid`) or the reflection's coordinates aren't finite, e.g. because the reflection
+    /// formula overflowed on extreme coordinates.
+    pub fn try_reflected_point(&self, p: &Point<T>) -> Option<Point<T>> {
+        if !self.is_valid() {
+            return None;
+        }
+
+        let denom = self.a.powi(2) + self.b.powi(2);
+        let factor = (T::one() + T::one()) * (self.a * p.x + self.b * p.y + self.c) / denom;
+        Point::from_array([p.x - factor * self.a, p.y - factor * self.b]).ok()
+    }
+
+    /// Checks if the given point lies on this line, within floating-point tolerance, as checked by
+    /// `is_point_on_line_tol` with the scalar's `TOLERANCE`.
+    pub fn is_point_on_line(&self, p: &Point<T>) -> bool {
+        self.is_point_on_line_tol(p, T::TOLERANCE)
+    }
+
+    /// Checks if the given point is less than `tol` from this line, e.g. with a looser tolerance
+    /// for noisy data.
+    ///
+    /// The residual `a * x + b * y + c` grows with the scale of the coefficients, so it is divided
+    /// by the length of the normal `(a, b)` to give the distance, and the result doesn't depend on
+    /// how the equation is scaled. A line that isn't valid (see `is_valid`) has no normal, and its
+    /// raw residual is compared as by `is_point_on_line_within`.
+    pub fn is_point_on_line_tol(&self, p: &Point<T>, tol: T) -> bool {
+        if !self.is_valid() {
+            return self.is_point_on_line_within(p, tol);
+        }
+        (self.a * p.x + self.b * p.y + self.c).abs() / self.a.hypot(self.b) < tol
+    }
+
+    /// Checks if the given point lies on this line, i.e. `a * x + b * y + c` is less than `tol`
+    /// from zero.
+    pub fn is_point_on_line_within(&self, p: &Point<T>, tol: T) -> bool {
+        (self.a * p.x + self.b * p.y + self.c).abs() < tol
+    }
+
+    /// Returns which side of this line `p` lies on, from the sign of `a * x + b * y + c`.
+    ///
+    /// The sides are relative to the line's direction `(b, -a)`: the normal `(a, b)` points to
+    /// the left. Points on the line within floating-point tolerance (see `is_point_on_line`)
+    /// are `On` it.
+    pub fn side_of(&self, p: &Point<T>) -> Side {
+        if self.is_point_on_line(p) {
+            Side::On
+        } else if self.a * p.x + self.b * p.y + self.c > T::zero() {
+            Side::Left
+        } else {
+            Side::Right
+        }
+    }
+
+    /// Returns the coefficients as `[a, b, c]`.
+    pub fn to_array(&self) -> [T; 3] {
+        [self.a, self.b, self.c]
+    }
+
+    /// Returns the coefficients in canonical form: scaled so that `(a, b)` is a unit vector with
+    /// `a` positive, or `b` positive if the scaled `a` is within `TOLERANCE` of zero.
+    ///
+    /// Two lines are the same line, in the sense of `==` and `Hash`, exactly when their canonical
+    /// coefficients agree within `TOLERANCE`, so compare or display axes through this rather than
+    /// the raw fields, which any nonzero multiple of the equation leaves free. Zero coefficients
+    /// are returned as positive zero. An invalid line, see `is_valid`, has no canonical form and
+    /// its raw coefficients are returned.
+    pub fn normalized_coeffs(&self) -> (T, T, T) {
+        let [a, b, c] = self.comparison_coefficients();
+        (a + T::zero(), b + T::zero(), c + T::zero())
+    }
+
+    /// Returns the line in Hesse normal form `(θ, ρ)`, i.e. as `x·cos θ + y·sin θ = ρ`, with the
+    /// angle `θ` of the unit normal in `[-π/2, π/2)` and the signed distance `ρ` from the origin.
+    ///
+    /// Each valid line has one polar form, whatever the scaling of its coefficients, so the form
+    /// suits hashing, comparison and Hough-transform-style binning. The range of `θ` is cut at the
+    /// horizontal lines, whose normal is vertical: a line within tolerance of horizontal may land at
+    /// either end of the range, with `ρ` negated. A line that isn't valid (see `is_valid`) has no
+    /// normal and gives NaNs.
+    pub fn to_polar(&self) -> (T, T) {
+        if !self.is_valid() {
+            return (T::nan(), T::nan());
+        }
+
+        let pi = T::from(core::f64::consts::PI).unwrap();
+        let half_pi = T::from(core::f64::consts::FRAC_PI_2).unwrap();
+        let (a, b, c) = self.normalized_coeffs();
+        match b.atan2(a) {
+            theta if theta >= half_pi => (theta - pi, c + T::zero()),
+            theta => (theta, -c + T::zero()),
+        }
+    }
+
+    /// Returns the line `x·cos θ + y·sin θ = ρ` given in Hesse normal form, as returned by
+    /// `to_polar`. Any angle is accepted.
+    pub fn from_polar(theta: T, rho: T) -> Line<T> {
+        let (sin, cos) = theta.sin_cos();
+        Line::new(cos, sin, -rho)
+    }
+
+    /// Returns the line as a JSON object `{"a": a, "b": b, "c": c}` with the coefficients as given,
+    /// not scaled to a unit normal. Requires the `serde` feature.
+    ///
+    /// This shape is the crate's stable wire format for lines, independent of the layout of the
+    /// struct and of its `Serialize` implementation.
+    #[cfg(feature = "serde")]
+    pub fn to_json_value(&self) -> serde_json::Value {
+        serde_json::json!({ "a": to_f64(self.a), "b": to_f64(self.b), "c": to_f64(self.c) })
+    }
+
+    /// Returns the distance between this line and a parallel `other`, or `None` if the lines
+    /// aren't parallel within floating-point tolerance or either isn't valid.
+    pub fn distance_to_parallel(&self, other: &Line<T>) -> Option<T> {
+        if !self.is_valid() || !other.is_valid() {
+            return None;
+        }
+
+        let n1 = self.a.hypot(self.b);
+        let n2 = other.a.hypot(other.b);
+
+        // Cross product of the unit normals, which vanishes for parallel lines.
+        let cross = (self.a * other.b - other.a * self.b) / (n1 * n2);
+        if !util::floats_equal_toler(cross, T::zero()) {
+            return None;
+        }
+
+        // The normals may point in opposite directions; flip `other` to match before comparing offsets.
+        let same_direction = self.a * other.a + self.b * other.b > T::zero();
+        let c2 = if same_direction { other.c } else { -other.c };
+
+        Some((self.c / n1 - c2 / n2).abs())
+    }
+
+    /// Returns the orientation of the line in radians, in `[0, π)`.
+    ///
+    /// Horizontal lines have angle 0 and vertical lines π/2, regardless of the sign or
+    /// scale of the coefficients.
+    pub fn angle_rad(&self) -> T {
+        let pi = T::from(core::f64::consts::PI).unwrap();
+
+        // The line's direction is perpendicular to its normal (a, b).
+        let mut angle = (-self.a).atan2(self.b);
+        if angle < T::zero() {
+            angle = angle + pi;
+        }
+        if angle >= pi {
+            angle = angle - pi;
+        }
+
+        angle
+    }
+
+    /// Returns the orientation of the line in degrees, in `[0, 180)`.
+    pub fn angle_deg(&self) -> T {
+        self.angle_rad().to_degrees()
+    }
+
+    /// Checks whether this line and `other` are the same geometric line, within `tol`.
+    ///
+    /// Like `==`, this compares the coefficients after scaling both lines to a unit normal, so
+    /// lines written with different scaling or sign (e.g. `2x - 2 = 0` and `-x + 1 = 0`) are
+    /// equal. Unlike `==`, it takes an explicit tolerance, and it also compares the coefficients
+    /// with their signs flipped, so lines whose `a` is within the tolerance of zero but of opposite
+    /// signs are equal too.
+    pub fn approx_eq(&self, other: &Line<T>, tol: T) -> bool {
+        let [a1, b1, c1] = self.unit_coefficients();
+        let [a2, b2, c2] = other.unit_coefficients();
+
+        let same = (a1 - a2).abs() <= tol && (b1 - b2).abs() <= tol && (c1 - c2).abs() <= tol;
+        let opposite = (a1 + a2).abs() <= tol && (b1 + b2).abs() <= tol && (c1 + c2).abs() <= tol;
+        same || opposite
+    }
+
+    /// Checks whether this line and `other` are the same axis within `angle_tol` radians in the
+    /// angle of their normals and `dist_tol` in their distance from the origin, comparing their
+    /// polar forms (see `to_polar`).
+    ///
+    /// Unlike `==` and `approx_eq`, which compare the coefficients against one tolerance, this
+    /// separates the two ways lines can differ, e.g. to accept noisy orientations of axes through a
+    /// precisely known center. Normals on either side of the cut of the polar form's range are
+    /// compared across it. Lines that aren't valid (see `is_valid`) are the same as no line.
+    pub fn same_line_as(&self, other: &Line<T>, angle_tol: T, dist_tol: T) -> bool {
+        if !self.is_valid() || !other.is_valid() {
+            return false;
+        }
+
+        let pi = T::from(core::f64::consts::PI).unwrap();
+        let half_pi = T::from(core::f64::consts::FRAC_PI_2).unwrap();
+        let ((theta1, rho1), (theta2, rho2)) = (self.to_polar(), other.to_polar());
+        // Normals at opposite ends of the range are nearly opposite, so one offset changes sign.
+        let (between, rho2) = match (theta1 - theta2).abs() {
+            between if between > half_pi => (pi - between, -rho2),
+            between => (between, rho2),
+        };
+        between <= angle_tol && (rho1 - rho2).abs() <= dist_tol
+    }
+
+    /// Returns the coefficients scaled so that `(a, b)` is a unit vector with a canonical sign:
+    /// `a` is positive, or `b` is if the scaled `a` is within `TOLERANCE` of zero.
+    ///
+    /// A line and its negation, e.g. `x - 1 = 0` and `-x + 1 = 0`, thus have the same unit
+    /// coefficients. The sign is chosen after scaling, so that it doesn't depend on the magnitude
+    /// of the coefficients.
+    fn unit_coefficients(&self) -> [T; 3] {
+        let norm = self.a.hypot(self.b);
+        let leading = if (self.a / norm).abs() > T::TOLERANCE { self.a } else { self.b };
+        let norm = norm.copysign(leading);
+        [self.a / norm, self.b / norm, self.c / norm]
+    }
+
+    /// Returns the coefficients compared and hashed by `==` and `Hash`: the unit coefficients of
+    /// a valid line, so that their magnitude is bounded, or the raw coefficients otherwise.
+    fn comparison_coefficients(&self) -> [T; 3] {
+        if self.is_unit() {
+            self.to_array()
+        } else if self.is_valid() {
+            self.unit_coefficients()
+        } else {
+            self.to_array()
+        }
+    }
+
+    /// Checks whether the coefficients are already their unit coefficients, up to rounding, so
+    /// that comparisons can skip the scaling.
+    fn is_unit(&self) -> bool {
+        let four_ulps = T::epsilon() * T::from(4).unwrap();
+        (self.a * self.a + self.b * self.b - T::one()).abs() <= four_ulps && self.has_canonical_sign()
+    }
+
+    /// Checks whether the sign of the coefficients is the canonical one of `unit_coefficients`,
+    /// for a line whose normal is already a unit vector.
+    fn has_canonical_sign(&self) -> bool {
+        let leading = if self.a.abs() > T::TOLERANCE { self.a } else { self.b };
+        leading > T::zero()
+    }
+
+    /// Scales the coefficients in place to the line's unit coefficients, as by `normalized_coeffs`,
+    /// so that comparing and hashing the line later skips the scaling, e.g. for lines kept in a set
+    /// that is queried often. An invalid line (see `is_valid`) is left unchanged.
+    pub fn normalize_in_place(&mut self) {
+        if self.is_valid() {
+            [self.a, self.b, self.c] = self.unit_coefficients();
+        }
+    }
+
+    /// Returns the acute angle between this line and `other` in radians, in `[0, π/2]`.
+    pub fn angle_between(&self, other: &Line<T>) -> T {
+        let pi = T::from(core::f64::consts::PI).unwrap();
+        let diff = (self.angle_rad() - other.angle_rad()).abs();
+        diff.min(pi - diff)
+    }
+
+    /// Returns the line with its coefficients snapped to small integer ratios.
+    ///
+    /// The coefficients are scaled so that the larger of `a` and `b` in magnitude is 1, then each
+    /// is replaced by the closest fraction with a denominator of at most `max_denom`, and the
+    /// fractions are scaled to coprime integers with a positive leading coefficient. For example,
+    /// a bisector computed as `1.9999999x - 2.0000001 = 0` becomes `x - 1 = 0`.
+    ///
+    /// This is meant for lines with rational coefficients, such as the axes of integer-coordinate
+    /// inputs; snapping moves a line whose coefficients aren't close to small ratios. Lines that
+    /// aren't valid (see `is_valid`), or whose scaled coefficients overflow, are returned
+    /// unchanged. A `max_denom` of 0 is taken as 1.
+    pub fn rationalize(&self, max_denom: u64) -> Line<T> {
+        if !self.is_valid() {
+            return *self;
+        }
+
+        let [a, b, c] = self.to_array().map(to_f64);
+        let lead = if a.abs() >= b.abs() { a } else { b };
+        let scaled = [a / lead, b / lead, c / lead].map(|x| closest_fraction(x, max_denom.max(1)));
+        let [Some(fa), Some(fb), Some(fc)] = scaled else {
+            return *self;
+        };
+        let fractions = [fa, fb, fc];
+
+        let denom = fractions.iter().fold(1, |l, &(_, q)| l / gcd(l, q) * q);
+        let numers = fractions.map(|(p, q)| p * (denom / q) as i128);
+        let divisor = numers.iter().fold(0, |g, &p| gcd(g, p.unsigned_abs())) as i128;
+        let [a, b, c] = numers.map(|p| T::from((p / divisor) as f64).unwrap());
+
+        Line::new(a, b, c)
+    }
+
+    /// Returns the line snapped to discrete orientations and offsets, e.g. to bin the axes of many
+    /// shapes into a histogram: the angle of the unit normal, as in `normalized_coeffs`, is
+    /// rounded to a multiple of `angle_step` radians, and the signed distance of the line from the
+    /// origin to a multiple of `offset_step`.
+    ///
+    /// Nearby lines thus quantize to identical coefficients, with `(a, b)` a unit vector, unless a
+    /// bucket boundary falls between them. Lines that aren't valid (see `is_valid`), and steps that
+    /// aren't positive and finite, leave the line unchanged.
+    pub fn quantized(&self, angle_step: T, offset_step: T) -> Line<T> {
+        let positive = |step: T| step > T::zero() && step.is_finite();
+        if !self.is_valid() || !positive(angle_step) || !positive(offset_step) {
+            return *self;
+        }
+
+        let (a, b, c) = self.normalized_coeffs();
+        let angle = (b.atan2(a) / angle_step).round() * angle_step;
+        let (sin, cos) = angle.sin_cos();
+        Line::new(cos, sin, (c / offset_step).round() * offset_step)
     }
 }
 
-impl PartialEq for Line {
+/// Returns the fraction `p / q` closest to `x` with `1 <= q <= max_denom`, found from the continued
+/// fraction expansion of `x`, or `None` if `x` isn't finite or `max_denom` is 0.
+fn closest_fraction(x: f64, max_denom: u64) -> Option<(i128, u128)> {
+    if !x.is_finite() || max_denom == 0 {
+        return None;
+    }
+    let max_denom = max_denom as u128;
+    let r0 = Float::abs(x);
+
+    // The last two convergents, starting from the conventional 0/1 and 1/0.
+    let (mut p0, mut q0, mut p1, mut q1) = (0u128, 1u128, 1u128, 0u128);
+    let mut r = r0;
+    loop {
+        let whole = Float::floor(r);
+        let term = whole as u128;
+        let q2 = q0.saturating_add(term.saturating_mul(q1));
+        if q2 > max_denom {
+            break;
+        }
+        (p0, q0, p1, q1) = (p1, q1, p0.saturating_add(term.saturating_mul(p1)), q2);
+
+        let frac = r - whole;
+        if frac == 0. {
+            break;
+        }
+        r = 1. / frac;
+    }
+
+    // The closest fraction is the last convergent or the largest semiconvergent before it.
+    let k = (max_denom - q0) / q1;
+    let (ps, qs) = (p0 + k * p1, q0 + k * q1);
+    let error = |p: u128, q: u128| Float::abs(r0 - p as f64 / q as f64);
+    let (p, q) = if error(p1, q1) <= error(ps, qs) { (p1, q1) } else { (ps, qs) };
+
+    let p = p as i128;
+    Some((if x < 0. { -p } else { p }, q))
+}
+
+/// Returns the greatest common divisor of `a` and `b`.
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Lines are compared by their coefficients scaled to a unit normal with a canonical sign, within
+/// tolerance, so differently scaled or negated equations of the same line are equal. Lines with a
+/// non-finite coefficient are equal to no line, including themselves.
+impl<T: Scalar> PartialEq for Line<T> {
     fn eq(&self, other: &Self) -> bool {
-        util::float_partial_cmp_tolerance(&self.a, &other.a) == Some(Ordering::Equal)
-            && util::float_partial_cmp_tolerance(&self.b, &other.b) == Some(Ordering::Equal)
-            && util::float_partial_cmp_tolerance(&self.c, &other.c) == Some(Ordering::Equal)
+        if !self.is_finite() || !other.is_finite() {
+            return false;
+        }
+
+        let (lhs, rhs) = (self.comparison_coefficients(), other.comparison_coefficients());
+        lhs.iter().zip(&rhs).all(|(x, y)| util::float_partial_cmp_tolerance(x, y) == Some(Ordering::Equal))
     }
 }
 
-impl Eq for Line {}
+impl<T: Scalar> Eq for Line<T> {}
 
-impl Hash for Line {
+impl<T: Scalar> Hash for Line<T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        let round = |x: f64| (x / config::EPSILON).round() * config::EPSILON;
-        round(self.a).to_bits().hash(state);
-        round(self.b).to_bits().hash(state);
-        round(self.c).to_bits().hash(state);
+        /*
+         * The scaled coefficients are hashed, so that the grid is as fine relative to them as the
+         * tolerance of `==`, which it wouldn't be for the large coefficients of lines from distant
+         * pairs of points. Equal lines hash differently if they straddle a grid line, so the grid
+         * is a thousand times the tolerance: lines computed from different pairs of points differ
+         * by far less than that, and lines sharing a hash but unequal only cost an `==`.
+         */
+        let grid = 1000.0 * to_f64(T::TOLERANCE);
+        // Adding zero turns a negative zero, which has other bits than zero, into zero.
+        let round = |x: T| Float::round(to_f64(x) / grid) * grid + 0.0;
+        for x in self.comparison_coefficients() {
+            round(x).to_bits().hash(state);
+        }
+    }
+}
+
+/// The side of a line a point lies on, as returned by `Line::side_of`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+    On,
+}
+
+/// Returns the reflection of `p` across the x-axis.
+pub fn reflect_over_x<T: Scalar>(p: &Point<T>) -> Point<T> {
+    Point::new(p.x, -p.y)
+}
+
+/// Returns the reflection of `p` across the y-axis.
+pub fn reflect_over_y<T: Scalar>(p: &Point<T>) -> Point<T> {
+    Point::new(-p.x, p.y)
+}
+
+/// Returns the reflection of `p` across the horizontal line at height `y`.
+pub fn reflect_over_horizontal<T: Scalar>(p: &Point<T>, y: T) -> Point<T> {
+    Point::new(p.x, y + y - p.y)
+}
+
+/// Returns the reflection of `p` across the vertical line at `x`.
+pub fn reflect_over_vertical<T: Scalar>(p: &Point<T>, x: T) -> Point<T> {
+    Point::new(x + x - p.x, p.y)
+}
+
+/// Returns the reflection of `p` across the line through `a` and `b`, or `None` if `a` and `b`
+/// coincide (see `Line::through_points`) or the reflection's coordinates aren't finite.
+pub fn reflect_point_across_points<T: Scalar>(p: &Point<T>, a: &Point<T>, b: &Point<T>) -> Option<Point<T>> {
+    Line::through_points(a, b)?.try_reflected_point(p)
+}
+
+/// Returns the reflection of `p` across the perpendicular bisector of `a` and `b`, the line that
+/// swaps `a` and `b`, without building the line.
+///
+/// This is the reflection a search checks for every point against the candidate axis of a pair
+/// of points. Returns `SymmError::DegenerateLine` if `a` and `b` coincide within tolerance, so that
+/// the bisector is undefined, or `SymmError::NonFinitePoint` if the reflection's coordinates
+/// aren't finite.
+pub fn reflect_across_bisector<T: Scalar>(
+    p: &Point<T>,
+    a: &Point<T>,
+    b: &Point<T>,
+) -> Result<Point<T>, SymmError> {
+    if a == b {
+        return Err(SymmError::DegenerateLine);
+    }
+
+    // p - 2 ((p - m) · n / |n|²) n, with the normal n = b - a and the midpoint m of a and b.
+    let half = T::one() / (T::one() + T::one());
+    let (nx, ny) = (b.x - a.x, b.y - a.y);
+    let (mx, my) = (half * (a.x + b.x), half * (a.y + b.y));
+    let scale = ((p.x - mx) * nx + (p.y - my) * ny) / (nx * nx + ny * ny);
+    Point::from_array([p.x - (scale + scale) * nx, p.y - (scale + scale) * ny])
+}
+
+/// A directed line segment between two points.
+#[derive(Debug, PartialEq)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Segment {
+    pub fn new(start: Point, end: Point) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns the reflection of this segment across `line`, keeping the direction of traversal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `line` isn't valid (see `Line::is_valid`).
+    pub fn reflect_across(&self, line: &Line) -> Segment {
+        Segment::new(line.get_reflected_point(&self.start), line.get_reflected_point(&self.end))
+    }
+
+    /// Returns the length of the segment.
+    pub fn length(&self) -> f64 {
+        self.start.distance(&self.end)
+    }
+
+    /// Returns the midpoint of the segment.
+    pub fn midpoint(&self) -> Point {
+        Point::new(0.5 * (self.start.x + self.end.x), 0.5 * (self.start.y + self.end.y))
+    }
+
+    /// Returns the line containing the segment.
+    ///
+    /// The result is degenerate (a = b = 0) if the segment has zero length.
+    pub fn to_line(&self) -> Line {
+        alg::get_through_line(&self.start, &self.end)
     }
 }
 
 /// An unordered pair of points, used for symmetry calculations.
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
-pub struct UnorderedPointPair<'a> {
-    pub p1: &'a Point,
-    pub p2: &'a Point,
+#[derive(Debug, Clone)]
+pub struct UnorderedPointPair<'a, T = f64> {
+    pub p1: &'a Point<T>,
+    pub p2: &'a Point<T>,
+}
+
+impl<T: Scalar> PartialEq for UnorderedPointPair<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.p1 == other.p1 && self.p2 == other.p2
+    }
+}
+
+impl<T: Scalar> Eq for UnorderedPointPair<'_, T> {}
+
+impl<T: Scalar> Hash for UnorderedPointPair<'_, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.p1.hash(state);
+        self.p2.hash(state);
+    }
 }
 
-impl<'a> UnorderedPointPair<'a> {
+impl<'a, T: Scalar> UnorderedPointPair<'a, T> {
     /// Constructs a new unordered pair, ordering the points canonically.
-    pub fn new(p1: &'a Point, p2: &'a Point) -> Self {
+    pub fn new(p1: &'a Point<T>, p2: &'a Point<T>) -> Self {
         if p1 <= p2 {
             Self { p1, p2 }
         } else {
             Self { p1: p2, p2: p1 }
         }
     }
+
+    /// Returns the two points, in canonical order.
+    pub fn points(&self) -> (&'a Point<T>, &'a Point<T>) {
+        (self.p1, self.p2)
+    }
+
+    /// Copies the points into an `OwnedPointPair`, which doesn't borrow from the input set.
+    pub fn into_owned(self) -> OwnedPointPair<T> {
+        OwnedPointPair { p1: *self.p1, p2: *self.p2 }
+    }
+}
+
+/// An unordered pair of points that owns its points, unlike `UnorderedPointPair`, so it can be
+/// stored beyond the lifetime of the set the points came from, e.g. in caches.
+#[derive(Debug, Clone, Copy)]
+pub struct OwnedPointPair<T = f64> {
+    pub p1: Point<T>,
+    pub p2: Point<T>,
+}
+
+impl<T: Scalar> PartialEq for OwnedPointPair<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.p1 == other.p1 && self.p2 == other.p2
+    }
+}
+
+impl<T: Scalar> Eq for OwnedPointPair<T> {}
+
+impl<T: Scalar> Hash for OwnedPointPair<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.p1.hash(state);
+        self.p2.hash(state);
+    }
+}
+
+impl<T: Scalar> OwnedPointPair<T> {
+    /// Constructs a new owned unordered pair, ordering the points canonically.
+    pub fn new(p1: Point<T>, p2: Point<T>) -> Self {
+        if p1 <= p2 {
+            Self { p1, p2 }
+        } else {
+            Self { p1: p2, p2: p1 }
+        }
+    }
+
+    /// Returns the two points, in canonical order.
+    pub fn points(&self) -> (Point<T>, Point<T>) {
+        (self.p1, self.p2)
+    }
+
+    /// Returns a pair borrowing the points of this one.
+    pub fn as_borrowed(&self) -> UnorderedPointPair<'_, T> {
+        UnorderedPointPair { p1: &self.p1, p2: &self.p2 }
+    }
+}
+
+impl<T: Scalar> From<UnorderedPointPair<'_, T>> for OwnedPointPair<T> {
+    fn from(pair: UnorderedPointPair<'_, T>) -> Self {
+        pair.into_owned()
+    }
+}
+
+/// Formats the pair as `{(x1,y1) - (x2,y2)}`.
+impl<T: Scalar + fmt::Display> fmt::Display for UnorderedPointPair<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{({},{}) - ({},{})}}", self.p1.x, self.p1.y, self.p2.x, self.p2.y)
+    }
+}
+
+/// Formats the pair like an `UnorderedPointPair`.
+impl<T: Scalar + fmt::Display> fmt::Display for OwnedPointPair<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_borrowed().fmt(f)
+    }
+}
+
+/// Accumulates points into a set, validating each one, and reports every invalid point at once.
+///
+/// A point with a NaN or infinite coordinate is an error. A point equal to one added earlier,
+/// within floating-point tolerance, is merged into it by default, or is an error if duplicates
+/// are rejected. Finding such a point compares it with each point added so far, as the hash
+/// lookup alone misses equal points whose coordinates differ by rounding.
+#[derive(Debug, Default)]
+pub struct PointSetBuilder<T = f64> {
+    points: HashSet<Point<T>>,
+    errors: Vec<SymmError>,
+    reject_duplicates: bool,
+}
+
+impl<T: Scalar> PointSetBuilder<T> {
+    /// Creates an empty builder that merges duplicates.
+    pub fn new() -> Self {
+        Self { points: HashSet::default(), errors: Vec::new(), reject_duplicates: false }
+    }
+
+    /// Sets whether a point equal to an earlier one is an error (`SymmError::DuplicatePoint`)
+    /// rather than merged into it.
+    pub fn reject_duplicates(mut self, reject: bool) -> Self {
+        self.reject_duplicates = reject;
+        self
+    }
+
+    /// Adds the point with the given coordinates, recording an error if it is invalid.
+    pub fn add(&mut self, x: T, y: T) -> &mut Self {
+        match Point::from_array([x, y]) {
+            Ok(p) if self.points.contains(&p) || self.points.iter().any(|q| *q == p) => {
+                if self.reject_duplicates {
+                    self.errors.push(SymmError::DuplicatePoint);
+                }
+            }
+            Ok(p) => {
+                self.points.insert(p);
+            }
+            Err(e) => self.errors.push(e),
+        }
+        self
+    }
+
+    /// Adds each point of `points` as by `add`.
+    pub fn extend<I: IntoIterator<Item = Point<T>>>(&mut self, points: I) -> &mut Self {
+        for p in points {
+            self.add(p.x, p.y);
+        }
+        self
+    }
+
+    /// Returns the set of points, or every error recorded while adding them, in order.
+    pub fn build(self) -> Result<HashSet<Point<T>>, Vec<SymmError>> {
+        if self.errors.is_empty() {
+            Ok(self.points)
+        } else {
+            Err(self.errors)
+        }
+    }
+}
+
+/// Returns the set of points whose coordinates are interleaved in `coords` as `x0, y0, x1, y1, ...`,
+/// e.g. from a flat array of numeric or foreign code.
+///
+/// Returns `SymmError::Parse` if `coords` has an odd length, or `SymmError::NonFinitePoint` if a
+/// coordinate is NaN or infinite. Points with identical coordinates are merged, but points equal
+/// within tolerance and not identical usually aren't, as `Point` hashes the exact bits of its
+/// coordinates. Use `PointSetBuilder` to merge those reliably.
+pub fn points_from_slice<T: Scalar>(coords: &[T]) -> Result<HashSet<Point<T>>, SymmError> {
+    if !coords.len().is_multiple_of(2) {
+        return Err(SymmError::Parse(alloc::format!("odd number of coordinates: {}", coords.len())));
+    }
+
+    coords.chunks_exact(2).map(|xy| Point::from_array([xy[0], xy[1]])).collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashSet;
+    use crate::alg::perpendicular_bisector;
+    use crate::collections::{DefaultHashBuilder, HashSet};
+    use crate::config;
+    use core::hash::BuildHasher;
+    use alloc::vec::Vec;
+    use proptest::prelude::*;
+
+    /// Points with finite coordinates of moderate magnitude.
+    impl Arbitrary for Point {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: ()) -> Self::Strategy {
+            (-1e3..1e3, -1e3..1e3).prop_map(|(x, y)| Point::new(x, y)).boxed()
+        }
+    }
+
+    /// Valid lines, with normals long enough that reflections stay within a few orders of
+    /// magnitude of the points.
+    impl Arbitrary for Line {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: ()) -> Self::Strategy {
+            (-10.0..10.0, -10.0..10.0, -100.0..100.0)
+                .prop_map(|(a, b, c)| Line::new(a, b, c))
+                .prop_filter("normal too short", |line: &Line| line.a.hypot(line.b) >= 1e-3)
+                .boxed()
+        }
+    }
+
+    proptest! {
+        /// Reflecting a point twice across a valid line returns it, within tolerance.
+        #[test]
+        fn prop_reflection_is_involution(p in any::<Point>(), line in any::<Line>()) {
+            prop_assert!(line.is_valid());
+            let twice = line.get_reflected_point(&line.get_reflected_point(&p));
+            prop_assert_eq!(twice, p);
+        }
+    }
 
     /// Tests that lines which are equal within the configured floating-point tolerance
     /// are treated as equal by both hashing and equality, and that lines differing by
@@ -149,10 +1085,11 @@ mod tests {
         let l3 = Line::new(1.0, 2.0 + config::EPSILON / 10.0, 3.0);
         let l4 = Line::new(1.0, 2.0, 3.0 + config::EPSILON / 10.0);
 
-        // Create lines that should be different (beyond EPSILON)
-        let l5 = Line::new(1.0 + config::EPSILON, 2.0, 3.0);
-        let l6 = Line::new(1.0, 2.0 + config::EPSILON, 3.0);
-        let l7 = Line::new(1.0, 2.0, 3.0 + config::EPSILON);
+        // Create lines that should be different (beyond EPSILON once scaled to a unit normal,
+        // which divides these coefficients by about 2.24)
+        let l5 = Line::new(1.0 + 10.0 * config::EPSILON, 2.0, 3.0);
+        let l6 = Line::new(1.0, 2.0 + 10.0 * config::EPSILON, 3.0);
+        let l7 = Line::new(1.0, 2.0, 3.0 + 10.0 * config::EPSILON);
 
         // Create a HashSet to test hashing
         let mut set = HashSet::new();
@@ -170,6 +1107,27 @@ mod tests {
         assert_eq!(set.len(), 4); // l1-l4 should be one entry, l5-l7 should be separate
     }
 
+    /// Tests that the same axis generated from a near pair and a far pair of points, with
+    /// coefficients three orders of magnitude apart, is equal, hashes equal, and is stored once.
+    #[test]
+    fn test_line_hash_after_scaling() {
+        let near = perpendicular_bisector(&Point::new(0.5, 0.), &Point::new(1.5, 0.)).unwrap();
+        let far = perpendicular_bisector(&Point::new(-999., 5.), &Point::new(1001., 5.)).unwrap();
+        assert!((far.a / near.a - 2000.).abs() < 1e-9);
+
+        let hasher = DefaultHashBuilder::default();
+        assert_eq!(near, far);
+        assert_eq!(hasher.hash_one(near), hasher.hash_one(far));
+        assert_eq!(hasher.hash_one(Line::new(2., 0., -2.)), hasher.hash_one(Line::new(1., 0., -1.)));
+
+        let set: HashSet<Line> = [near, far].into_iter().collect();
+        assert_eq!(set.len(), 1);
+
+        // Invalid lines have no unit normal and are compared as written.
+        assert_eq!(Line::new(0., 0., 1.), Line::new(0., 0., 1.));
+        assert_ne!(Line::new(0., 0., 1.), Line::new(0., 0., 2.));
+    }
+
     /// Tests the custom `PartialOrd` implementation for `Point`.
     /// Ensures that point ordering is robust to floating-point imprecision and behaves as intended.
     #[test]
@@ -212,4 +1170,573 @@ mod tests {
         assert!(p9 <= p10);
         assert!(p9 >= p10);
     }
+
+    /// Tests that points and lines round-trip through arrays, and that non-finite
+    /// coordinates are rejected.
+    #[test]
+    fn test_array_round_trip() {
+        let p = Point::new(3.5, -1.0);
+        assert_eq!(p.to_array(), [3.5, -1.0]);
+        assert_eq!(Point::from_array(p.to_array()).unwrap(), p);
+
+        assert!(matches!(Point::from_array([f64::NAN, 0.0]), Err(SymmError::NonFinitePoint)));
+        assert!(matches!(Point::from_array([0.0, f64::INFINITY]), Err(SymmError::NonFinitePoint)));
+
+        let line = Line::new(1.0, 2.0, -3.0);
+        let [a, b, c] = line.to_array();
+        assert_eq!(Line::new(a, b, c), line);
+    }
+
+    /// Tests the distance between two horizontal lines, and that non-parallel lines have none.
+    #[test]
+    fn test_distance_to_parallel() {
+        // y = 1 and y = 4, the second written with a flipped, scaled normal.
+        let l1: Line = Line::new(0., 1., -1.);
+        let l2: Line = Line::new(0., -2., 8.);
+        assert!((l1.distance_to_parallel(&l2).unwrap() - 3.).abs() < 1e-9);
+        assert!((l2.distance_to_parallel(&l1).unwrap() - 3.).abs() < 1e-9);
+        assert!(l1.distance_to_parallel(&l1).unwrap().abs() < 1e-9);
+
+        let diagonal = Line::new(1., -1., 0.);
+        assert_eq!(l1.distance_to_parallel(&diagonal), None);
+    }
+
+    /// Tests line orientations, including lines whose coefficients have flipped signs.
+    #[test]
+    fn test_line_angle() {
+        let horizontal: Line = Line::new(0., 1., -2.);
+        let vertical: Line = Line::new(1., 0., -2.);
+        assert!(horizontal.angle_rad().abs() < 1e-9);
+        assert!(Line::<f64>::new(0., -1., 2.).angle_rad().abs() < 1e-9);
+        assert!((vertical.angle_rad() - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+        assert!((Line::<f64>::new(-1., 0., 2.).angle_rad() - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+
+        // y = x and y = -x.
+        assert!((Line::<f64>::new(1., -1., 0.).angle_deg() - 45.).abs() < 1e-9);
+        assert!((Line::<f64>::new(1., 1., 0.).angle_deg() - 135.).abs() < 1e-9);
+    }
+
+    /// Tests that a square's axes, sorted by orientation, are evenly spaced by 45°, and that
+    /// the angle between lines is acute.
+    #[test]
+    fn test_angle_between() {
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+        ]);
+        let mut axes: Vec<Line> = alg::get_lines_of_sym(&square, None).unwrap().into_iter().collect();
+        axes.sort_by(|l1, l2| l1.angle_rad().partial_cmp(&l2.angle_rad()).unwrap());
+        assert_eq!(axes.len(), 4);
+
+        for i in 0..axes.len() {
+            let next = &axes[(i + 1) % axes.len()];
+            assert!((axes[i].angle_between(next) - std::f64::consts::FRAC_PI_4).abs() < 1e-9);
+        }
+
+        // Lines at 10° and 170° meet at 20°, not 160°.
+        let l1: Line = Line::new(-(10f64.to_radians().sin()), 10f64.to_radians().cos(), 0.);
+        let l2: Line = Line::new(-(170f64.to_radians().sin()), 170f64.to_radians().cos(), 0.);
+        assert!((l1.angle_between(&l2).to_degrees() - 20.).abs() < 1e-9);
+    }
+
+    /// Tests that lines differing only in scale or sign are approximately equal.
+    #[test]
+    fn test_line_approx_eq() {
+        let line: Line = Line::new(1., 0., -1.);
+        assert!(Line::new(2., 0., -2.).approx_eq(&line, 1e-9));
+        assert!(Line::new(-3., 0., 3.).approx_eq(&line, 1e-9));
+        assert!(!Line::new(1., 0., -1.1).approx_eq(&line, 1e-9));
+        assert!(Line::new(1., 0., -1.1).approx_eq(&line, 0.2));
+        assert!(!Line::new(1., 1., -1.).approx_eq(&line, 1e-9));
+    }
+
+    /// Tests that reflections with non-finite results, or across degenerate lines, are rejected.
+    #[test]
+    fn test_try_reflected_point() {
+        let line: Line = Line::new(1., 0., -1.);
+        assert_eq!(line.try_reflected_point(&Point::new(3., 2.)), Some(Point::new(-1., 2.)));
+
+        let huge: Line = Line::new(f64::MAX, f64::MAX, 0.);
+        assert_eq!(huge.try_reflected_point(&Point::new(1e300, 1e300)), None);
+        assert_eq!(Line::new(0., 0., 1.).try_reflected_point(&Point::new(1., 1.)), None);
+    }
+
+    /// Tests that lines without a usable normal are invalid, and that the guarded methods reject them.
+    #[test]
+    fn test_line_is_valid() {
+        let degenerate: Line = Line::new(0.0, 0.0, 5.0);
+        assert!(!degenerate.is_valid());
+        assert!(!Line::new(0.1 * config::EPSILON, 0., 1.).is_valid());
+        assert!(Line::new(1e3 * config::EPSILON, 0., 1.).is_valid());
+        assert!(Line::<f64>::new(0., 2., -1.).is_valid());
+
+        assert_eq!(degenerate.distance_to_parallel(&Line::new(0., 1., 0.)), None);
+        let result = std::panic::catch_unwind(|| degenerate.get_reflected_point(&Point::new(1., 1.)));
+        assert!(result.is_err());
+        let checked = degenerate.checked_reflected_point(&Point::new(1., 1.));
+        assert!(matches!(checked, Err(SymmError::DegenerateLine)));
+
+        let overflowing = Line::new(1., 0., -f64::MAX).checked_reflected_point(&Point::new(-f64::MAX, 0.));
+        assert!(matches!(overflowing, Err(SymmError::NonFinitePoint)));
+        let reflected = Line::new(1., 0., -1.).checked_reflected_point(&Point::new(0., 3.)).unwrap();
+        assert_eq!(reflected, Point::new(2., 3.));
+    }
+
+    /// Tests that `try_new` accepts well-defined lines and rejects non-finite and degenerate ones.
+    #[test]
+    fn test_try_new() {
+        assert_eq!(Line::try_new(1., -1., 0.5).unwrap(), Line::new(1., -1., 0.5));
+        assert_eq!(Line::try_new(0., 2., 0.).unwrap().to_array(), [0., 2., 0.]);
+
+        for (a, b, c) in [(f64::NAN, 1., 0.), (1., f64::INFINITY, 0.), (1., 0., f64::NEG_INFINITY)] {
+            assert!(matches!(Line::try_new(a, b, c), Err(SymmError::NonFiniteLine)));
+        }
+        assert!(matches!(Line::try_new(0., 0., 1.), Err(SymmError::DegenerateLine)));
+        assert!(matches!(Line::try_new(1e-12, -1e-12, 1.), Err(SymmError::DegenerateLine)));
+    }
+
+    /// Tests that a line and its negation are equal and hash alike, so a set keeps only one.
+    #[test]
+    fn test_negated_line_deduplicates() {
+        let hasher = DefaultHashBuilder::default();
+        for (a, b, c) in [(1., -1., 0.5), (0., 2., -3.), (-3., 0., 1.), (1e-12, -1., 2.)] {
+            let line = Line::new(a, b, c);
+            let negated = Line::new(-a, -b, -c);
+            assert_eq!(line, negated);
+            assert_eq!(hasher.hash_one(line), hasher.hash_one(negated));
+
+            let set: HashSet<Line> = [line, negated].into_iter().collect();
+            assert_eq!(set.len(), 1);
+        }
+
+        // Scaling by a negative factor gives zero the other sign.
+        assert_eq!(hasher.hash_one(Line::new(-2., 2., 0.)), hasher.hash_one(Line::new(1., -1., 0.)));
+
+        // The same nearly horizontal line at two scales, with `a` above the tolerance only in the
+        // larger one, takes the same sign.
+        let (small, large) = (Line::new(-1e-10, 1., 0.), Line::new(-1e-7, 1e3, 0.));
+        assert_eq!(small, large);
+        assert_eq!(hasher.hash_one(small), hasher.hash_one(large));
+        assert_eq!(small.normalized_coeffs().1, 1.);
+        assert_eq!(large.normalized_coeffs().1, 1.);
+    }
+
+    /// Tests that proportional lines have identical canonical coefficients.
+    #[test]
+    fn test_normalized_coeffs() {
+        let expected = Line::new(1., -2., 3.).normalized_coeffs();
+        for factor in [2., -0.5, -4.] {
+            let scaled = Line::new(factor, -2. * factor, 3. * factor);
+            assert_eq!(scaled.normalized_coeffs(), expected);
+        }
+        let (a, b, c) = expected;
+        assert!(a > 0.);
+        assert!(util::floats_equal_toler(a.hypot(b), 1.));
+        assert!(util::floats_equal_toler(c, 3. / Float::sqrt(5f64)));
+
+        // A horizontal line's normal is made to point up, and zero has a single sign.
+        let (a, b, c) = Line::new(0., -3., 0.).normalized_coeffs();
+        assert_eq!((a, b, c), (0., 1., 0.));
+        assert!(a.is_sign_positive() && c.is_sign_positive());
+
+        assert_eq!(Line::new(0., 0., 2.).normalized_coeffs(), (0., 0., 2.));
+    }
+
+    /// Tests that normalizing a line in place gives its unit coefficients, leaving it equal, with
+    /// the same hash, to its other equations, and that changing the coefficients afterwards is seen
+    /// by `==`.
+    #[test]
+    fn test_normalize_in_place() {
+        let mut line = Line::new(-2., 0., 2.);
+        let original = line;
+        line.normalize_in_place();
+        assert_eq!(line.to_array(), [1., 0., -1.]);
+        assert_eq!(line, original);
+        let set: HashSet<Line> = [original].into_iter().collect();
+        assert!(set.contains(&line));
+
+        line.c = -2.;
+        assert_eq!(line, Line::new(2., 0., -4.));
+        assert_ne!(line, original);
+
+        let mut degenerate = Line::new(0., 0., 3.);
+        degenerate.normalize_in_place();
+        assert_eq!(degenerate.to_array(), [0., 0., 3.]);
+    }
+
+    /// Tests that points and lines with a non-finite coordinate or coefficient equal nothing.
+    #[test]
+    #[allow(clippy::eq_op)]
+    fn test_non_finite_equality() {
+        let nan_line: Line = Line { a: f64::NAN, b: 1., c: 0. };
+        assert!(!nan_line.is_finite());
+        assert!(nan_line != nan_line);
+        assert!(nan_line != Line::new(1., 1., 0.));
+        assert!(Line::new(0., 1., f64::INFINITY) != Line::new(0., 1., f64::INFINITY));
+        assert!(Line::new(0., 1., 0.) == Line::new(0., 1., 0.));
+
+        let nan_point: Point = Point { x: f64::NAN, y: 0. };
+        assert!(!nan_point.is_finite());
+        assert!(nan_point != nan_point);
+        assert!(Point { x: f64::INFINITY, y: 0. } != Point { x: f64::INFINITY, y: 0. });
+        assert!(Point::new(0., 0.).is_finite());
+    }
+
+    /// Tests reflecting points across the line through two markers.
+    #[test]
+    fn test_reflect_point_across_points() {
+        let (a, b) = (Point::new(0., 1.), Point::new(2., 3.));
+        let line = Line::through_points(&a, &b).unwrap();
+        assert!(line.is_point_on_line(&a) && line.is_point_on_line(&b));
+        assert!(Line::through_points(&a, &a).is_none());
+
+        // The line through the markers is y = x + 1.
+        assert_eq!(reflect_point_across_points(&Point::new(3., 0.), &a, &b), Some(Point::new(-1., 4.)));
+        assert_eq!(reflect_point_across_points(&a, &a, &b), Some(a));
+        assert_eq!(reflect_point_across_points(&Point::new(3., 0.), &a, &a), None);
+    }
+
+    /// Tests that reflecting across a bisector directly matches reflecting across the bisector line.
+    #[test]
+    fn test_reflect_across_bisector() {
+        let (a, b) = (Point::new(1., 2.), Point::new(4., -1.));
+        let bisector = perpendicular_bisector(&a, &b).unwrap();
+        for p in [Point::new(0., 0.), Point::new(-3., 7.5), Point::new(2.5, 0.5), a, b] {
+            assert_eq!(reflect_across_bisector(&p, &a, &b).unwrap(), bisector.get_reflected_point(&p));
+        }
+        assert_eq!(reflect_across_bisector(&a, &a, &b).unwrap(), b);
+
+        assert!(matches!(reflect_across_bisector(&b, &a, &a), Err(SymmError::DegenerateLine)));
+        let far = Point::new(f64::MAX, 0.);
+        let overflowing = reflect_across_bisector(&far, &Point::new(0., 0.), &Point::new(1., 0.));
+        assert!(matches!(overflowing, Err(SymmError::NonFinitePoint)));
+    }
+
+    /// Tests that computed axes of an integer-coordinate square snap to small integer coefficients.
+    #[test]
+    fn test_rationalize() {
+        let corners = [Point::new(0., 0.), Point::new(2., 0.), Point::new(2., 2.), Point::new(0., 2.)];
+        let square = HashSet::from(corners);
+        let mut snapped: Vec<[f64; 3]> = alg::get_lines_of_sym(&square, None)
+            .unwrap()
+            .iter()
+            .map(|line| line.rationalize(10).to_array())
+            .collect();
+        snapped.sort_by(|l, r| l.partial_cmp(r).unwrap());
+        assert_eq!(snapped, [[0., 1., -1.], [1., -1., 0.], [1., 0., -1.], [1., 1., -2.]]);
+
+        let noisy: Line = Line::new(-1.9999999, 0., 2.0000001);
+        assert_eq!(noisy.rationalize(10).to_array(), [1., 0., -1.]);
+        let half: Line = Line::new(2.0000001, -1., -0.9999999);
+        assert_eq!(half.rationalize(10).to_array(), [2., -1., -1.]);
+        assert_eq!(Line::new(0., 0., 3.).rationalize(10).to_array(), [0., 0., 3.]);
+        assert_eq!(noisy.rationalize(0).to_array(), [1., 0., -1.]);
+
+        // The offset overflows once scaled by the small leading coefficient.
+        let steep: Line = Line::new(1e-3, 0., f64::MAX);
+        assert_eq!(steep.rationalize(10).to_array(), steep.to_array());
+
+        assert_eq!(closest_fraction(0.5, 10), Some((1, 2)));
+        assert_eq!(closest_fraction(f64::NAN, 10), None);
+        assert_eq!(closest_fraction(f64::NEG_INFINITY, 10), None);
+        assert_eq!(closest_fraction(0.5, 0), None);
+    }
+
+    /// Tests that membership within an explicit tolerance is by distance, whatever the scale of the
+    /// coefficients.
+    #[test]
+    fn test_is_point_on_line_tol() {
+        for scale in [1., 1000., -0.001] {
+            let line: Line = Line::new(0., scale, -2. * scale);
+            assert!(!line.is_point_on_line_tol(&Point::new(1., 2. + 1e-4), 1e-6));
+            assert!(line.is_point_on_line_tol(&Point::new(1., 2. + 1e-4), 1e-3));
+            assert!(line.is_point_on_line(&Point::new(-5., 2.)));
+        }
+
+        // The raw residual of the scaled line is a thousand times the distance.
+        let scaled: Line = Line::new(1000., 0., -2000.);
+        let near = Point::new(2. + 1e-4, 0.);
+        assert!(scaled.is_point_on_line_tol(&near, 1e-3) && !scaled.is_point_on_line_within(&near, 1e-3));
+        assert!(!Line::new(0., 0., 1.).is_point_on_line_tol(&near, 0.5));
+    }
+
+    /// Tests the polar forms of the diagonals and a horizontal line, and round-tripping them.
+    #[test]
+    fn test_polar() {
+        let (theta, rho) = Line::new(-3., 3., 0.).to_polar();
+        assert!(util::floats_equal_toler(theta, -core::f64::consts::FRAC_PI_4) && rho == 0.);
+
+        let diagonal: Line = Line::new(2., 2., -4.);
+        let (theta, rho) = diagonal.to_polar();
+        assert!(util::floats_equal_toler(theta, core::f64::consts::FRAC_PI_4));
+        assert!(util::floats_equal_toler(rho, Float::sqrt(2.)));
+        assert_eq!(Line::from_polar(theta, rho), diagonal);
+
+        let (theta, rho) = Line::new(0., 2., -1.).to_polar();
+        assert!(util::floats_equal_toler(theta, -core::f64::consts::FRAC_PI_2));
+        assert!(util::floats_equal_toler(rho, -0.5));
+        assert_eq!(Line::from_polar(theta, rho), Line::new(0., 1., -0.5));
+        assert!(Line::new(0., 0., 1.).to_polar().0.is_nan());
+    }
+
+    /// Tests telling apart axes that differ slightly in angle from axes that differ slightly in
+    /// offset, including nearly horizontal lines on either side of the cut of the polar range.
+    #[test]
+    fn test_same_line_as() {
+        let axis: Line = Line::new(2., 0., -2.);
+        // Rotated by 1e-3 radians about (1, 0), and moved by 0.01 along x.
+        let turned = Line::from_polar(1e-3, Float::cos(1e-3));
+        let moved: Line = Line::new(-1., 0., 1.01);
+
+        assert!(axis.same_line_as(&turned, 1e-2, 1e-6));
+        assert!(!axis.same_line_as(&turned, 1e-4, 1e-6));
+        assert!(axis.same_line_as(&moved, 1e-9, 0.1));
+        assert!(!axis.same_line_as(&moved, 1., 1e-3));
+
+        let half_pi = core::f64::consts::FRAC_PI_2;
+        let below = Line::from_polar(half_pi - 1e-4, 2.);
+        let above = Line::from_polar(-half_pi + 1e-4, -2.);
+        assert!(below.same_line_as(&above, 1e-3, 1e-3));
+        assert!(!below.same_line_as(&above, 1e-5, 1e-3));
+        assert!(!axis.same_line_as(&Line::new(0., 0., -2.), 1., 1.));
+    }
+
+    /// Tests that nearby lines, scaled and negated differently, quantize to the identical line.
+    #[test]
+    fn test_quantized() {
+        let line: Line = Line::new(1., 1e-4, -2.0001);
+        let nearby: Line = Line::new(-2., 0., 3.9998);
+        assert_eq!(line.quantized(0.01, 0.01).to_array(), [1., 0., -2.]);
+        assert_eq!(nearby.quantized(0.01, 0.01).to_array(), line.quantized(0.01, 0.01).to_array());
+
+        // A diagonal is snapped to the nearest orientation on a coarse grid of angles.
+        let diagonal: Line = Line::new(1., -1.1, 0.3).quantized(core::f64::consts::FRAC_PI_4, 1.);
+        let [a, b, c] = diagonal.to_array();
+        assert!(util::floats_equal_toler(a, -b) && util::floats_equal_toler(a.hypot(b), 1.));
+        assert_eq!(c, 0.);
+        assert_eq!(Line::new(0., 0., 1.).quantized(0.1, 0.1).to_array(), [0., 0., 1.]);
+        assert_eq!(line.quantized(0., 0.1).to_array(), line.to_array());
+    }
+
+    /// Tests which side of a diagonal axis points lie on, and that reflection swaps the sides.
+    #[test]
+    fn test_side_of() {
+        let diagonal: Line = Line::new(1., -1., 0.);
+        assert_eq!(diagonal.side_of(&Point::new(1., 0.)), Side::Left);
+        assert_eq!(diagonal.side_of(&Point::new(-2., 3.)), Side::Right);
+        assert_eq!(diagonal.side_of(&Point::new(2., 2.)), Side::On);
+        assert_eq!(diagonal.side_of(&Point::new(2., 2. + 1e-12)), Side::On);
+
+        let flipped: Line = Line::new(-1., 1., 0.);
+        assert_eq!(flipped.side_of(&Point::new(1., 0.)), Side::Right);
+        let p = Point::new(3., -1.);
+        assert_eq!(diagonal.side_of(&diagonal.get_reflected_point(&p)), Side::Right);
+        assert_eq!(diagonal.side_of(&p), Side::Left);
+    }
+
+    /// Tests points that are equal within a box of the tolerance but not within a disk of it, and
+    /// vice versa.
+    #[test]
+    fn test_approx_eq_radial() {
+        let tol = config::EPSILON;
+        let origin = Point::new(0., 0.);
+
+        let corner = Point::new(0.9 * tol, 0.9 * tol);
+        assert_eq!(origin, corner);
+        assert!(!origin.approx_eq_radial(&corner, tol));
+
+        let edge = Point::new(tol, 0.);
+        assert_ne!(origin, edge);
+        assert!(origin.approx_eq_radial(&edge, tol));
+
+        assert!(origin.approx_eq_radial(&Point::new(3., 4.), 5.));
+        assert!(!origin.approx_eq_radial(&Point::new(3., 4.), 4.99));
+    }
+
+    /// Tests the axis-aligned reflections against the general `Line::get_reflected_point`.
+    #[test]
+    fn test_axis_aligned_reflections() {
+        let points = [Point::new(3., -2.), Point::new(0., 0.), Point::new(-1.5, 4.25)];
+
+        for p in &points {
+            assert_eq!(reflect_over_x(p), Line::new(0., 1., 0.).get_reflected_point(p));
+            assert_eq!(reflect_over_y(p), Line::new(1., 0., 0.).get_reflected_point(p));
+            assert_eq!(reflect_over_horizontal(p, 1.5), Line::new(0., 2., -3.).get_reflected_point(p));
+            assert_eq!(reflect_over_vertical(p, -0.5), Line::new(1., 0., 0.5).get_reflected_point(p));
+        }
+    }
+
+    /// Tests that a pair is formatted in canonical order regardless of construction order.
+    #[test]
+    fn test_unordered_pair_display() {
+        let p1 = Point::new(2.5, -1.);
+        let p2 = Point::new(0., 3.);
+        let pair = UnorderedPointPair::new(&p1, &p2);
+
+        assert_eq!(alloc::format!("{}", pair), "{(0,3) - (2.5,-1)}");
+        assert_eq!(alloc::format!("{}", UnorderedPointPair::new(&p2, &p1)), "{(0,3) - (2.5,-1)}");
+        assert_eq!(pair.points(), (&p2, &p1));
+    }
+
+    /// Tests that owned pairs stay usable after the set their points came from is dropped.
+    #[test]
+    fn test_owned_pairs_outlive_set() {
+        let owned: Vec<OwnedPointPair> = {
+            let points: HashSet<Point> =
+                [Point::new(2.5, -1.), Point::new(0., 3.), Point::new(1., 1.)].into_iter().collect();
+            let mut sorted: Vec<&Point> = points.iter().collect();
+            sorted.sort_by(|p, q| p.partial_cmp(q).unwrap());
+            let pairs = [
+                UnorderedPointPair::new(sorted[0], sorted[1]),
+                UnorderedPointPair::new(sorted[2], sorted[1]),
+            ];
+            pairs.into_iter().map(OwnedPointPair::from).collect()
+        };
+
+        assert_eq!(owned, [
+            OwnedPointPair::new(Point::new(0., 3.), Point::new(1., 1.)),
+            OwnedPointPair::new(Point::new(2.5, -1.), Point::new(1., 1.)),
+        ]);
+        assert_eq!(owned[1].points(), (Point::new(1., 1.), Point::new(2.5, -1.)));
+        assert_eq!(alloc::format!("{}", owned[1]), "{(1,1) - (2.5,-1)}");
+        assert_eq!(owned[0].as_borrowed().into_owned(), owned[0]);
+    }
+
+    /// Tests the exact JSON shapes of a point and a line.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_json_value() {
+        let point = Point::new(1.5, -2.).to_json_value();
+        assert_eq!(alloc::format!("{}", point), r#"{"x":1.5,"y":-2.0}"#);
+        assert_eq!(Point::new(0.5f32, 3.).to_json_value(), serde_json::json!({ "x": 0.5, "y": 3.0 }));
+
+        let line = Line::new(2., 0., -2.).to_json_value();
+        assert_eq!(alloc::format!("{}", line), r#"{"a":2.0,"b":0.0,"c":-2.0}"#);
+    }
+
+    /// Tests that relative point equality follows the magnitude of the coordinates.
+    #[test]
+    fn test_approx_eq_rel() {
+        let far = Point::new(1e7, -3e7);
+        let rounded = Point::new(1e7 + 20. * config::EPSILON, -3e7);
+        assert!(far != rounded);
+        assert!(far.approx_eq_rel(&rounded, 1e-3 * config::EPSILON, 0.));
+        assert!(!far.approx_eq_rel(&Point::new(1e7 + 1., -3e7), 1e-3 * config::EPSILON, 0.));
+
+        let near_origin = Point::new(0., 1e-12);
+        assert!(!near_origin.approx_eq_rel(&Point::new(0., 0.), 1e-9, 0.));
+        assert!(near_origin.approx_eq_rel(&Point::new(0., 0.), 1e-9, 1e-9));
+    }
+
+    /// Tests rotating points about the origin and about another center.
+    #[test]
+    fn test_rotate_about() {
+        let origin = Point::new(0., 0.);
+        let quarter = core::f64::consts::FRAC_PI_2;
+        assert_eq!(Point::new(1., 0.).rotate_about(&origin, quarter), Point::new(0., 1.));
+        assert_eq!(Point::new(2., 3.).rotate_about(&origin, quarter), Point::new(-3., 2.));
+        assert_eq!(Point::new(2., 3.).rotate_about(&origin, -quarter), Point::new(3., -2.));
+
+        let center = Point::new(1., 1.);
+        assert_eq!(Point::new(2., 1.).rotate_about(&center, core::f64::consts::PI), Point::new(0., 1.));
+        assert_eq!(center.rotate_about(&center, 1.234), center);
+
+        let overflowing = Point::new(f64::MAX, f64::MAX).checked_rotate_about(&origin, quarter / 2.);
+        assert!(matches!(overflowing, Err(SymmError::NonFinitePoint)));
+    }
+
+    /// Tests averaging the corners of a square, and points too large to sum.
+    #[test]
+    fn test_mean() {
+        let corners = [Point::new(0., 0.), Point::new(2., 0.), Point::new(2., 2.), Point::new(0., 2.)];
+        assert_eq!(Point::mean(corners), Some(Point::new(1., 1.)));
+        assert_eq!(Point::mean(core::iter::empty::<Point>()), None);
+
+        // Summing these coordinates would overflow to infinity.
+        let huge = [Point::new(f64::MAX, -f64::MAX), Point::new(f64::MAX, -f64::MAX), Point::new(0., 0.)];
+        let mean = Point::mean(huge).unwrap();
+        assert!(mean.approx_eq_rel(&Point::new(f64::MAX / 1.5, -f64::MAX / 1.5), 1e-12, 0.));
+    }
+
+    /// Tests clamping points outside a box to its nearest corner or side, and inside it to itself.
+    #[test]
+    fn test_clamp_to_box() {
+        let (min, max) = (Point::new(-1., 0.), Point::new(2., 3.));
+        assert_eq!(Point::new(5., -4.).clamp_to_box(&min, &max), Point::new(2., 0.));
+        assert_eq!(Point::new(-3., 7.).clamp_to_box(&min, &max), Point::new(-1., 3.));
+        assert_eq!(Point::new(0.5, 9.).clamp_to_box(&min, &max), Point::new(0.5, 3.));
+        assert_eq!(Point::new(1., 1.).clamp_to_box(&min, &max), Point::new(1., 1.));
+    }
+
+    /// Tests reading points from interleaved coordinates, and rejecting odd or non-finite input.
+    #[test]
+    fn test_points_from_slice() {
+        let points = points_from_slice(&[1.0, 0.0, 0.0, 1.0]).unwrap();
+        let expected: HashSet<Point> = [Point::new(1., 0.), Point::new(0., 1.)].into_iter().collect();
+        assert_eq!(points, expected);
+        assert!(points_from_slice::<f64>(&[]).unwrap().is_empty());
+
+        let repeated = points_from_slice(&[1.0, 0.0, 1.0, 0.0]).unwrap();
+        assert_eq!(repeated.len(), 1);
+
+        let odd = points_from_slice(&[1.0, 0.0, 0.0]);
+        assert!(matches!(odd, Err(SymmError::Parse(reason)) if reason == "odd number of coordinates: 3"));
+        assert!(matches!(points_from_slice(&[1.0, f64::NAN]), Err(SymmError::NonFinitePoint)));
+    }
+
+    /// Tests building a set from valid, duplicate and non-finite points, with duplicates merged
+    /// and rejected.
+    #[test]
+    fn test_point_set_builder() {
+        let inputs = [
+            (0., 0.),
+            (1., 0.),
+            (1. + config::EPSILON / 2., 0.),
+            (f64::NAN, 1.),
+            (0., 0.),
+            (0., f64::INFINITY),
+        ];
+
+        let mut merging = PointSetBuilder::new();
+        for (x, y) in inputs {
+            merging.add(x, y);
+        }
+        let errors = merging.build().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| matches!(e, SymmError::NonFinitePoint)));
+
+        let mut merging = PointSetBuilder::new();
+        let finite = inputs.iter().filter(|(x, y)| x.is_finite() && y.is_finite());
+        merging.extend(finite.map(|&(x, y)| Point::new(x, y)));
+        let points = merging.build().unwrap();
+        assert_eq!(points.len(), 2);
+        assert!(points.contains(&Point::new(1., 0.)));
+
+        let mut rejecting = PointSetBuilder::new().reject_duplicates(true);
+        for (x, y) in inputs {
+            rejecting.add(x, y);
+        }
+        let errors = rejecting.build().unwrap_err();
+        let kinds: Vec<bool> = errors.iter().map(|e| matches!(e, SymmError::DuplicatePoint)).collect();
+        assert_eq!(kinds, [true, false, true, false]);
+    }
+
+    /// Tests that reflecting a segment across its own perpendicular bisector yields the
+    /// reversed segment, and checks the basic segment measurements.
+    #[test]
+    fn test_segment_reflection() {
+        let segment = Segment::new(Point::new(1.0, 1.0), Point::new(4.0, 5.0));
+        assert!(util::floats_equal_toler(segment.length(), 5.0));
+        assert_eq!(segment.midpoint(), Point::new(2.5, 3.0));
+
+        let line = segment.to_line();
+        assert!(line.is_point_on_line(&segment.start));
+        assert!(line.is_point_on_line(&segment.end));
+
+        let bisector = alg::perpendicular_bisector(&segment.start, &segment.end).unwrap();
+        let reflected = segment.reflect_across(&bisector);
+        assert_eq!(reflected, Segment::new(Point::new(4.0, 5.0), Point::new(1.0, 1.0)));
+    }
 }
@@ -0,0 +1,62 @@
+//! Conversions between this crate's types and `nalgebra` types, enabled by the `nalgebra` feature.
+
+use crate::model::{Line, Point};
+use nalgebra::{Point2, Vector3};
+
+impl From<Point2<f64>> for Point {
+    /// Converts an `nalgebra` point into a point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either coordinate is NaN or infinite, like `Point::new`.
+    fn from(p: Point2<f64>) -> Self {
+        Point::new(p.x, p.y)
+    }
+}
+
+impl From<Point> for Point2<f64> {
+    fn from(p: Point) -> Self {
+        Point2::new(p.x, p.y)
+    }
+}
+
+impl From<&Line> for Vector3<f64> {
+    /// Converts a line into its homogeneous coordinates `(a, b, c)`, so that a point `(x, y)`
+    /// lies on the line when the dot product with `(x, y, 1)` is zero.
+    fn from(line: &Line) -> Self {
+        Vector3::new(line.a, line.b, line.c)
+    }
+}
+
+impl From<Vector3<f64>> for Line {
+    /// Converts homogeneous line coordinates `(a, b, c)` into a line.
+    fn from(v: Vector3<f64>) -> Self {
+        Line::new(v.x, v.y, v.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_round_trip() {
+        let p = Point::new(1.5, -2.25);
+        let q: Point2<f64> = p.into();
+        assert_eq!(q, Point2::new(1.5, -2.25));
+        assert_eq!(Point::from(q), p);
+    }
+
+    /// Tests that a line round-trips through homogeneous coordinates, and that the homogeneous
+    /// form gives point-on-line checks as a dot product.
+    #[test]
+    fn test_line_round_trip() {
+        let line = Line::new(1., -1., 2.);
+        let v = Vector3::from(&line);
+        assert_eq!(Line::from(v), line);
+
+        let on_line = Point2::new(0., 2.);
+        assert_eq!(v.dot(&on_line.to_homogeneous()), 0.0);
+        assert!(line.is_point_on_line(&Point::from(on_line)));
+    }
+}
@@ -0,0 +1,687 @@
+use crate::alg;
+use crate::config;
+use crate::geometry::convex_hull;
+use crate::model::{Line, Point, Vector2};
+use crate::util;
+use std::collections::HashSet;
+use std::f64::consts::{FRAC_PI_2, PI};
+
+/// Returns the glide reflections of a finite, cropped repeating pattern.
+///
+/// A glide reflection reflects across an axis, then translates along it. A finite set cannot
+/// be mapped exactly onto itself by a glide reflection (its square is a nonzero translation), so
+/// the pattern is treated as a crop of an infinite one: a glide is accepted if every point's image
+/// is either in the set or lies beyond the end of the set along the axis, the same holds for
+/// every point's preimage, and every point has its image or its preimage in the set.
+///
+/// # Returns
+///
+/// Each glide axis paired with the shortest glide translation found for it. A glide's inverse
+/// is also a glide of the pattern, so only the translation pointing towards increasing x (or
+/// increasing y, for vertical axes) is reported. Pure reflections are not included.
+///
+/// # Notes
+///
+/// Candidates are generated from pairs of points, and each is validated against the whole set,
+/// so the cost grows as O(n^4) in the worst case. This is intended for modestly sized patterns.
+pub fn glide_reflections(points: &HashSet<Point>) -> Vec<(Line, Vector2)> {
+    let points_vec: Vec<&Point> = points.iter().collect();
+    let mut glides: Vec<(Line, Vector2)> = Vec::new();
+
+    for (ux, uy) in candidate_directions(&points_vec) {
+        let project = |p: &Point| p.x * ux + p.y * uy;
+
+        // With a positive translation, the lowest point along the axis must map onto a point of
+        // the set, which determines the axis (through their midpoint) and the translation.
+        let p0 = *points_vec
+            .iter()
+            .min_by(|p, q| project(p).total_cmp(&project(q)))
+            .unwrap();
+
+        let mut found: Option<(Line, Vector2)> = None;
+        for r in &points_vec {
+            let shift = project(r) - project(p0);
+            if !util::floats_lt_toler(0.0, shift) {
+                continue;
+            }
+            if matches!(&found, Some((_, t)) if t.length() <= shift) {
+                continue;
+            }
+
+            let mid = Point::new(0.5 * (p0.x + r.x), 0.5 * (p0.y + r.y));
+            let axis = Line::new(-uy, ux, uy * mid.x - ux * mid.y);
+            let translation = Vector2::new(shift * ux, shift * uy);
+
+            if is_cropped_glide(points, &axis, &translation, &project) {
+                found = Some((axis, translation));
+            }
+        }
+
+        glides.extend(found);
+    }
+
+    glides
+}
+
+/// Returns the minimal translation vectors of a finite, cropped periodic pattern.
+///
+/// As with `glide_reflections`, the set is treated as a crop of an infinite pattern: a translation
+/// `t` is accepted if, for every point `p`, both `p + t` and `p - t` are either in the set or outside
+/// its convex hull. To rule out trivially long vectors that move most points outside the hull, at
+/// least half the points must also have `p + t` in the set.
+///
+/// # Returns
+///
+/// A reduced basis of the accepted translations: the shortest one, followed by the shortest one
+/// not parallel to it, if any. Vectors point towards increasing x (or increasing y, for vertical
+/// vectors). An empty vector is returned if no translation is accepted.
+pub fn translational_symmetries(points: &HashSet<Point>) -> Vec<Vector2> {
+    let points_vec: Vec<&Point> = points.iter().collect();
+    let hull = convex_hull(points);
+
+    let mut seen: HashSet<(i64, i64)> = HashSet::new();
+    let mut candidates: Vec<Vector2> = Vec::new();
+    for i in 0..points_vec.len() {
+        for j in (i + 1)..points_vec.len() {
+            let v = canonical_orientation(points_vec[j].x - points_vec[i].x, points_vec[j].y - points_vec[i].y);
+            let key = ((v.x / config::EPSILON).round() as i64, (v.y / config::EPSILON).round() as i64);
+            if seen.insert(key) {
+                candidates.push(v);
+            }
+        }
+    }
+    candidates.sort_by(|v, w| v.length().total_cmp(&w.length()));
+
+    let mut basis: Vec<Vector2> = Vec::new();
+    for v in candidates {
+        let parallel_to_basis = basis
+            .iter()
+            .any(|b| util::floats_equal_toler((b.x * v.y - b.y * v.x) / (b.length() * v.length()), 0.0));
+        if parallel_to_basis || !is_cropped_translation(points, &hull, &v) {
+            continue;
+        }
+
+        basis.push(v);
+        if basis.len() == 2 {
+            break;
+        }
+    }
+
+    basis
+}
+
+/// Returns the lines of symmetry of a periodic pattern of which `points` is a fundamental domain,
+/// e.g. a crop of a tiling: the pattern is the points translated by every integer multiple of
+/// `period`.
+///
+/// A line is accepted if it maps the pattern onto itself, so the mirror partner of a point may be
+/// in the set only after a shift by some multiple of `period`. Such a line maps `period` onto
+/// itself or its opposite, so it is parallel or perpendicular to it. Each perpendicular axis
+/// repeats every half period along the pattern, and is reported once, shifted by a multiple of
+/// `period` to within half a period of the centroid of the points. With a zero `period`, the
+/// pattern is the points alone, and their own lines of symmetry are returned. A `period` shorter
+/// than the extent of the points along it, whose copies would overlap, gives no lines.
+pub fn get_lines_of_sym_periodic(points: &HashSet<Point>, period: Vector2) -> HashSet<Line> {
+    let len2 = period.x * period.x + period.y * period.y;
+    if util::floats_equal_toler(period.length(), 0.0) {
+        return alg::get_lines_of_sym_or_empty(points, None);
+    }
+    let Some(centroid) = Point::mean(points.iter().copied()) else {
+        return HashSet::new();
+    };
+
+    let index = alg::PointIndex::new(points);
+    let shifted = |p: &Point, k: f64| Point::new(p.x + k * period.x, p.y + k * period.y);
+    // The position of a point along the pattern, in periods.
+    let along = |p: &Point| (p.x * period.x + p.y * period.y) / len2;
+
+    // A fundamental domain spans at most one period, so a reflection can land on at most a few
+    // copies of it; for a longer set that number, and so the search below, would be unbounded.
+    let lo = points.iter().map(along).fold(f64::INFINITY, f64::min);
+    let hi = points.iter().map(along).fold(f64::NEG_INFINITY, f64::max);
+    let slack = config::EPSILON / len2.sqrt();
+    if (hi - lo).is_nan() || hi - lo > 1. + slack {
+        return HashSet::new();
+    }
+
+    // Every axis maps some point onto a copy of a point of the set, or has the point on it.
+    let p0 = index.points()[0];
+    let direction = Point::new(p0.x + period.x, p0.y + period.y);
+    let normal = Point::new(p0.x - period.y, p0.y + period.x);
+    let mut candidates = vec![alg::get_through_line(p0, &direction), alg::get_through_line(p0, &normal)];
+    for q in index.points() {
+        let (dx, dy) = (q.x - p0.x, q.y - p0.y);
+        if util::floats_equal_toler((dx * period.y - dy * period.x) / len2.sqrt(), 0.0) {
+            // On the line through `p0` along the pattern: every second copy of `q` gives a
+            // perpendicular axis of another class, up to a shift by a period.
+            candidates.extend([0., 1.].map(|k| alg::perpendicular_bisector(p0, &shifted(q, k))).into_iter().flatten());
+        }
+        let k = -along(&Point::new(dx, dy)).round();
+        if util::floats_equal_toler((along(&Point::new(dx, dy)) + k) * len2.sqrt(), 0.0) {
+            // The copy of `q` straight across the pattern from `p0`, giving a parallel axis.
+            candidates.extend(alg::perpendicular_bisector(p0, &shifted(q, k)));
+        }
+    }
+
+    let is_periodic_axis = |line: &Line| {
+        points.iter().all(|p| {
+            let Some(r) = line.try_reflected_point(p) else {
+                return false;
+            };
+            // The copies of the set that the reflection can land on.
+            let (first, last) = ((along(&r) - hi - slack).ceil() as i64, (along(&r) - lo + slack).floor() as i64);
+            (first..=last).any(|k| index.get(&shifted(&r, -(k as f64))).is_some())
+        })
+    };
+
+    candidates
+        .into_iter()
+        .filter(|line| is_periodic_axis(line))
+        .filter_map(|line| {
+            // Shifting a perpendicular axis by `k` periods moves it by `k` periods along the
+            // pattern; a parallel axis stays in place.
+            let foot = line.try_reflected_point(&centroid)?;
+            let foot = Point::new((centroid.x + foot.x) / 2., (centroid.y + foot.y) / 2.);
+            let k = (along(&centroid) - along(&foot)).round();
+            Some(Line::new(line.a, line.b, line.c - k * (line.a * period.x + line.b * period.y)))
+        })
+        .collect()
+}
+
+/// A transform of the plane: a rotation or translation, as composed by `compose_reflections`, a
+/// reflection, as returned by `symmetry_transforms`, or a scaling, e.g. to undo the distortion of a
+/// map projection for `get_lines_of_sym_in_frame`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transform2D {
+    /// A counterclockwise rotation by `angle` radians, in `(-π, π]`, about `center`.
+    Rotation { center: Point, angle: f64 },
+    /// A translation by the given vector. The identity is a translation by zero.
+    Translation(Vector2),
+    /// A reflection across the line `a·x + b·y + c = 0`, which must be valid (see
+    /// `Line::is_valid`).
+    Reflection { a: f64, b: f64, c: f64 },
+    /// A scaling about `center` by `factor_x` along the x axis and `factor_y` along the y axis.
+    /// Unlike the other transforms, it doesn't preserve distances unless both factors are 1.
+    Scaling { center: Point, factor_x: f64, factor_y: f64 },
+}
+
+impl Transform2D {
+    /// Returns the reflection across `line`.
+    pub fn reflection(line: &Line) -> Self {
+        Transform2D::Reflection { a: line.a, b: line.b, c: line.c }
+    }
+
+    /// Returns the image of `p` under this transform.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is a reflection across an invalid line.
+    pub fn apply(&self, p: &Point) -> Point {
+        match self {
+            Transform2D::Rotation { center, angle } => p.rotate_about(center, *angle),
+            Transform2D::Translation(t) => Point::new(p.x + t.x, p.y + t.y),
+            Transform2D::Reflection { a, b, c } => Line::new(*a, *b, *c).get_reflected_point(p),
+            Transform2D::Scaling { center, factor_x, factor_y } => {
+                Point::new(center.x + factor_x * (p.x - center.x), center.y + factor_y * (p.y - center.y))
+            }
+        }
+    }
+
+    /// Returns the transform undoing this one, or `None` if this is a scaling by a factor of 0.
+    pub fn inverse(&self) -> Option<Transform2D> {
+        match *self {
+            Transform2D::Rotation { center, angle } => Some(Transform2D::Rotation { center, angle: -angle }),
+            Transform2D::Translation(t) => Some(Transform2D::Translation(Vector2::new(-t.x, -t.y))),
+            Transform2D::Reflection { .. } => Some(*self),
+            Transform2D::Scaling { center, factor_x, factor_y } => (factor_x != 0.0 && factor_y != 0.0)
+                .then(|| Transform2D::Scaling { center, factor_x: 1.0 / factor_x, factor_y: 1.0 / factor_y }),
+        }
+    }
+
+    /// Returns the image of `line` under this transform, or `None` if it isn't a line, i.e. if
+    /// `line` isn't valid or this is a scaling by a factor of 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is a reflection across an invalid line.
+    pub fn map_line(&self, line: &Line) -> Option<Line> {
+        if !line.is_valid() {
+            return None;
+        }
+
+        // The foot of the origin's perpendicular on the line, and a point one direction further.
+        let norm = line.a * line.a + line.b * line.b;
+        let foot = Point::new(-line.a * line.c / norm, -line.b * line.c / norm);
+        let further = Point::new(foot.x - line.b, foot.y + line.a);
+        Line::through_points(&self.apply(&foot), &self.apply(&further))
+    }
+}
+
+/// Returns the lines of symmetry of the points in a working frame given by `transform`, mapped
+/// back to the points' own frame.
+///
+/// The points are mapped into the working frame with `transform`, their lines of symmetry are
+/// found there, and each is mapped back with the inverse transform. If `transform` is a rotation,
+/// translation or reflection, the result is the same as searching the points directly; if it is a
+/// scaling, e.g. undoing the stretch of a map projection, the lines are axes of the points'
+/// symmetry in the working frame, which the points' own frame distorts.
+///
+/// # Panics
+///
+/// Panics if `transform` is a scaling by a factor of 0, or a reflection across an invalid line.
+pub fn get_lines_of_sym_in_frame(points: &HashSet<Point>, transform: &Transform2D) -> HashSet<Line> {
+    let inverse = transform.inverse().expect("the transform into the working frame must be invertible");
+    let working: HashSet<Point> = points.iter().map(|p| transform.apply(p)).collect();
+
+    alg::get_lines_of_sym_or_empty(&working, None)
+        .iter()
+        .filter_map(|line| inverse.map_line(line))
+        .collect()
+}
+
+/// Returns each line of symmetry of `points` paired with the reflection across it, ordered by the
+/// angle of the line, then by its offset.
+///
+/// Each reflection maps the set onto itself, and so does each composition of two of them with
+/// `compose_reflections`.
+pub fn symmetry_transforms(points: &HashSet<Point>) -> Vec<(Line, Transform2D)> {
+    let mut lines: Vec<Line> = alg::get_lines_of_sym_or_empty(points, None).into_iter().collect();
+    lines.sort_by(|l1, l2| {
+        let offset = |l: &Line| l.c / l.a.hypot(l.b);
+        l1.angle_rad().total_cmp(&l2.angle_rad()).then(offset(l1).total_cmp(&offset(l2)))
+    });
+    lines
+        .into_iter()
+        .map(|line| {
+            let reflection = Transform2D::reflection(&line);
+            (line, reflection)
+        })
+        .collect()
+}
+
+/// Returns the transform equal to reflecting across `l1`, then across `l2`.
+///
+/// If the lines intersect, this is a rotation about the intersection by twice the angle from
+/// `l1` to `l2`; if they are parallel, it is a translation perpendicular to them by twice the
+/// distance from `l1` to `l2`. Lines of symmetry of a set compose to rotations that also map the
+/// set onto itself, so this generates the rotations of its symmetry group from its axes.
+///
+/// # Panics
+///
+/// Panics if either line isn't valid (see `Line::is_valid`).
+pub fn compose_reflections(l1: &Line, l2: &Line) -> Transform2D {
+    if !l1.is_valid() || !l2.is_valid() {
+        panic!("Invalid line: (a, b) must not be (near) zero");
+    }
+
+    let unit = |l: &Line| {
+        let norm = l.a.hypot(l.b);
+        (l.a / norm, l.b / norm, l.c / norm)
+    };
+    let (a1, b1, c1) = unit(l1);
+    let (a2, b2, c2) = unit(l2);
+
+    // The sine and cosine of the angle from the direction (b1, -a1) of `l1` to that of `l2`.
+    let (sin, cos) = (a1 * b2 - a2 * b1, a1 * a2 + b1 * b2);
+
+    if util::floats_equal_toler(sin, 0.0) {
+        // Parallel: with the normals oriented alike, translate along them by twice the offset.
+        let c2 = if cos < 0.0 { -c2 } else { c2 };
+        let shift = 2.0 * (c1 - c2);
+        return Transform2D::Translation(Vector2::new(shift * a1, shift * b1));
+    }
+
+    // The lines are undirected, so the angle between them is taken in (-π/2, π/2].
+    let mut between = sin.atan2(cos);
+    if between > FRAC_PI_2 {
+        between -= PI;
+    } else if between <= -FRAC_PI_2 {
+        between += PI;
+    }
+
+    // Solve the two line equations; their determinant is `sin`, as the normals are unit vectors.
+    let center = Point::new((b1 * c2 - b2 * c1) / sin, (a2 * c1 - a1 * c2) / sin);
+    Transform2D::Rotation { center, angle: 2.0 * between }
+}
+
+/// Returns a minimal set of lines of symmetry of `points` whose reflections generate its whole
+/// symmetry group, e.g. to describe the symmetry of a shape compactly.
+///
+/// A set with `n` lines of symmetry has the dihedral group `Dn`, whose axes cross at the centroid
+/// at multiples of `π / n` apart. Two adjacent axes generate it: they compose with
+/// `compose_reflections` to the rotation by `2π / n`, whose powers are the rotations of the group,
+/// and composing those with either axis gives the reflections. These are the first two axes in the
+/// order of `symmetry_transforms`. A set with one axis is generated by it alone, and a set without
+/// any by none, even if it has rotational symmetry.
+pub fn generating_reflections(points: &HashSet<Point>) -> Vec<Line> {
+    symmetry_transforms(points).into_iter().take(2).map(|(line, _)| line).collect()
+}
+
+/// Returns the vector (dx, dy) or its opposite, whichever points towards increasing x
+/// (or increasing y, for vertical vectors).
+fn canonical_orientation(dx: f64, dy: f64) -> Vector2 {
+    if dx < -config::EPSILON || (util::floats_equal_toler(dx, 0.0) && dy < 0.0) {
+        Vector2::new(-dx, -dy)
+    } else {
+        Vector2::new(dx, dy)
+    }
+}
+
+/// Checks the cropped-pattern translation criterion described in `translational_symmetries`.
+fn is_cropped_translation(points: &HashSet<Point>, hull: &[Point], t: &Vector2) -> bool {
+    let mut matched = 0;
+
+    for p in points {
+        let image = Point::new(p.x + t.x, p.y + t.y);
+        let preimage = Point::new(p.x - t.x, p.y - t.y);
+
+        let image_ok = points.contains(&image);
+        if !image_ok && hull_contains(hull, &image) {
+            return false;
+        }
+        if !points.contains(&preimage) && hull_contains(hull, &preimage) {
+            return false;
+        }
+
+        matched += image_ok as usize;
+    }
+
+    2 * matched >= points.len()
+}
+
+/// Checks whether a point lies inside a convex hull given in counter-clockwise order, with tolerance.
+fn hull_contains(hull: &[Point], p: &Point) -> bool {
+    match hull.len() {
+        0 => false,
+        1 => hull[0] == *p,
+        2 => {
+            // Degenerate hull: the segment between the two extreme points.
+            let (a, b) = (&hull[0], &hull[1]);
+            let cross = (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x);
+            let dot = (b.x - a.x) * (p.x - a.x) + (b.y - a.y) * (p.y - a.y);
+            let len2 = (b.x - a.x).powi(2) + (b.y - a.y).powi(2);
+            util::floats_equal_toler(cross / len2.sqrt(), 0.0)
+                && !util::floats_lt_toler(dot, 0.0)
+                && !util::floats_lt_toler(len2, dot)
+        }
+        n => (0..n).all(|i| {
+            let (a, b) = (&hull[i], &hull[(i + 1) % n]);
+            let cross = (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x);
+            !util::floats_lt_toler(cross / a.distance(b), 0.0)
+        }),
+    }
+}
+
+/// Returns the distinct unit directions of the differences between pairs of points, oriented
+/// towards increasing x (or increasing y, for vertical directions).
+fn candidate_directions(points: &[&Point]) -> Vec<(f64, f64)> {
+    let mut seen: HashSet<(i64, i64)> = HashSet::new();
+    let mut directions = Vec::new();
+
+    for i in 0..points.len() {
+        for j in (i + 1)..points.len() {
+            let (dx, dy) = (points[j].x - points[i].x, points[j].y - points[i].y);
+            let len = dx.hypot(dy);
+            let u = canonical_orientation(dx / len, dy / len);
+            let (ux, uy) = (u.x, u.y);
+
+            // Quantize the direction so that parallel differences are only considered once.
+            let key = ((ux * 1e9).round() as i64, (uy * 1e9).round() as i64);
+            if seen.insert(key) {
+                directions.push((ux, uy));
+            }
+        }
+    }
+
+    directions
+}
+
+/// Checks the cropped-pattern glide criterion described in `glide_reflections`.
+fn is_cropped_glide(
+    points: &HashSet<Point>,
+    axis: &Line,
+    translation: &Vector2,
+    project: &impl Fn(&Point) -> f64,
+) -> bool {
+    let lo = points.iter().map(project).fold(f64::INFINITY, f64::min);
+    let hi = points.iter().map(project).fold(f64::NEG_INFINITY, f64::max);
+
+    points.iter().all(|p| {
+        let reflected = axis.try_reflected_point(p);
+        let preimage = axis.try_reflected_point(&Point::new(p.x - translation.x, p.y - translation.y));
+        let (Some(reflected), Some(preimage)) = (reflected, preimage) else {
+            return false;
+        };
+        let image = Point::new(reflected.x + translation.x, reflected.y + translation.y);
+
+        let image_ok = points.contains(&image);
+        let preimage_ok = points.contains(&preimage);
+
+        (image_ok || util::floats_lt_toler(hi, project(&image)))
+            && (preimage_ok || util::floats_lt_toler(project(&preimage), lo))
+            && (image_ok || preimage_ok)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alg::{get_lines_of_sym, get_lines_of_sym_or_empty};
+
+    /// Tests that a row of alternating left and right footprints has a glide reflection along
+    /// its midline, but no pure reflection.
+    #[test]
+    fn test_footprints_glide_without_reflection() {
+        let footprints = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(2., 0.),
+            Point::new(4., 0.),
+            Point::new(1., 1.),
+            Point::new(3., 1.),
+            Point::new(5., 1.),
+        ]);
+        assert!(get_lines_of_sym(&footprints, None).unwrap().is_empty());
+
+        let glides = glide_reflections(&footprints);
+        assert_eq!(glides.len(), 1);
+
+        let (axis, translation) = &glides[0];
+        assert!(axis.is_point_on_line(&Point::new(0., 0.5)));
+        assert!(axis.is_point_on_line(&Point::new(10., 0.5)));
+        assert_eq!(*translation, Vector2::new(1., 0.));
+    }
+
+    #[test]
+    fn test_scattered_points_have_no_glide() {
+        let points = HashSet::from([Point::new(0., 0.), Point::new(3., 1.), Point::new(1., 4.), Point::new(5., 2.5)]);
+        assert!(glide_reflections(&points).is_empty());
+    }
+
+    /// Tests that a 3x3 grid has the two unit translations as its minimal translation vectors.
+    #[test]
+    fn test_grid_translations() {
+        let mut grid = HashSet::new();
+        for x in 0..3 {
+            for y in 0..3 {
+                grid.insert(Point::new(x as f64, y as f64));
+            }
+        }
+
+        let translations = translational_symmetries(&grid);
+        assert_eq!(translations.len(), 2);
+        assert!(translations.contains(&Vector2::new(1., 0.)));
+        assert!(translations.contains(&Vector2::new(0., 1.)));
+    }
+
+    /// Tests that a strip has one translation, and a scalene triangle has none.
+    #[test]
+    fn test_strip_and_triangle_translations() {
+        let strip: HashSet<Point> = (0..5).map(|i| Point::new(2. * i as f64, 1.)).collect();
+        assert_eq!(translational_symmetries(&strip), vec![Vector2::new(2., 0.)]);
+
+        let triangle = HashSet::from([Point::new(0., 0.), Point::new(4., 0.), Point::new(1., 3.)]);
+        assert!(translational_symmetries(&triangle).is_empty());
+    }
+
+    /// Tests that each transform of a square's symmetries, and each composition of two, leaves the
+    /// square invariant.
+    #[test]
+    fn test_symmetry_transforms() {
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(2., 0.),
+            Point::new(2., 2.),
+            Point::new(0., 2.),
+        ]);
+        let transforms = symmetry_transforms(&square);
+        assert_eq!(transforms.len(), 4);
+
+        // The images may differ from the points by rounding, which misses the hash lookup.
+        let invariant = |t: &Transform2D| square.iter().all(|p| square.iter().any(|q| *q == t.apply(p)));
+        for (line, transform) in &transforms {
+            assert_eq!(*transform, Transform2D::reflection(line));
+            assert!(invariant(transform));
+            for (other, _) in &transforms {
+                assert!(invariant(&compose_reflections(line, other)));
+            }
+        }
+
+        let angles: Vec<f64> = transforms.iter().map(|(line, _)| line.angle_deg()).collect();
+        assert!(angles.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    /// Tests that two axes of a square generate its 8 symmetries: the powers of the quarter turn
+    /// they compose to, each alone and followed by a reflection.
+    #[test]
+    fn test_generating_reflections() {
+        let square = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(2., 0.),
+            Point::new(2., 2.),
+            Point::new(0., 2.),
+        ]);
+        let generators = generating_reflections(&square);
+        assert_eq!(generators.len(), 2);
+
+        let Transform2D::Rotation { angle, .. } = compose_reflections(&generators[0], &generators[1]) else {
+            panic!("adjacent axes should compose to a rotation");
+        };
+        assert!(util::floats_equal_toler(angle.abs(), FRAC_PI_2));
+
+        // A point off every axis has a distinct image under each symmetry.
+        let turn = compose_reflections(&generators[0], &generators[1]);
+        let (probe, mut images) = (Point::new(0.5, 0.2), Vec::new());
+        let mut rotated = probe;
+        for _ in 0..4 {
+            for image in [rotated, generators[0].get_reflected_point(&rotated)] {
+                assert!(!images.contains(&image));
+                images.push(image);
+            }
+            rotated = turn.apply(&rotated);
+        }
+        assert_eq!(images.len(), 8);
+        assert_eq!(rotated, probe);
+
+        let kite = HashSet::from([
+            Point::new(-1., 0.),
+            Point::new(0., 1.),
+            Point::new(3., 0.),
+            Point::new(0., -1.),
+        ]);
+        assert_eq!(generating_reflections(&kite), [Line::new(0., 1., 0.)]);
+    }
+
+    /// Tests that the axes of a 2 by 1 rectangle, a square when squeezed along x, are recovered in
+    /// the rectangle's frame, and that a rigid working frame doesn't change the axes.
+    #[test]
+    fn test_lines_of_sym_in_frame() {
+        let rectangle = HashSet::from([
+            Point::new(0., 0.),
+            Point::new(2., 0.),
+            Point::new(2., 1.),
+            Point::new(0., 1.),
+        ]);
+        let squeeze = Transform2D::Scaling { center: Point::new(0., 0.), factor_x: 0.5, factor_y: 1. };
+        let lines = get_lines_of_sym_in_frame(&rectangle, &squeeze);
+
+        // The square's axes x = 0.5, y = 0.5, y = x and x + y = 1, stretched back along x.
+        let expected = [
+            Line::new(1., 0., -1.),
+            Line::new(0., 1., -0.5),
+            Line::new(1., -2., 0.),
+            Line::new(1., 2., -2.),
+        ];
+        assert_eq!(lines.len(), 4);
+        assert!(expected.iter().all(|axis| lines.iter().any(|line| line.approx_eq(axis, 1e-9))));
+
+        let turn = Transform2D::Rotation { center: Point::new(1., 0.), angle: 0.7 };
+        let lines = get_lines_of_sym_in_frame(&rectangle, &turn);
+        let direct = alg::get_lines_of_sym(&rectangle, None).unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(direct.iter().all(|axis| lines.iter().any(|line| line.approx_eq(axis, 1e-9))));
+
+        let collapse = Transform2D::Scaling { center: Point::new(0., 0.), factor_x: 0., factor_y: 1. };
+        assert_eq!(collapse.inverse(), None);
+    }
+
+    /// Tests that reflecting across two perpendicular axes is a half turn about their intersection.
+    #[test]
+    fn test_compose_perpendicular_reflections() {
+        let (l1, l2) = (Line::new(1., 0., -1.), Line::new(0., 1., -2.));
+        let Transform2D::Rotation { center, angle } = compose_reflections(&l1, &l2) else {
+            panic!("perpendicular reflections should compose to a rotation");
+        };
+        assert_eq!(center, Point::new(1., 2.));
+        assert!((angle.abs() - PI).abs() < 1e-9);
+
+        let p = Point::new(4., -1.);
+        let composed = compose_reflections(&l1, &l2).apply(&p);
+        assert_eq!(composed, l2.get_reflected_point(&l1.get_reflected_point(&p)));
+        assert_eq!(composed, Point::new(-2., 5.));
+    }
+
+    /// Tests that reflecting across two parallel axes is a translation by twice their distance,
+    /// and that the angle of a rotation follows the order of the reflections.
+    #[test]
+    fn test_compose_parallel_reflections() {
+        // x = 1, then x = 3, written with opposite signs.
+        let (l1, l2) = (Line::new(1., 0., -1.), Line::new(-2., 0., 6.));
+        assert_eq!(compose_reflections(&l1, &l2), Transform2D::Translation(Vector2::new(4., 0.)));
+        assert_eq!(compose_reflections(&l2, &l1), Transform2D::Translation(Vector2::new(-4., 0.)));
+        assert_eq!(compose_reflections(&l1, &l1), Transform2D::Translation(Vector2::new(0., 0.)));
+
+        // The x-axis, then y = x: a quarter turn counterclockwise about the origin.
+        let (x_axis, diagonal) = (Line::new(0., 1., 0.), Line::new(1., -1., 0.));
+        let Transform2D::Rotation { angle, .. } = compose_reflections(&x_axis, &diagonal) else {
+            panic!("intersecting reflections should compose to a rotation");
+        };
+        assert!((angle - FRAC_PI_2).abs() < 1e-9);
+        assert_eq!(compose_reflections(&x_axis, &diagonal).apply(&Point::new(1., 0.)), Point::new(0., 1.));
+    }
+
+    /// Tests that a cropped strip repeating every 4 units along x has the vertical axes of the
+    /// whole pattern, across which some points only find their partner a period away, though the
+    /// crop alone has no axis.
+    #[test]
+    fn test_lines_of_sym_periodic() {
+        let strip = HashSet::from([Point::new(0., 0.), Point::new(1., 1.), Point::new(3., 1.)]);
+        assert!(get_lines_of_sym(&strip, None).unwrap().is_empty());
+
+        let axes = get_lines_of_sym_periodic(&strip, Vector2::new(4., 0.));
+        assert_eq!(axes, HashSet::from([Line::new(1., 0., 0.), Line::new(1., 0., -2.)]));
+
+        // Repeating every 7 units, the points don't repeat onto their mirror images.
+        assert!(get_lines_of_sym_periodic(&strip, Vector2::new(7., 0.)).is_empty());
+        assert_eq!(get_lines_of_sym_periodic(&strip, Vector2::new(0., 0.)), HashSet::new());
+
+        // Periods shorter than the strip, however short, are rejected rather than searched.
+        assert!(get_lines_of_sym_periodic(&strip, Vector2::new(2., 0.)).is_empty());
+        assert!(get_lines_of_sym_periodic(&strip, Vector2::new(1e-8, 0.)).is_empty());
+
+        // Two rows mirrored across the x-axis a period apart.
+        let rows = HashSet::from([Point::new(0., 1.), Point::new(4., -1.)]);
+        let axes = get_lines_of_sym_periodic(&rows, Vector2::new(4., 0.));
+        assert!(axes.contains(&Line::new(0., 1., 0.)));
+        assert!(!get_lines_of_sym_or_empty(&rows, None).contains(&Line::new(0., 1., 0.)));
+    }
+}
@@ -0,0 +1,347 @@
+use crate::alg::{self, get_equidistant_line, get_through_line};
+use crate::model::{Line, Point};
+use std::collections::HashSet;
+
+/// A closed polygon given by an ordered list of vertices.
+///
+/// The polygon is implicitly closed, so the first vertex should not be repeated at the end.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    /// The vertices of the polygon, in order.
+    pub vertices: Vec<Point>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Point>) -> Self {
+        Self { vertices }
+    }
+
+    /// Returns the polygon with every vertex reflected across `line`, in the same order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `line` isn't valid (see `Line::is_valid`).
+    pub fn reflect_across(&self, line: &Line) -> Polygon {
+        Polygon::new(self.vertices.iter().map(|v| line.get_reflected_point(v)).collect())
+    }
+
+    /// Checks whether reflecting the polygon across `line` yields the same polygon, i.e. the same
+    /// vertex ring up to the choice of starting vertex and direction.
+    ///
+    /// Unlike checking the vertex set, this takes edge connectivity into account, like
+    /// `polygon_symmetry`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `line` isn't valid (see `Line::is_valid`).
+    pub fn is_symmetric_about(&self, line: &Line) -> bool {
+        is_same_ring(&self.vertices, &self.reflect_across(line).vertices)
+    }
+}
+
+/// Checks whether two vertex rings are equal up to rotation and reversal.
+fn is_same_ring(ring: &[Point], other: &[Point]) -> bool {
+    let n = ring.len();
+    if other.len() != n {
+        return false;
+    }
+    if n == 0 {
+        return true;
+    }
+
+    (0..n).any(|shift| {
+        let forward = (0..n).all(|i| ring[i] == other[(shift + i) % n]);
+        let backward = (0..n).all(|i| ring[i] == other[(shift + n - i) % n]);
+        forward || backward
+    })
+}
+
+/// The symmetries of a closed polygon given by an ordered list of vertices.
+#[derive(Debug)]
+pub struct PolygonSymmetry {
+    /// The reflective axes of the polygon.
+    pub axes: Vec<Line>,
+    /// The number of rotations (including the identity) that map the polygon onto itself.
+    pub rotational_order: usize,
+}
+
+/// Returns the reflective axes and rotational order of a closed polygon.
+///
+/// Unlike `alg::get_lines_of_sym`, which treats its input as an unordered point set, this
+/// takes edge connectivity into account: a symmetry must map every edge of the polygon onto
+/// an edge. Two polygons with the same vertex set but different vertex orders can therefore
+/// have different symmetries.
+///
+/// # Arguments
+///
+/// * `vertices` - The vertices of the polygon, in order. The polygon is implicitly closed,
+///   so the first vertex should not be repeated at the end. Vertices are expected to be distinct.
+///
+/// # Returns
+///
+/// A `PolygonSymmetry`. Fewer than two vertices yield no axes and a rotational order of 1.
+pub fn polygon_symmetry(vertices: &[Point]) -> PolygonSymmetry {
+    let n = vertices.len();
+
+    if n < 2 {
+        return PolygonSymmetry {
+            axes: Vec::new(),
+            rotational_order: 1,
+        };
+    }
+
+    /*
+     * The symmetries of a cyclic vertex sequence are among the index maps i -> i + k and
+     * i -> k - i. Each is a symmetry of the polygon if it is realized by an isometry of the
+     * plane. For simple polygons the former are realized by rotations and the latter by
+     * reflections, but a self-intersecting polygon may realize either by either, so both
+     * kinds of isometry are checked for every map.
+     */
+    let index_maps: Vec<IndexMap> = (0..n).map(IndexMap::Shift).chain((0..n).map(IndexMap::Flip)).collect();
+
+    let axes = index_maps
+        .iter()
+        .filter_map(|map| reflection_axis(vertices, |i| map.apply(i, n)))
+        .collect();
+    let rotational_order = index_maps
+        .iter()
+        .filter(|map| is_rotation(vertices, |i| map.apply(i, n)))
+        .count();
+
+    PolygonSymmetry { axes, rotational_order }
+}
+
+/// A permutation of vertex indices that preserves the cyclic adjacency of a polygon.
+enum IndexMap {
+    /// i -> i + k (mod n)
+    Shift(usize),
+    /// i -> k - i (mod n)
+    Flip(usize),
+}
+
+impl IndexMap {
+    fn apply(&self, i: usize, n: usize) -> usize {
+        match *self {
+            IndexMap::Shift(k) => (i + k) % n,
+            IndexMap::Flip(k) => (k + n - i) % n,
+        }
+    }
+}
+
+/// Returns the axis of a reflection mapping each vertex `i` to vertex `map(i)`, if there is one.
+fn reflection_axis(vertices: &[Point], map: impl Fn(usize) -> usize) -> Option<Line> {
+    let n = vertices.len();
+
+    let axis = match (0..n).find(|&i| vertices[i] != vertices[map(i)]) {
+        Some(i) => get_equidistant_line(&vertices[i], &vertices[map(i)]),
+        // Every vertex is fixed: only possible if all of them lie on the axis.
+        None => get_through_line(&vertices[0], &vertices[1]),
+    };
+
+    // The axis is invalid if the vertices defining it coincide.
+    let valid = (0..n).all(|i| axis.try_reflected_point(&vertices[i]).is_some_and(|r| r == vertices[map(i)]));
+    valid.then_some(axis)
+}
+
+/// Checks whether a rotation about the vertex centroid maps each vertex `i` to vertex `map(i)`.
+fn is_rotation(vertices: &[Point], map: impl Fn(usize) -> usize) -> bool {
+    let n = vertices.len();
+    let center = Point::new(
+        vertices.iter().map(|p| p.x).sum::<f64>() / n as f64,
+        vertices.iter().map(|p| p.y).sum::<f64>() / n as f64,
+    );
+
+    // Any rotation mapping the vertices onto themselves fixes their centroid.
+    let Some(r) = (0..n).find(|&i| vertices[i] != center) else {
+        return false;
+    };
+    let from = (vertices[r].x - center.x, vertices[r].y - center.y);
+    let to = (vertices[map(r)].x - center.x, vertices[map(r)].y - center.y);
+    let angle = (from.0 * to.1 - from.1 * to.0).atan2(from.0 * to.0 + from.1 * to.1);
+    let (sin, cos) = angle.sin_cos();
+
+    (0..n).all(|i| {
+        let dx = vertices[i].x - center.x;
+        let dy = vertices[i].y - center.y;
+        let rotated = Point::new(center.x + dx * cos - dy * sin, center.y + dx * sin + dy * cos);
+        rotated == vertices[map(i)]
+    })
+}
+
+/// How a line of symmetry of a polygon's vertices passes through the polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AxisKind {
+    /// The axis passes through at least two vertices, like a square's diagonals.
+    ThroughVertices,
+    /// The axis passes through no vertex, so it crosses the polygon through edges, like the
+    /// axes of a square that bisect opposite edges.
+    ThroughEdges,
+    /// The axis passes through exactly one vertex and crosses an edge, like every axis of an
+    /// equilateral triangle.
+    Mixed,
+}
+
+/// Returns the lines of symmetry of a set of polygon vertices, each annotated with how it
+/// passes through the polygon, based on the number of vertices on it (see `alg::points_on_axis`).
+pub fn classify_axes(points: &HashSet<Point>) -> Vec<(Line, AxisKind)> {
+    alg::get_lines_of_sym_or_empty(points, None)
+        .into_iter()
+        .map(|axis| {
+            let kind = match alg::points_on_axis(points, &axis).len() {
+                0 => AxisKind::ThroughEdges,
+                1 => AxisKind::Mixed,
+                _ => AxisKind::ThroughVertices,
+            };
+            (axis, kind)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry;
+
+    #[test]
+    fn test_square() {
+        let square = [Point::new(0., 0.), Point::new(1., 0.), Point::new(1., 1.), Point::new(0., 1.)];
+        let symmetry = polygon_symmetry(&square);
+        assert_eq!(symmetry.axes.len(), 4);
+        assert_eq!(symmetry.rotational_order, 4);
+    }
+
+    /// Tests that the same vertex set connected as a crossed "bowtie" loses the diagonal axes
+    /// and the quarter-turn rotations of the square.
+    #[test]
+    fn test_bowtie_connectivity() {
+        let bowtie = [Point::new(0., 0.), Point::new(1., 1.), Point::new(1., 0.), Point::new(0., 1.)];
+        let symmetry = polygon_symmetry(&bowtie);
+        assert_eq!(symmetry.axes.len(), 2);
+        assert_eq!(symmetry.rotational_order, 2);
+        assert!(symmetry.axes.iter().all(|axis| axis.is_point_on_line(&Point::new(0.5, 0.5))));
+        assert!(symmetry.axes.iter().all(|axis| !axis.is_point_on_line(&Point::new(0., 0.))));
+    }
+
+    /// Tests that a polygon whose vertices all coincide has no axes, rather than panicking on the
+    /// invalid line through two of them.
+    #[test]
+    fn test_coincident_vertices() {
+        let symmetry = polygon_symmetry(&[Point::new(1., 1.); 3]);
+        assert!(symmetry.axes.is_empty());
+    }
+
+    #[test]
+    fn test_isosceles_triangle() {
+        let triangle = [Point::new(-1., 0.), Point::new(1., 0.), Point::new(0., 3.)];
+        let symmetry = polygon_symmetry(&triangle);
+        assert_eq!(symmetry.axes.len(), 1);
+        assert!(symmetry.axes[0].is_point_on_line(&Point::new(0., 3.)));
+        assert!(symmetry.axes[0].is_point_on_line(&Point::new(0., 0.)));
+        assert_eq!(symmetry.rotational_order, 1);
+    }
+
+    #[test]
+    fn test_non_convex_arrow() {
+        let arrow = [
+            Point::new(0., 3.),
+            Point::new(-2., 1.),
+            Point::new(-1., 1.),
+            Point::new(-1., -2.),
+            Point::new(1., -2.),
+            Point::new(1., 1.),
+            Point::new(2., 1.),
+        ];
+        let symmetry = polygon_symmetry(&arrow);
+        assert_eq!(symmetry.axes.len(), 1);
+        assert!(symmetry.axes[0].is_point_on_line(&Point::new(0., -5.)));
+        assert!(symmetry.axes[0].is_point_on_line(&Point::new(0., 3.)));
+        assert_eq!(symmetry.rotational_order, 1);
+    }
+
+    /// Tests an L shape with arms of equal length, symmetric about the diagonal through its corner,
+    /// against one with a slightly longer arm, which has no symmetry at all.
+    #[test]
+    fn test_l_shape_symmetry() {
+        let diagonal = Line::new(1., -1., 0.);
+
+        let even = Polygon::new(vec![
+            Point::new(0., 0.),
+            Point::new(2., 0.),
+            Point::new(2., 1.),
+            Point::new(1., 1.),
+            Point::new(1., 2.),
+            Point::new(0., 2.),
+        ]);
+        assert!(even.is_symmetric_about(&diagonal));
+        assert!(!even.is_symmetric_about(&Line::new(1., 0., -1.)));
+
+        let uneven = Polygon::new(vec![
+            Point::new(0., 0.),
+            Point::new(3., 0.),
+            Point::new(3., 1.),
+            Point::new(1., 1.),
+            Point::new(1., 2.),
+            Point::new(0., 2.),
+        ]);
+        let candidates = [
+            Line::new(1., -1., 0.),
+            Line::new(1., 1., -2.),
+            Line::new(1., 0., -1.5),
+            Line::new(0., 1., -1.),
+        ];
+        assert!(candidates.iter().all(|axis| !uneven.is_symmetric_about(axis)));
+        assert!(polygon_symmetry(&uneven.vertices).axes.is_empty());
+
+        // Reflection reverses the orientation of the ring.
+        let reflected = even.reflect_across(&diagonal);
+        assert_eq!(reflected.vertices[1], Point::new(0., 2.));
+        assert_ne!(reflected, even);
+    }
+
+    /// Tests a square's vertices in the self-intersecting order of a bowtie, which are symmetric as
+    /// a set about both diagonals, but as a ring only about the lines through the midpoints of its
+    /// sides.
+    #[test]
+    fn test_bowtie_symmetry() {
+        let bowtie = Polygon::new(vec![
+            Point::new(0., 0.),
+            Point::new(1., 1.),
+            Point::new(1., 0.),
+            Point::new(0., 1.),
+        ]);
+        let diagonals = [Line::new(1., -1., 0.), Line::new(1., 1., -1.)];
+        let set: HashSet<Point> = bowtie.vertices.iter().copied().collect();
+        assert!(diagonals.iter().all(|axis| alg::verify_axis(&set, axis)));
+        assert!(diagonals.iter().all(|axis| !bowtie.is_symmetric_about(axis)));
+
+        assert!(bowtie.is_symmetric_about(&Line::new(1., 0., -0.5)));
+        assert!(bowtie.is_symmetric_about(&Line::new(0., 1., -0.5)));
+    }
+
+    /// Tests that a regular hexagon's six axes are three through opposite vertices and three through
+    /// the midpoints of opposite edges, the x-axis through its first vertex being of the first kind.
+    #[test]
+    fn test_classify_hexagon_axes() {
+        let hexagon = geometry::regular_polygon(6, &Point::new(0., 0.), 2., 0.);
+        let axes = classify_axes(&hexagon);
+        assert_eq!(axes.len(), 6);
+
+        let count = |kind: AxisKind| axes.iter().filter(|(_, k)| *k == kind).count();
+        assert_eq!(count(AxisKind::ThroughVertices), 3);
+        assert_eq!(count(AxisKind::ThroughEdges), 3);
+
+        let kind_through = |p: Point| axes.iter().find(|(l, _)| l.is_point_on_line(&p)).unwrap().1;
+        assert_eq!(kind_through(Point::new(2., 0.)), AxisKind::ThroughVertices);
+        assert_eq!(kind_through(Point::new(0., 3.)), AxisKind::ThroughEdges);
+    }
+
+    /// Tests that an isosceles triangle's axis, through its apex and the midpoint of its base, is
+    /// of mixed kind.
+    #[test]
+    fn test_classify_triangle_axes() {
+        let triangle = HashSet::from([Point::new(-1., 0.), Point::new(1., 0.), Point::new(0., 2.)]);
+        let axes = classify_axes(&triangle);
+        assert_eq!(axes.len(), 1);
+        assert_eq!(axes[0].1, AxisKind::Mixed);
+    }
+}
@@ -0,0 +1,84 @@
+//! Python bindings, enabled by the `python` feature.
+//!
+//! The extension module is built from the `rust-symm-py` crate of this workspace (e.g. with
+//! `maturin`), a `cdylib` whose module adds these functions with `register`, so this crate stays
+//! a plain library. It exposes a `rust_symm` Python module:
+//!
+//! ```python
+//! import rust_symm
+//! lines = rust_symm.find_lines_of_symmetry([(0, 0), (1, 0), (1, 1), (0, 1)])
+//! for line in lines:
+//!     (x0, y0), (x1, y1) = rust_symm.axis_endpoints(line, ((0, 0), (1, 1)))
+//!     plt.plot([x0, x1], [y0, y1])
+//! ```
+
+use crate::alg::get_lines_of_sym_or_empty;
+use crate::geometry;
+use crate::error::SymmError;
+use crate::model::{Line, Point};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::HashSet;
+
+/// Returns the lines of symmetry of a list of `(x, y)` points as `(a, b, c)` coefficient tuples.
+///
+/// Duplicate points are merged. This is the function wrapped by the Python binding, which
+/// raises a `ValueError` in place of the returned error.
+pub fn lines_of_symmetry_from_tuples(points: Vec<(f64, f64)>) -> Result<Vec<(f64, f64, f64)>, SymmError> {
+    let points: HashSet<Point> = points
+        .into_iter()
+        .map(|(x, y)| Point::from_array([x, y]))
+        .collect::<Result<_, _>>()?;
+
+    if points.len() < 2 {
+        return Ok(Vec::new());
+    }
+
+    Ok(get_lines_of_sym_or_empty(&points, None).iter().map(|l| (l.a, l.b, l.c)).collect())
+}
+
+/// Returns the lines of symmetry of a list of `(x, y)` points as `(a, b, c)` tuples, each
+/// describing the line `a*x + b*y + c = 0`. Raises `ValueError` on NaN or infinite coordinates.
+#[pyfunction]
+fn find_lines_of_symmetry(points: Vec<(f64, f64)>) -> PyResult<Vec<(f64, f64, f64)>> {
+    lines_of_symmetry_from_tuples(points).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Returns the endpoints `((x0, y0), (x1, y1))` of the line `(a, b, c)` clipped to the box with
+/// lower-left and upper-right corners `bbox`, or `None` if the line misses the box. Raises
+/// `ValueError` on NaN or infinite coordinates.
+#[pyfunction]
+#[allow(clippy::type_complexity)]
+fn axis_endpoints(
+    line: (f64, f64, f64),
+    bbox: ((f64, f64), (f64, f64)),
+) -> PyResult<Option<((f64, f64), (f64, f64))>> {
+    let to_point =
+        |(x, y): (f64, f64)| Point::from_array([x, y]).map_err(|e| PyValueError::new_err(e.to_string()));
+    let bbox = (to_point(bbox.0)?, to_point(bbox.1)?);
+    let endpoints = geometry::axis_endpoints(&Line::new(line.0, line.1, line.2), bbox);
+    Ok(endpoints.map(|(p, q)| ((p.x, p.y), (q.x, q.y))))
+}
+
+/// Adds the functions of the `rust_symm` Python module to `m`, for the `#[pymodule]` of the
+/// extension module crate.
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(find_lines_of_symmetry, m)?)?;
+    m.add_function(wrap_pyfunction!(axis_endpoints, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_of_symmetry_from_tuples() {
+        let lines = lines_of_symmetry_from_tuples(vec![(0., 0.), (1., 0.), (1., 1.), (0., 1.), (0., 0.)]).unwrap();
+        assert_eq!(lines.len(), 4);
+
+        assert!(lines_of_symmetry_from_tuples(vec![(0., 0.)]).unwrap().is_empty());
+        let non_finite = lines_of_symmetry_from_tuples(vec![(0., 0.), (f64::NAN, 1.)]);
+        assert!(matches!(non_finite, Err(SymmError::NonFinitePoint)));
+    }
+}
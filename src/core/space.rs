@@ -0,0 +1,302 @@
+use crate::config::{self, SymmetryOptions};
+use crate::util;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+/// A point in 3D space with floating-point coordinates.
+///
+/// Like `model::Point`, points are compared using a tolerance-based comparison, and
+/// coordinates must be finite and non-NaN.
+#[derive(Debug, Clone, Copy)]
+pub struct Point3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Point3 {
+    /// Creates a new Point3 with the given coordinates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any coordinate is NaN or infinite.
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        if !x.is_finite() || !y.is_finite() || !z.is_finite() {
+            panic!("Point coordinates must be finite and non-NaN");
+        }
+        Self { x, y, z }
+    }
+
+    /// Returns the Euclidean distance between this point and `other`.
+    pub fn distance(&self, other: &Point3) -> f64 {
+        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2) + (self.z - other.z).powi(2)).sqrt()
+    }
+}
+
+impl PartialEq for Point3 {
+    fn eq(&self, other: &Self) -> bool {
+        util::floats_equal_toler(self.x, other.x)
+            && util::floats_equal_toler(self.y, other.y)
+            && util::floats_equal_toler(self.z, other.z)
+    }
+}
+
+impl Eq for Point3 {}
+
+impl PartialOrd for Point3 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match util::float_partial_cmp_tolerance(&self.x, &other.x) {
+            Some(Ordering::Equal) => match util::float_partial_cmp_tolerance(&self.y, &other.y) {
+                Some(Ordering::Equal) => util::float_partial_cmp_tolerance(&self.z, &other.z),
+                other => other,
+            },
+            other => other,
+        }
+    }
+}
+
+impl Hash for Point3 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.x.to_bits().hash(state);
+        self.y.to_bits().hash(state);
+        self.z.to_bits().hash(state);
+    }
+}
+
+/// A plane in 3D space represented by the equation ax + by + cz + d = 0.
+#[derive(Debug)]
+pub struct Plane {
+    // ax + by + cz + d = 0
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+}
+
+impl Plane {
+    pub fn new(a: f64, b: f64, c: f64, d: f64) -> Self {
+        Self { a, b, c, d }
+    }
+
+    /// Returns the reflection of the given point `p` across this plane.
+    pub fn get_reflected_point(&self, p: &Point3) -> Point3 {
+        let denom = self.a.powi(2) + self.b.powi(2) + self.c.powi(2);
+        if denom == 0.0 {
+            panic!("Invalid plane: a^2 + b^2 + c^2 cannot be zero");
+        }
+
+        let factor = 2.0 * (self.a * p.x + self.b * p.y + self.c * p.z + self.d) / denom;
+
+        Point3::new(p.x - factor * self.a, p.y - factor * self.b, p.z - factor * self.c)
+    }
+
+    /// Checks if the given point lies on this plane, within floating-point tolerance.
+    pub fn is_point_on_plane(&self, p: &Point3) -> bool {
+        util::floats_equal_toler(self.a * p.x + self.b * p.y + self.c * p.z + self.d, 0.0)
+    }
+
+    /// Returns the coefficients compared and hashed by `==` and `Hash`, like those of `Line`: the
+    /// coefficients scaled to a unit normal whose first nonzero component is positive, so that
+    /// a plane and its multiples, e.g. `2z - 2 = 0` and `-z + 1 = 0`, compare equal. A plane with
+    /// a (near) zero or non-finite normal keeps its raw coefficients.
+    fn comparison_coefficients(&self) -> [f64; 4] {
+        let raw = [self.a, self.b, self.c, self.d];
+        let norm = (self.a * self.a + self.b * self.b + self.c * self.c).sqrt();
+        if !norm.is_finite() || norm <= config::EPSILON {
+            return raw;
+        }
+
+        let leading = [self.a, self.b, self.c].into_iter().find(|x| (x / norm).abs() > config::EPSILON).unwrap_or(norm);
+        let norm = norm.copysign(leading);
+        raw.map(|x| x / norm)
+    }
+}
+
+impl PartialEq for Plane {
+    fn eq(&self, other: &Self) -> bool {
+        let (lhs, rhs) = (self.comparison_coefficients(), other.comparison_coefficients());
+        lhs.iter().zip(&rhs).all(|(x, y)| util::floats_equal_toler(*x, *y))
+    }
+}
+
+impl Eq for Plane {}
+
+impl Hash for Plane {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // As for `Line`, the grid is a thousand times the tolerance of `==`, so that equal planes
+        // computed from different pairs of points rarely straddle a grid line.
+        let grid = 1000.0 * config::EPSILON;
+        // Adding zero turns a negative zero, which has other bits than zero, into zero.
+        let round = |x: f64| (x / grid).round() * grid + 0.0;
+        for x in self.comparison_coefficients() {
+            round(x).to_bits().hash(state);
+        }
+    }
+}
+
+/// Returns all planes of symmetry for a given set of 3D points.
+///
+/// This is the 3D analogue of `alg::get_lines_of_sym`: every plane of symmetry is either the
+/// perpendicular bisector plane of a pair of points, or a plane containing all the points.
+/// Bisector planes are enumerated from point pairs, and pairs found to be mirror partners
+/// across a candidate plane are removed from the candidates, since they generate the same plane.
+///
+/// # Notes
+///
+/// - At least two points are required to define a plane of symmetry.
+/// - If the points are coplanar but not collinear, their common plane is included. Collinear
+///   points have infinitely many planes containing them, none of which are included.
+/// - If the input set contains fewer than two points, an empty set is returned and a warning is printed.
+pub fn get_planes_of_sym(points: &HashSet<Point3>) -> HashSet<Plane> {
+    get_planes_of_sym_with(points, &SymmetryOptions::default())
+}
+
+/// Like `get_planes_of_sym`, but without the warning for fewer than two points if `opts.quiet`
+/// is set. The other options don't apply to 3D points.
+pub fn get_planes_of_sym_with(points: &HashSet<Point3>, opts: &SymmetryOptions) -> HashSet<Plane> {
+    let mut planes_set: HashSet<Plane> = HashSet::new();
+
+    if points.len() < 2 {
+        if !opts.quiet {
+            eprintln!("Warning: at least 2 points needed to find planes of symmetry.");
+        }
+        return planes_set;
+    }
+
+    let points_vec: Vec<&Point3> = points.iter().collect();
+    let index: HashMap<&Point3, usize> = points_vec.iter().enumerate().map(|(i, p)| (*p, i)).collect();
+
+    // Pairs of point indices (i < j) that can be used to generate candidate planes.
+    let mut e_plane_generators: HashSet<(usize, usize)> = HashSet::new();
+    for i in 0..points_vec.len() {
+        for j in (i + 1)..points_vec.len() {
+            e_plane_generators.insert((i, j));
+        }
+    }
+
+    while let Some(&(i, j)) = e_plane_generators.iter().next() {
+        let e_plane = get_equidistant_plane(points_vec[i], points_vec[j]);
+        e_plane_generators.remove(&(i, j));
+
+        let mut valid_plane = true;
+        for (k, point) in points_vec.iter().enumerate() {
+            match index.get(&e_plane.get_reflected_point(point)) {
+                Some(&m) => {
+                    // Covered pair; it would generate the same plane.
+                    e_plane_generators.remove(&(k.min(m), k.max(m)));
+                }
+                None => valid_plane = false,
+            }
+        }
+
+        if valid_plane {
+            planes_set.insert(e_plane);
+        }
+    }
+
+    if let Some(common_plane) = coplanar_plane(&points_vec) {
+        planes_set.insert(common_plane);
+    }
+
+    planes_set
+}
+
+/// Returns the plane whose points are equidistant from `p1` and `p2`.
+pub fn get_equidistant_plane(p1: &Point3, p2: &Point3) -> Plane {
+    let a = p2.x - p1.x;
+    let b = p2.y - p1.y;
+    let c = p2.z - p1.z;
+    let d = 0.5 * (p1.x.powi(2) + p1.y.powi(2) + p1.z.powi(2) - p2.x.powi(2) - p2.y.powi(2) - p2.z.powi(2));
+
+    Plane::new(a, b, c, d)
+}
+
+/// Returns the plane containing all the points, if they are coplanar but not collinear.
+fn coplanar_plane(points: &[&Point3]) -> Option<Plane> {
+    let origin = points[0];
+    let u = (points[1].x - origin.x, points[1].y - origin.y, points[1].z - origin.z);
+
+    // Find a point that is not collinear with the first two, giving a non-degenerate normal.
+    let normal = points[2..].iter().find_map(|p| {
+        let v = (p.x - origin.x, p.y - origin.y, p.z - origin.z);
+        let n = (u.1 * v.2 - u.2 * v.1, u.2 * v.0 - u.0 * v.2, u.0 * v.1 - u.1 * v.0);
+        let len = (n.0 * n.0 + n.1 * n.1 + n.2 * n.2).sqrt();
+        util::floats_lt_toler(0.0, len).then_some((n.0 / len, n.1 / len, n.2 / len))
+    })?;
+
+    let d = -(normal.0 * origin.x + normal.1 * origin.y + normal.2 * origin.z);
+    let plane = Plane::new(normal.0, normal.1, normal.2, d);
+
+    points.iter().all(|p| plane.is_point_on_plane(p)).then_some(plane)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cube() -> HashSet<Point3> {
+        let mut corners = HashSet::new();
+        for x in [0., 1.] {
+            for y in [0., 1.] {
+                for z in [0., 1.] {
+                    corners.insert(Point3::new(x, y, z));
+                }
+            }
+        }
+        corners
+    }
+
+    /// Tests that a plane equals its multiples by any nonzero factor, negative or not, and that
+    /// they hash alike, so that a set holds one of them.
+    #[test]
+    fn test_plane_equality_up_to_scale() {
+        let plane = Plane::new(0., 0., 2., -2.);
+        let multiples = [Plane::new(0., 0., 1., -1.), Plane::new(0., 0., -3., 3.), Plane::new(0., 0., 1e6, -1e6)];
+        assert!(multiples.iter().all(|m| *m == plane));
+        assert_ne!(plane, Plane::new(0., 0., 1., 1.));
+        assert_ne!(plane, Plane::new(0., 1., 1., -1.));
+
+        let set: HashSet<Plane> = multiples.into_iter().chain([plane]).collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_plane_reflection() {
+        let plane = Plane::new(0., 0., 2., -2.);
+        assert_eq!(plane.get_reflected_point(&Point3::new(1., 2., 3.)), Point3::new(1., 2., -1.));
+        assert!(plane.is_point_on_plane(&Point3::new(5., -5., 1.)));
+    }
+
+    /// Tests that the corners of a cube have its nine reflective planes: three parallel to the
+    /// faces and six through pairs of opposite edges.
+    #[test]
+    fn test_cube_planes_of_symmetry() {
+        let planes = get_planes_of_sym(&cube());
+        assert_eq!(planes.len(), 9);
+
+        let center = Point3::new(0.5, 0.5, 0.5);
+        assert!(planes.iter().all(|plane| plane.is_point_on_plane(&center)));
+
+        // Exactly one plane contains the edge from (0, 0, 0) to (0, 0, 1).
+        let through_edge = planes
+            .iter()
+            .filter(|p| p.is_point_on_plane(&Point3::new(0., 0., 0.)) && p.is_point_on_plane(&Point3::new(0., 0., 1.)));
+        assert_eq!(through_edge.count(), 1);
+    }
+
+    /// Tests that the common plane of coplanar points is included.
+    #[test]
+    fn test_coplanar_square() {
+        let square = HashSet::from([
+            Point3::new(0., 0., 2.),
+            Point3::new(1., 0., 2.),
+            Point3::new(1., 1., 2.),
+            Point3::new(0., 1., 2.),
+        ]);
+
+        let planes = get_planes_of_sym(&square);
+        assert_eq!(planes.len(), 5);
+        assert!(planes.iter().any(|p| p.is_point_on_plane(&Point3::new(7., -3., 2.))));
+    }
+}
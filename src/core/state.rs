@@ -0,0 +1,239 @@
+use crate::alg::{get_equidistant_line, get_lines_of_sym_with, verify_axis};
+use crate::config::SymmetryOptions;
+use crate::model::{Line, Point};
+use std::collections::{HashSet, VecDeque};
+
+/// A point set together with its lines of symmetry, kept up to date as points are added.
+///
+/// This is intended for interactive use where points arrive one at a time, and avoids
+/// re-running the full search in `alg::get_lines_of_sym` after every change.
+#[derive(Debug, Default)]
+pub struct SymmetryState {
+    points: HashSet<Point>,
+    axes: HashSet<Line>,
+    /// The options of the searches run when the axes are recomputed.
+    opts: SymmetryOptions,
+}
+
+impl SymmetryState {
+    /// Creates an empty state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty state whose axes are recomputed with `alg::get_lines_of_sym_with` as
+    /// configured by `opts`.
+    ///
+    /// The axes gained by adding a point are still checked with `alg::verify_axis`, at the default
+    /// tolerance, so options changing how points are matched or which points are searched apply
+    /// only once the axes are recomputed, e.g. after a removal.
+    pub fn with_options(opts: SymmetryOptions) -> Self {
+        Self { opts, ..Self::default() }
+    }
+
+    /// Creates a state from an existing point set, computing its lines of symmetry.
+    pub fn from_points(points: HashSet<Point>) -> Self {
+        let mut state = Self { points, ..Self::default() };
+        state.axes = state.search();
+        state
+    }
+
+    /// Returns the lines of symmetry of the current points, found from scratch.
+    fn search(&self) -> HashSet<Line> {
+        if self.points.len() < 2 {
+            HashSet::new()
+        } else {
+            get_lines_of_sym_with(&self.points, &self.opts).lines
+        }
+    }
+
+    /// Returns the current point set.
+    pub fn points(&self) -> &HashSet<Point> {
+        &self.points
+    }
+
+    /// Returns the current lines of symmetry.
+    pub fn axes(&self) -> &HashSet<Line> {
+        &self.axes
+    }
+
+    /// Adds a point to the set and updates the lines of symmetry.
+    ///
+    /// An existing axis survives only if the new point lies on it: the rest of the set is
+    /// already symmetric about the axis, so the new point's reflection cannot be an existing
+    /// point. Any new axis must map the new point onto an existing one, so the only new
+    /// candidates are the perpendicular bisectors between the new point and each existing point.
+    ///
+    /// Adding a point that is already in the set does nothing.
+    pub fn add_point(&mut self, p: Point) {
+        if self.points.contains(&p) {
+            return;
+        }
+
+        if self.points.len() < 2 {
+            // Too few points for incremental updates to apply; recompute from scratch.
+            self.points.insert(p);
+            self.axes = self.search();
+            return;
+        }
+
+        self.axes.retain(|axis| axis.is_point_on_line(&p));
+
+        let candidates: Vec<Line> = self.points.iter().map(|q| get_equidistant_line(&p, q)).collect();
+        self.points.insert(p);
+
+        for candidate in candidates {
+            if !self.axes.contains(&candidate) && verify_axis(&self.points, &candidate) {
+                self.axes.insert(candidate);
+            }
+        }
+    }
+
+    /// Removes a point from the set and updates the lines of symmetry.
+    ///
+    /// Removal changes the axes in both directions. An existing axis survives only if the
+    /// removed point lies on it (otherwise its reflection loses its partner), and any pair of
+    /// remaining points may now generate a new axis, e.g. once an asymmetric outlier is removed.
+    /// Since new axes are not restricted to a small candidate set, they are recomputed from
+    /// the remaining points.
+    ///
+    /// Removing a point that is not in the set does nothing.
+    pub fn remove_point(&mut self, p: &Point) {
+        if !self.points.remove(p) {
+            return;
+        }
+
+        self.axes = self.search();
+    }
+}
+
+/// Returns an iterator over the lines of symmetry of the last `window` points of `stream`, with one
+/// item per point of the stream, e.g. for live analysis of a trajectory.
+///
+/// The window is kept in a `SymmetryState` configured by `opts` (see
+/// `SymmetryState::with_options`): each point of the stream is added to it, and once the window is
+/// full the oldest point is removed first. A window of 0 points has no axes. Until the window is
+/// full, the axes are updated incrementally; after, each removal recomputes them. The first items
+/// are the axes of the partial windows, and a point repeated within the window is kept until its
+/// last copy leaves it.
+pub fn sliding_window_axes<I: Iterator<Item = Point>>(
+    stream: I,
+    window: usize,
+    opts: &SymmetryOptions,
+) -> impl Iterator<Item = HashSet<Line>> {
+    let mut state = SymmetryState::with_options(opts.clone());
+    let mut recent: VecDeque<Point> = VecDeque::with_capacity(window);
+
+    stream.map(move |p| {
+        if window == 0 {
+            return HashSet::new();
+        }
+        if recent.len() == window {
+            if let Some(oldest) = recent.pop_front() {
+                if oldest != p && !recent.contains(&oldest) {
+                    state.remove_point(&oldest);
+                }
+            }
+        }
+        recent.push_back(p);
+        state.add_point(p);
+
+        state.axes().iter().copied().collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alg::get_lines_of_sym;
+
+    /// Tests that building a square point by point yields the expected number of axes
+    /// at each step, and that the result matches a full recomputation.
+    #[test]
+    fn test_add_point_builds_square() {
+        let mut state = SymmetryState::new();
+
+        state.add_point(Point::new(0., 0.));
+        assert_eq!(state.axes().len(), 0);
+
+        // Two points: the perpendicular bisector.
+        state.add_point(Point::new(1., 0.));
+        assert!(state.axes().contains(&Line::new(1., 0., -0.5)));
+
+        // Isosceles right triangle: only the diagonal x = y.
+        state.add_point(Point::new(1., 1.));
+        assert_eq!(state.axes().len(), 1);
+        assert!(state.axes().iter().all(|axis| axis.is_point_on_line(&Point::new(0.5, 0.5))));
+
+        // Full square: four axes.
+        state.add_point(Point::new(0., 1.));
+        assert_eq!(state.axes().len(), 4);
+
+        // Adding an existing point changes nothing.
+        state.add_point(Point::new(0., 1.));
+        assert_eq!(state.points().len(), 4);
+        assert_eq!(state.axes().len(), 4);
+
+        let full = get_lines_of_sym(state.points(), None).unwrap();
+        assert_eq!(full.len(), state.axes().len());
+        assert!(full.iter().all(|l| state.axes().iter().any(|m| l.approx_eq(m, 1e-9))));
+    }
+
+    /// Tests that removing a square's vertex leaves only the diagonal through it and the opposite
+    /// vertex, and that removing an on-axis point keeps that axis.
+    #[test]
+    fn test_remove_point_from_square() {
+        let mut state = SymmetryState::from_points(HashSet::from([
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+            Point::new(0.5, 0.5),
+        ]));
+        assert_eq!(state.axes().len(), 4);
+
+        // Removing the center, which lies on every axis, keeps all four.
+        state.remove_point(&Point::new(0.5, 0.5));
+        assert_eq!(state.axes().len(), 4);
+
+        // Removing a vertex leaves only the diagonal y = x, which swaps (1, 0) and (0, 1).
+        state.remove_point(&Point::new(0., 0.));
+        assert_eq!(state.axes().len(), 1);
+        let diagonal = state.axes().iter().next().unwrap();
+        assert!(diagonal.approx_eq(&Line::new(1., -1., 0.), 1e-9));
+
+        // Removing a missing point does nothing.
+        state.remove_point(&Point::new(5., 5.));
+        assert_eq!(state.points().len(), 3);
+
+        state.remove_point(&Point::new(1., 0.));
+        state.remove_point(&Point::new(1., 1.));
+        assert!(state.axes().is_empty());
+    }
+
+    /// Tests a stream of scattered points followed by a square's corners, whose window of four
+    /// points becomes symmetric once the last corner arrives and stays so as they repeat.
+    #[test]
+    fn test_sliding_window_axes() {
+        let stream = [
+            Point::new(5., 1.),
+            Point::new(-3., 2.),
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(1., 1.),
+            Point::new(0., 1.),
+            Point::new(0., 0.),
+            Point::new(7., 3.),
+        ];
+        let opts = SymmetryOptions::default();
+        let axes: Vec<HashSet<Line>> = sliding_window_axes(stream.into_iter(), 4, &opts).collect();
+        let counts: Vec<usize> = axes.iter().map(HashSet::len).collect();
+        // Two points have two axes; the square is complete at the sixth point, still complete at
+        // the seventh, which repeats a corner, and is broken up at the eighth.
+        assert_eq!(counts, [0, 2, 0, 0, 0, 4, 4, 0]);
+
+        let square: HashSet<Point> = stream[2..6].iter().copied().collect();
+        assert_eq!(axes[5], get_lines_of_sym(&square, None).unwrap());
+        assert!(sliding_window_axes(stream.into_iter(), 0, &opts).all(|axes| axes.is_empty()));
+    }
+}
@@ -1,17 +1,25 @@
-use std::cmp::Ordering;
-use crate::config;
+//! Approximate floating-point comparisons, the crate's canonical helpers for comparing
+//! coordinates and distances.
+//!
+//! Two values are equal if they differ by less than the tolerance, and one is less than the other
+//! if it is smaller by more than the tolerance. Values exactly the tolerance apart are neither
+//! equal nor ordered by `floats_equal_toler` and `floats_lt_toler`; `floats_cmp_toler` treats them
+//! as equal. The `_toler` helpers without a tolerance argument use the scalar's `TOLERANCE`.
 
+use core::cmp::Ordering;
+use crate::config::Scalar;
 
 /// Returns the type name of a given value as a static string slice.
 pub fn type_of<T>(_: &T) -> &'static str {
-    std::any::type_name::<T>()
+    core::any::type_name::<T>()
 }
 
-pub fn float_partial_cmp_tolerance(a: &f64, b: &f64) -> Option<Ordering> {
+/// Compares two values within `T::TOLERANCE`, or returns `None` if either isn't finite.
+pub fn float_partial_cmp_tolerance<T: Scalar>(a: &T, b: &T) -> Option<Ordering> {
     if a.is_finite() && b.is_finite() {
-        let diff = (a - b).abs();
+        let diff = (*a - *b).abs();
 
-        if diff < config::EPSILON {
+        if diff < T::TOLERANCE {
             return Some(Ordering::Equal);
         } else if a < b {
             return Some(Ordering::Less);
@@ -23,12 +31,58 @@ pub fn float_partial_cmp_tolerance(a: &f64, b: &f64) -> Option<Ordering> {
     None
 }
 
-pub fn floats_equal_toler(a: f64, b: f64) -> bool {
-    (a - b).abs() < config::EPSILON
+/// Checks whether `a` and `b` differ by less than `T::TOLERANCE`.
+pub fn floats_equal_toler<T: Scalar>(a: T, b: T) -> bool {
+    (a - b).abs() < T::TOLERANCE
 }
 
-pub fn floats_lt_toler(a: f64, b: f64) -> bool {
-    b - a > config::EPSILON
+/// Checks whether `a` and `b` are equal within a tolerance relative to their magnitude, or within
+/// `abs_tol`: whether `|a - b| <= max(rel_tol * max(|a|, |b|), abs_tol)`, like Python's
+/// `math.isclose`.
+///
+/// Unlike `floats_equal_toler`, whose tolerance is absolute, this compares large values as
+/// precisely as their representation allows, while `abs_tol` still equates values near zero.
+/// Equal infinities are equal, and NaN is equal to nothing.
+pub fn floats_equal_rel<T: Scalar>(a: T, b: T, rel_tol: T, abs_tol: T) -> bool {
+    if a == b {
+        return true;
+    }
+
+    (a - b).abs() <= (rel_tol * a.abs().max(b.abs())).max(abs_tol)
+}
+
+/// Checks whether `a` is less than `b` by more than `T::TOLERANCE`.
+pub fn floats_lt_toler<T: Scalar>(a: T, b: T) -> bool {
+    b - a > T::TOLERANCE
+}
+
+/// Checks whether `a` is greater than `b` by more than `tol`.
+pub fn floats_gt_toler<T: Scalar>(a: T, b: T, tol: T) -> bool {
+    a - b > tol
+}
+
+/// Checks whether `a` is at most `b`, i.e. not greater than `b` by more than `tol`.
+pub fn floats_le_toler<T: Scalar>(a: T, b: T, tol: T) -> bool {
+    !floats_gt_toler(a, b, tol)
+}
+
+/// Checks whether `a` is at least `b`, i.e. not less than `b` by more than `tol`.
+pub fn floats_ge_toler<T: Scalar>(a: T, b: T, tol: T) -> bool {
+    !floats_gt_toler(b, a, tol)
+}
+
+/// Compares two finite values within `tol`.
+///
+/// Values at most `tol` apart compare `Equal`. Unlike `float_partial_cmp_tolerance`, this doesn't
+/// check for non-finite inputs; NaN compares `Equal` to everything.
+pub fn floats_cmp_toler<T: Scalar>(a: T, b: T, tol: T) -> Ordering {
+    if floats_gt_toler(b, a, tol) {
+        Ordering::Less
+    } else if floats_gt_toler(a, b, tol) {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
 }
 
 
@@ -44,4 +98,48 @@ mod tests {
         let y: i32 = 0;
         assert_eq!(type_of(&y), "i32");
     }
+
+    /// Tests relative equality at large and small magnitudes, against the absolute tolerance.
+    #[test]
+    fn test_floats_equal_rel() {
+        // Large values one apart: far outside the absolute tolerance, but relatively close.
+        assert!(floats_equal_rel(1e12, 1e12 + 1., 1e-9, 0.));
+        assert!(!floats_equal_toler(1e12, 1e12 + 1.));
+        assert!(!floats_equal_rel(1e12, 1e12 + 1e4, 1e-9, 1e-9));
+
+        // Small values twice as large as each other: only equal within the absolute tolerance.
+        assert!(!floats_equal_rel(1e-12, 2e-12, 1e-9, 0.));
+        assert!(floats_equal_rel(1e-12, 2e-12, 1e-9, 1e-9));
+        assert!(floats_equal_rel(0., 1e-10, 1e-9, 1e-9));
+
+        assert!(floats_equal_rel(f64::INFINITY, f64::INFINITY, 1e-9, 0.));
+        assert!(!floats_equal_rel(f64::NAN, f64::NAN, 1e-9, 1.));
+        assert!(floats_equal_rel(1e6f32, 1e6 + 0.05, 1e-6, 0.));
+    }
+
+    /// Tests the explicit-tolerance comparisons just inside, at, and just outside the tolerance.
+    #[test]
+    fn test_comparisons_at_tolerance_boundary() {
+        let tol = 0.5;
+
+        // Exactly `tol` apart: not strictly greater, so within tolerance in both directions.
+        assert!(!floats_gt_toler(1.5, 1.0, tol));
+        assert!(floats_le_toler(1.5, 1.0, tol));
+        assert!(floats_ge_toler(1.0, 1.5, tol));
+        assert_eq!(floats_cmp_toler(1.5, 1.0, tol), Ordering::Equal);
+
+        // Just inside the tolerance.
+        assert!(!floats_gt_toler(1.4, 1.0, tol));
+        assert!(floats_le_toler(1.4, 1.0, tol));
+        assert!(floats_ge_toler(1.0, 1.4, tol));
+        assert_eq!(floats_cmp_toler(1.0, 1.4, tol), Ordering::Equal);
+
+        // Just outside the tolerance.
+        assert!(floats_gt_toler(1.6, 1.0, tol));
+        assert!(!floats_le_toler(1.6, 1.0, tol));
+        assert!(!floats_ge_toler(1.0, 1.6, tol));
+        assert!(floats_ge_toler(1.6, 1.0, tol));
+        assert_eq!(floats_cmp_toler(1.6, 1.0, tol), Ordering::Greater);
+        assert_eq!(floats_cmp_toler(1.0, 1.6, tol), Ordering::Less);
+    }
 }
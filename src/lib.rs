@@ -1,6 +1,20 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+// The test harness needs the standard library even when the crate itself doesn't use it.
+#[cfg(all(test, not(feature = "std")))]
+extern crate std;
+
+#[path = "core/collections.rs"]
+pub mod collections;
+
 #[path = "core/config.rs"]
 pub mod config;
 
+#[path = "core/error.rs"]
+pub mod error;
+
 #[path = "core/model.rs"]
 pub mod model;
 
@@ -9,3 +23,46 @@ pub mod alg;
 
 #[path = "core/util.rs"]
 pub mod util;
+
+#[path = "core/cache.rs"]
+pub mod cache;
+
+#[cfg(feature = "std")]
+#[path = "core/state.rs"]
+pub mod state;
+
+#[cfg(feature = "std")]
+#[path = "core/polygon.rs"]
+pub mod polygon;
+
+#[cfg(feature = "std")]
+#[path = "core/geometry.rs"]
+pub mod geometry;
+
+#[cfg(feature = "std")]
+#[path = "core/space.rs"]
+pub mod space;
+
+#[cfg(feature = "std")]
+#[path = "core/pattern.rs"]
+pub mod pattern;
+
+#[cfg(feature = "geo")]
+#[path = "core/geo_interop.rs"]
+pub mod geo_interop;
+
+#[cfg(feature = "nalgebra")]
+#[path = "core/nalgebra_interop.rs"]
+pub mod nalgebra_interop;
+
+#[cfg(feature = "std")]
+#[path = "core/export.rs"]
+pub mod export;
+
+#[cfg(feature = "std")]
+#[path = "core/csv.rs"]
+pub mod csv;
+
+#[cfg(feature = "python")]
+#[path = "core/python.rs"]
+pub mod python;
@@ -1,9 +1,7 @@
-use std::collections::HashSet;
-
-use rust_symm::{alg::get_lines_of_sym, model::Point};
+use rust_symm::{alg::get_lines_of_sym, collections::HashSet, model::Point};
 
 fn main() {
-    let test_cases = vec![
+    let test_cases = [
         HashSet::from([
             Point::new(1.0, 0.),
             Point::new(0., 1.),
@@ -26,14 +24,18 @@ fn main() {
     ];
 
     for (i, case) in test_cases.iter().enumerate() {
-        let lines = get_lines_of_sym(case, Some(true));
         println!("Test case {}:", i + 1);
         println!("  Points: {:?}", case);
-        for line in &lines {
-            println!(
-                "  Line: a = {:.4}, b = {:.4}, c = {:.4}",
-                line.a, line.b, line.c
-            );
+        match get_lines_of_sym(case, Some(true)) {
+            Ok(lines) => {
+                for line in &lines {
+                    println!(
+                        "  Line: a = {:.4}, b = {:.4}, c = {:.4}",
+                        line.a, line.b, line.c
+                    );
+                }
+            }
+            Err(e) => println!("  Error: {}", e),
         }
         println!();
     }